@@ -0,0 +1,47 @@
+#!/usr/bin/env bash
+# Downloads the ImageMagick binary bundled as the `magick` sidecar.
+# Usage: scripts/download-magick.sh [target-triple]
+set -euo pipefail
+
+MAGICK_VERSION="${MAGICK_VERSION:-7.1.1-38}"
+TARGET="${1:-$(rustc -vV | sed -n 's/^host: //p')}"
+ROOT="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
+BIN_DIR="$ROOT/binaries"
+WORK_DIR="$(mktemp -d)"
+trap 'rm -rf "$WORK_DIR"' EXIT
+
+mkdir -p "$BIN_DIR"
+
+case "$TARGET" in
+  x86_64-pc-windows-msvc)
+    url="https://imagemagick.org/archive/binaries/ImageMagick-${MAGICK_VERSION}-portable-Q16-x64.zip"
+    curl -fL "$url" -o "$WORK_DIR/magick.zip"
+    unzip -q -j "$WORK_DIR/magick.zip" "magick.exe" -d "$WORK_DIR"
+    mv "$WORK_DIR/magick.exe" "$BIN_DIR/magick-$TARGET.exe"
+    ;;
+  i686-pc-windows-msvc)
+    url="https://imagemagick.org/archive/binaries/ImageMagick-${MAGICK_VERSION}-portable-Q16-x86.zip"
+    curl -fL "$url" -o "$WORK_DIR/magick.zip"
+    unzip -q -j "$WORK_DIR/magick.zip" "magick.exe" -d "$WORK_DIR"
+    mv "$WORK_DIR/magick.exe" "$BIN_DIR/magick-$TARGET.exe"
+    ;;
+  x86_64-unknown-linux-gnu)
+    curl -fL "https://imagemagick.org/archive/binaries/magick" -o "$BIN_DIR/magick-$TARGET"
+    chmod +x "$BIN_DIR/magick-$TARGET"
+    ;;
+  *-apple-darwin)
+    # ImageMagick does not publish a self-contained macOS binary; reuse a local install.
+    source="$(command -v magick || true)"
+    if [ -z "$source" ]; then
+      echo "Install ImageMagick (e.g. brew install imagemagick) before bundling for $TARGET" >&2
+      exit 1
+    fi
+    cp "$source" "$BIN_DIR/magick-$TARGET"
+    ;;
+  *)
+    echo "Unsupported target: $TARGET" >&2
+    exit 1
+    ;;
+esac
+
+echo "ImageMagick sidecar written to $BIN_DIR for $TARGET"
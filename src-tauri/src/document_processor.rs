@@ -1 +1,34 @@
+pub mod annotations;
+pub mod anonymize;
+pub mod batch;
+pub mod bookmarks;
+pub mod cache;
+pub mod cancellation;
+pub mod compliance;
+pub mod download;
+pub mod duplicates;
+pub mod error;
+pub mod export;
+pub mod fonts;
+pub mod forms;
+pub mod health;
+pub mod links;
+pub mod magick;
+pub mod manager;
+pub mod manifest;
+pub mod merge;
+pub mod ocr;
+pub mod options;
+pub mod outline;
+pub mod pages;
+pub mod processing_log;
+pub mod recents;
+pub mod redact;
+pub mod renderer;
+pub mod search;
 pub mod selector;
+pub mod settings;
+pub mod statistics;
+pub mod viewports;
+pub mod watcher;
+pub mod watermark;
@@ -0,0 +1,101 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+use super::cache::create_output_paths;
+use super::error::Error;
+use super::options::ProcessingOptions;
+
+const ANNOTATIONS_FILE_NAME: &str = "annotations.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AnnotationKind {
+    Highlight,
+    Rectangle,
+    Text,
+    FreeHand,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnnotationData {
+    id: String,
+    kind: AnnotationKind,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    color: String,
+    text: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PageAnnotation {
+    page: u32,
+    #[serde(flatten)]
+    annotation: AnnotationData,
+}
+
+#[tauri::command]
+pub fn add_annotation(
+    app: AppHandle,
+    path: PathBuf,
+    page: u32,
+    annotation: AnnotationData,
+) -> Result<(), Error> {
+    let mut annotations = load_annotations(&app, &path)?;
+    annotations.retain(|existing| existing.annotation.id != annotation.id);
+    annotations.push(PageAnnotation { page, annotation });
+    save_annotations(&app, &path, &annotations).map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub fn get_annotations(
+    app: AppHandle,
+    path: PathBuf,
+    page: u32,
+) -> Result<Vec<AnnotationData>, Error> {
+    let annotations = load_annotations(&app, &path)?
+        .into_iter()
+        .filter(|annotation| annotation.page == page)
+        .map(|annotation| annotation.annotation)
+        .collect();
+    Ok(annotations)
+}
+
+#[tauri::command]
+pub fn delete_annotation(app: AppHandle, path: PathBuf, id: String) -> Result<(), Error> {
+    let mut annotations = load_annotations(&app, &path)?;
+    annotations.retain(|annotation| annotation.annotation.id != id);
+    save_annotations(&app, &path, &annotations).map_err(Error::CommandError)
+}
+
+fn load_annotations(app: &AppHandle, path: &Path) -> Result<Vec<PageAnnotation>> {
+    let file = annotations_file(app, path)?;
+    if !file.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&file).context("Failed to read annotations")?;
+    serde_json::from_str(&contents).context("Failed to parse annotations")
+}
+
+fn save_annotations(app: &AppHandle, path: &Path, annotations: &[PageAnnotation]) -> Result<()> {
+    let file = annotations_file(app, path)?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).context("Failed to create data directory")?;
+    }
+
+    let contents = serde_json::to_string_pretty(annotations)?;
+    fs::write(&file, contents).context("Failed to write annotations")
+}
+
+fn annotations_file(app: &AppHandle, path: &Path) -> Result<PathBuf> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(app, path, &ProcessingOptions::default())?;
+    Ok(data_dir.join(ANNOTATIONS_FILE_NAME))
+}
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use lopdf::{Dictionary, Document, Object};
+
+use super::error::Error;
+use super::selector::load_pdf_blocking;
+
+#[tauri::command]
+pub async fn anonymize_document(path: PathBuf, output: PathBuf) -> Result<(), Error> {
+    let mut document = load_pdf_blocking(path.clone()).await?;
+    if document.is_encrypted() {
+        return Err(Error::EncryptedDocument);
+    }
+
+    let removed_fields = clear_info_dictionary(&mut document);
+    let removed_metadata = remove_metadata_streams(&mut document);
+    document.prune_objects();
+    document
+        .save(&output)
+        .context("Failed to save anonymized document")?;
+
+    log::info!(
+        "Anonymized {} into {}: removed info fields [{}] and {} XMP metadata streams",
+        path.display(),
+        output.display(),
+        removed_fields.join(", "),
+        removed_metadata
+    );
+    Ok(())
+}
+
+fn clear_info_dictionary(document: &mut Document) -> Vec<String> {
+    let info_id = document
+        .trailer
+        .get(b"Info")
+        .and_then(Object::as_reference)
+        .ok();
+    let info = match info_id {
+        Some(id) => document.get_object_mut(id).and_then(Object::as_dict_mut),
+        None => document
+            .trailer
+            .get_mut(b"Info")
+            .and_then(Object::as_dict_mut),
+    };
+    let Ok(info) = info else {
+        return Vec::new();
+    };
+
+    let fields = info
+        .iter()
+        .map(|(key, _)| String::from_utf8_lossy(key).into_owned())
+        .collect();
+    *info = Dictionary::new();
+    fields
+}
+
+// XMP packets can hang off the catalog as well as individual pages, images and fonts.
+fn remove_metadata_streams(document: &mut Document) -> usize {
+    let mut removed = 0;
+    for object in document.objects.values_mut() {
+        let dictionary = match object {
+            Object::Dictionary(dictionary) => dictionary,
+            Object::Stream(stream) => &mut stream.dict,
+            _ => continue,
+        };
+        if dictionary.remove(b"Metadata").is_some() {
+            removed += 1;
+        }
+    }
+    removed
+}
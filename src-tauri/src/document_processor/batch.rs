@@ -0,0 +1,82 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use super::cancellation::CancellationToken;
+use super::error::Error;
+use super::options::ProcessingOptions;
+use super::selector::preparation;
+
+const BATCH_SESSION_ID: &str = "batch";
+
+#[derive(Debug, Clone, Serialize)]
+struct BatchProgress {
+    completed: usize,
+    total: usize,
+    current_path: String,
+}
+
+#[tauri::command]
+pub async fn process_directory(
+    app: AppHandle,
+    dir: PathBuf,
+    options: ProcessingOptions,
+) -> Result<Vec<String>, Error> {
+    let documents = list_pdf_files(&dir)?;
+    let total = documents.len();
+    let cancel_token = CancellationToken::default();
+    let mut failed = Vec::new();
+
+    for (index, document) in documents.into_iter().enumerate() {
+        let current_path = document.display().to_string();
+        if let Err(err) = preparation(
+            app.clone(),
+            document,
+            BATCH_SESSION_ID,
+            options.clone(),
+            &cancel_token,
+        )
+        .await
+        {
+            log::error!("Failed to process {}: {}", current_path, err);
+            failed.push(current_path.clone());
+        }
+
+        app.emit(
+            "batch_progress",
+            BatchProgress {
+                completed: index + 1,
+                total,
+                current_path,
+            },
+        )
+        .map_err(anyhow::Error::from)?;
+    }
+
+    Ok(failed)
+}
+
+#[tauri::command]
+pub fn list_pdfs_in_dir(dir: PathBuf) -> Result<Vec<PathBuf>, Error> {
+    Ok(list_pdf_files(&dir)?)
+}
+
+fn list_pdf_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut documents: Vec<PathBuf> = fs::read_dir(dir)
+        .context("Failed to read directory")?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("pdf"))
+        })
+        .collect();
+    documents.sort();
+    Ok(documents)
+}
@@ -0,0 +1,57 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use tauri::AppHandle;
+
+use super::cache::create_output_paths;
+use super::error::Error;
+use super::options::ProcessingOptions;
+
+const BOOKMARKS_FILE_NAME: &str = "bookmarks.json";
+
+#[tauri::command]
+pub fn add_bookmark(app: AppHandle, path: PathBuf, page: u32) -> Result<(), Error> {
+    if page == 0 {
+        return Err(anyhow!("Invalid bookmark page {}", page).into());
+    }
+
+    let mut bookmarks = load_bookmarks(&app, &path)?;
+    if let Err(index) = bookmarks.binary_search(&page) {
+        bookmarks.insert(index, page);
+    }
+    save_bookmarks(&app, &path, &bookmarks).map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub fn get_bookmarks(app: AppHandle, path: PathBuf) -> Result<Vec<u32>, Error> {
+    load_bookmarks(&app, &path).map_err(Error::CommandError)
+}
+
+fn load_bookmarks(app: &AppHandle, path: &Path) -> Result<Vec<u32>> {
+    let file = bookmarks_file(app, path)?;
+    if !file.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&file).context("Failed to read bookmarks")?;
+    serde_json::from_str(&contents).context("Failed to parse bookmarks")
+}
+
+fn save_bookmarks(app: &AppHandle, path: &Path, bookmarks: &[u32]) -> Result<()> {
+    let file = bookmarks_file(app, path)?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).context("Failed to create data directory")?;
+    }
+
+    let contents = serde_json::to_string_pretty(bookmarks)?;
+    fs::write(&file, contents).context("Failed to write bookmarks")
+}
+
+fn bookmarks_file(app: &AppHandle, path: &Path) -> Result<PathBuf> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(app, path, &ProcessingOptions::default())?;
+    Ok(data_dir.join(BOOKMARKS_FILE_NAME))
+}
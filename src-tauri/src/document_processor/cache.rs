@@ -0,0 +1,432 @@
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::UNIX_EPOCH,
+};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+
+use super::cancellation::CancellationToken;
+use super::error::Error;
+use super::manager::document_manager;
+use super::manifest::{modified_at, CacheManifest, MANIFEST_FILE_NAME};
+use super::options::{ImageFormat, ProcessingOptions};
+use super::renderer::{PageRenderer, RenderEvents};
+use super::selector::{page_number_u32, process_pages};
+use super::settings::current_settings;
+
+const CACHE_DIR_NAME: &str = "cache";
+const BLANK_MARKER_EXTENSION: &str = "blank";
+const INVERTED_PAGE_SUFFIX: &str = "_inverted";
+const RENDER_EXTENSIONS: [&str; 4] = ["webp", "png", "jpg", BLANK_MARKER_EXTENSION];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheInfo {
+    data_dir: PathBuf,
+    document_path: Option<String>,
+    page_count: usize,
+    total_bytes: u64,
+    last_rendered: Option<u64>,
+    format: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheVerificationReport {
+    page: u32,
+    expected_hash: String,
+    actual_hash: String,
+    ok: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cache {
+    data_dir: PathBuf,
+    manifest: CacheManifest,
+}
+
+impl Cache {
+    pub fn new(data_dir: PathBuf, manifest: CacheManifest) -> Self {
+        Self { data_dir, manifest }
+    }
+
+    pub fn load(data_dir: &Path) -> Result<Option<Self>> {
+        Ok(CacheManifest::load(data_dir)?
+            .map(|manifest| Self::new(data_dir.to_path_buf(), manifest)))
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    pub fn manifest(&self) -> &CacheManifest {
+        &self.manifest
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.manifest.page_count()
+    }
+
+    pub fn is_valid(&self, pdf_path: &Path) -> bool {
+        let cached = match CacheManifest::load(&self.data_dir) {
+            Ok(Some(cached)) => cached,
+            Ok(None) => {
+                log::warn!("Cache manifest is missing.");
+                return false;
+            }
+            Err(err) => {
+                log::warn!("Failed to read cache manifest: {}", err);
+                return false;
+            }
+        };
+        let modified = modified_at(pdf_path).ok();
+        if !cached.matches(&self.manifest) || modified != Some(cached.pdf_modified_at()) {
+            log::warn!("Cache manifest is outdated.");
+            return false;
+        }
+
+        let page_count = self.page_count();
+        match count_webp_files(&self.data_dir, self.manifest.format()) {
+            Ok(count) if count == page_count => true,
+            Ok(count) => {
+                log::warn!(
+                    "Mismatch in page count. PDF has {} pages, but found {} webp files.",
+                    page_count,
+                    count
+                );
+                false
+            }
+            Err(err) => {
+                log::warn!("Failed to count cached pages: {}", err);
+                false
+            }
+        }
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        remove_cache_artifacts(&self.data_dir)?;
+        Ok(())
+    }
+
+    pub fn page_path(&self, page: usize, format: ImageFormat) -> PathBuf {
+        page_output_path(&self.data_dir, page, format)
+    }
+
+    pub fn blank_marker_path(&self, page: usize) -> PathBuf {
+        blank_marker_path(&self.data_dir, page)
+    }
+
+    pub fn record_page_hash(&mut self, page: usize) -> Result<()> {
+        let hash = hash_file(&self.page_path(page, self.manifest.format()))?;
+        self.manifest.set_page_hash(page_number_u32(page)?, hash);
+        Ok(())
+    }
+
+    pub fn refresh_modified_at(&mut self, pdf_path: &Path) -> Result<()> {
+        self.manifest.refresh_modified_at(pdf_path)
+    }
+
+    pub fn write_manifest(&self) -> Result<()> {
+        self.manifest.save(&self.data_dir)
+    }
+}
+
+#[tauri::command]
+pub fn get_cache_dir(app: AppHandle, path: PathBuf) -> Result<PathBuf, Error> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(&app, &path, &ProcessingOptions::default())?;
+    Ok(data_dir)
+}
+
+#[tauri::command]
+pub fn clear_cache(app: AppHandle, path: PathBuf) -> Result<(), Error> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(&app, &path, &ProcessingOptions::default())?;
+    remove_cache_artifacts(&data_dir)?;
+    document_manager(&app)?.forget_cache(&data_dir);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn verify_cache(app: AppHandle, path: PathBuf) -> Result<Vec<CacheVerificationReport>, Error> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(&app, &path, &ProcessingOptions::default())?;
+    let Some(cache) = Cache::load(&data_dir)? else {
+        return Err(Error::IoError {
+            path: data_dir,
+            reason: "Document has not been cached yet".to_string(),
+        });
+    };
+
+    let reports = cache
+        .manifest()
+        .page_hashes()
+        .iter()
+        .map(|(&page, expected_hash)| {
+            let output = cache.page_path(page as usize, cache.manifest().format());
+            let actual_hash = hash_file(&output).unwrap_or_else(|err| {
+                log::warn!("Failed to hash {}: {}", output.display(), err);
+                String::new()
+            });
+            if &actual_hash != expected_hash {
+                log::warn!("Cached page {} of {} is corrupted", page, path.display());
+            }
+            CacheVerificationReport {
+                page,
+                ok: &actual_hash == expected_hash,
+                expected_hash: expected_hash.clone(),
+                actual_hash,
+            }
+        })
+        .collect();
+    Ok(reports)
+}
+
+#[tauri::command]
+pub fn clear_all_caches(app: AppHandle) -> Result<usize, Error> {
+    let cache_root = cache_root(&app)?;
+    let mut removed = 0;
+    if cache_root.exists() {
+        for entry in fs::read_dir(&cache_root).map_err(|err| Error::io(&cache_root, err))? {
+            let data_dir = entry.map_err(|err| Error::io(&cache_root, err))?.path();
+            if data_dir.is_dir() && remove_cache_artifacts(&data_dir)? > 0 {
+                document_manager(&app)?.forget_cache(&data_dir);
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+#[tauri::command]
+pub fn get_cache_info(app: AppHandle, path: PathBuf) -> Result<CacheInfo, Error> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(&app, &path, &ProcessingOptions::default())?;
+    if !data_dir.exists() {
+        return Err(Error::IoError {
+            path: data_dir,
+            reason: "Document has not been cached yet".to_string(),
+        });
+    }
+    Ok(cache_info(&data_dir)?)
+}
+
+#[tauri::command]
+pub fn get_all_caches(app: AppHandle) -> Result<Vec<CacheInfo>, Error> {
+    let cache_root = cache_root(&app)?;
+    if !cache_root.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut caches = Vec::new();
+    for entry in fs::read_dir(&cache_root).map_err(|err| Error::io(&cache_root, err))? {
+        let data_dir = entry.map_err(|err| Error::io(&cache_root, err))?.path();
+        if data_dir.is_dir() {
+            caches.push(cache_info(&data_dir)?);
+        }
+    }
+    caches.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes));
+    Ok(caches)
+}
+
+pub fn cache_root(app: &AppHandle) -> Result<PathBuf> {
+    if let Some(cache_dir) = current_settings(app).cache_dir {
+        return Ok(cache_dir.join(CACHE_DIR_NAME));
+    }
+    Ok(app
+        .path()
+        .app_data_dir()
+        .context("Failed to resolve app data directory")?
+        .join(CACHE_DIR_NAME))
+}
+
+pub fn cached_document(app: &AppHandle, path: &Path) -> Result<Cache, Error> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(app, path, &ProcessingOptions::default())?;
+    match Cache::load(&data_dir)? {
+        Some(cache) => Ok(cache),
+        None => Err(Error::IoError {
+            path: data_dir,
+            reason: "Document has not been cached yet".to_string(),
+        }),
+    }
+}
+
+pub fn create_output_paths(
+    app: &AppHandle,
+    path: &Path,
+    options: &ProcessingOptions,
+) -> Result<(PathBuf, PathBuf)> {
+    let canonical_path = dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let digest = Sha256::digest(canonical_path.to_string_lossy().as_bytes());
+    let data_dir = cache_root(app)?.join(format!("{:x}", digest));
+    let output_file_name = data_dir
+        .join("page")
+        .with_extension(options.format.extension());
+    Ok((data_dir, output_file_name))
+}
+
+pub async fn handle_existing_data_dir(
+    cache: &mut Cache,
+    pdf_path: &Path,
+    events: &dyn RenderEvents,
+    options: &ProcessingOptions,
+    renderer: Arc<dyn PageRenderer>,
+    cancel_token: &CancellationToken,
+) -> Result<()> {
+    log::info!("Data dir already exists. Verifying...");
+    if cache.is_valid(pdf_path) {
+        log::info!("All pages are already processed. Emitting existing images.");
+        emit_existing_images(events, cache, options.format)
+    } else {
+        log::warn!("Re-rendering all pages.");
+        remove_cache_artifacts(cache.data_dir())?;
+        process_pages(
+            events,
+            &pdf_path.to_string_lossy(),
+            cache,
+            options,
+            renderer,
+            cancel_token,
+        )
+        .await
+    }
+}
+
+fn emit_existing_images(
+    events: &dyn RenderEvents,
+    cache: &Cache,
+    format: ImageFormat,
+) -> Result<()> {
+    let page_count = cache.page_count();
+    for page in 1..=page_count {
+        if cache.blank_marker_path(page).exists() {
+            events.blank_page_skipped(page)?;
+        } else {
+            events.page_rendered(page, &cache.page_path(page, format), format.mime_type())?;
+        }
+        events.progress(page, page_count)?;
+    }
+    Ok(())
+}
+
+pub fn page_output_path(data_dir: &Path, page_number: usize, format: ImageFormat) -> PathBuf {
+    data_dir.join(format!("{}.{}", page_number, format.extension()))
+}
+
+// Negated renders live next to the regular pages so night mode can switch without re-rendering.
+pub fn inverted_page_output_path(
+    data_dir: &Path,
+    page_number: usize,
+    format: ImageFormat,
+) -> PathBuf {
+    data_dir.join(format!(
+        "{}{}.{}",
+        page_number,
+        INVERTED_PAGE_SUFFIX,
+        format.extension()
+    ))
+}
+
+pub fn blank_marker_path(data_dir: &Path, page_number: usize) -> PathBuf {
+    data_dir.join(format!("{}.{}", page_number, BLANK_MARKER_EXTENSION))
+}
+
+fn cache_info(data_dir: &Path) -> Result<CacheInfo> {
+    let manifest = CacheManifest::load(data_dir)?;
+    let format = manifest
+        .as_ref()
+        .map(CacheManifest::format)
+        .unwrap_or_default();
+
+    let mut total_bytes = 0;
+    let mut last_rendered = None;
+    for entry in fs::read_dir(data_dir).context("Failed to read data directory")? {
+        let metadata = fs::metadata(entry?.path()).context("Failed to read cache file metadata")?;
+        if !metadata.is_file() {
+            continue;
+        }
+        total_bytes += metadata.len();
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .context("Cache file modification time is before the Unix epoch")?
+            .as_secs();
+        last_rendered = last_rendered.max(Some(modified));
+    }
+
+    Ok(CacheInfo {
+        data_dir: data_dir.to_path_buf(),
+        document_path: manifest
+            .as_ref()
+            .and_then(|manifest| manifest.document_path().map(str::to_string)),
+        page_count: count_webp_files(data_dir, format)?,
+        total_bytes,
+        last_rendered,
+        format: format.extension().to_string(),
+    })
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let data = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(format!("{:x}", Sha256::digest(&data)))
+}
+
+fn count_webp_files(dir: &Path, format: ImageFormat) -> Result<usize> {
+    Ok(fs::read_dir(dir)
+        .context("Failed to read data directory")?
+        .filter_map(Result::ok)
+        .filter(|e| {
+            let path = e.path();
+            let extension = path.extension();
+            let is_page = path
+                .file_stem()
+                .and_then(OsStr::to_str)
+                .is_some_and(|stem| stem.parse::<usize>().is_ok());
+            is_page
+                && (extension == Some(OsStr::new(format.extension()))
+                    || extension == Some(OsStr::new(BLANK_MARKER_EXTENSION)))
+        })
+        .count())
+}
+
+// The data directory also holds annotations, bookmarks, OCR results and decrypted or
+// converted PDFs, so only rendered pages, blank markers and the manifest are removed.
+// Returns how many artifacts were removed.
+pub fn remove_cache_artifacts(data_dir: &Path) -> Result<usize> {
+    if !data_dir.exists() {
+        return Ok(0);
+    }
+
+    log::info!("Clearing cache {}", data_dir.display());
+    let mut removed = 0;
+    for entry in fs::read_dir(data_dir).context("Failed to read data directory")? {
+        let path = entry?.path();
+        if path.is_file() && is_cache_artifact(&path) {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn is_cache_artifact(path: &Path) -> bool {
+    if path.file_name() == Some(OsStr::new(MANIFEST_FILE_NAME)) {
+        return true;
+    }
+    let is_page = path
+        .file_stem()
+        .and_then(OsStr::to_str)
+        .map(|stem| stem.strip_suffix(INVERTED_PAGE_SUFFIX).unwrap_or(stem))
+        .is_some_and(|stem| stem.parse::<usize>().is_ok());
+    let is_render = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .is_some_and(|extension| RENDER_EXTENSIONS.contains(&extension));
+    is_page && is_render
+}
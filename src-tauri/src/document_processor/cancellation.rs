@@ -0,0 +1,35 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use tauri::AppHandle;
+
+use super::error::Error;
+use super::manager::document_manager;
+
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    pub fn is(&self, other: &CancellationToken) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+// Only the documents of the requesting frontend session are cancelled, so one tab can't stop
+// another tab's preparation.
+#[tauri::command]
+pub fn cancel_preparation(app: AppHandle, session_id: String) -> Result<(), Error> {
+    log::info!("Cancelling document preparation for session {}", session_id);
+    document_manager(&app)?.cancel_client(&session_id);
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use lopdf::{Dictionary, Document, Object};
+use serde::Serialize;
+
+use super::error::Error;
+use super::fonts::{font_name, is_font_embedded};
+use super::selector::load_pdf_blocking;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PdfAReport {
+    conformance_level: Option<String>,
+    has_xmp_metadata: bool,
+    all_fonts_embedded: bool,
+    issues: Vec<String>,
+}
+
+#[tauri::command]
+pub async fn check_pdf_a_compliance(path: PathBuf) -> Result<PdfAReport, Error> {
+    inspect_compliance(path).await.map_err(Error::CommandError)
+}
+
+async fn inspect_compliance(path: PathBuf) -> Result<PdfAReport> {
+    let document = load_pdf_blocking(path).await?;
+    let catalog = document.catalog()?;
+    let mut issues = Vec::new();
+
+    let xmp = read_xmp_metadata(&document, catalog);
+    let has_xmp_metadata = xmp.is_some();
+    let conformance_level = match &xmp {
+        Some(xmp) => {
+            let level = conformance_level(xmp);
+            if level.is_none() {
+                issues.push("XMP metadata does not declare a PDF/A identification".to_string());
+            }
+            level
+        }
+        None => {
+            issues.push("Document has no XMP metadata stream".to_string());
+            None
+        }
+    };
+
+    let has_output_intent = catalog
+        .get(b"OutputIntents")
+        .ok()
+        .and_then(|intents| resolve(&document, intents))
+        .and_then(|intents| intents.as_array().ok())
+        .is_some_and(|intents| !intents.is_empty());
+    if !has_output_intent {
+        issues.push("Document has no OutputIntents entry".to_string());
+    }
+
+    let unembedded = unembedded_fonts(&document);
+    let all_fonts_embedded = unembedded.is_empty();
+    issues.extend(
+        unembedded
+            .into_iter()
+            .map(|font| format!("Font {} is not embedded", font)),
+    );
+
+    Ok(PdfAReport {
+        conformance_level,
+        has_xmp_metadata,
+        all_fonts_embedded,
+        issues,
+    })
+}
+
+fn read_xmp_metadata(document: &Document, catalog: &Dictionary) -> Option<String> {
+    let stream = resolve(document, catalog.get(b"Metadata").ok()?)?
+        .as_stream()
+        .ok()?;
+    let content = stream
+        .decompressed_content()
+        .unwrap_or_else(|_| stream.content.clone());
+    Some(String::from_utf8_lossy(&content).into_owned())
+}
+
+fn conformance_level(xmp: &str) -> Option<String> {
+    let part = xmp_value(xmp, "pdfaid:part")?;
+    let conformance = xmp_value(xmp, "pdfaid:conformance").unwrap_or_default();
+    Some(format!("PDF/A-{}{}", part, conformance.to_lowercase()))
+}
+
+// XMP writers use either the attribute form (pdfaid:part="1") or the element form.
+fn xmp_value(xmp: &str, key: &str) -> Option<String> {
+    if let Some(start) = xmp.find(&format!("{}=", key)) {
+        let rest = &xmp[start + key.len() + 1..];
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value = &rest[1..];
+        return value.find(quote).map(|end| value[..end].trim().to_string());
+    }
+
+    let open = format!("<{}>", key);
+    let start = xmp.find(&open)? + open.len();
+    let end = xmp[start..].find(&format!("</{}>", key))?;
+    Some(xmp[start..start + end].trim().to_string())
+}
+
+fn unembedded_fonts(document: &Document) -> Vec<String> {
+    let mut unembedded = Vec::new();
+
+    for page_id in document.get_pages().into_values() {
+        for font in document.get_page_fonts(page_id).into_values() {
+            let name = font_name(font);
+            if !is_font_embedded(document, font) && !unembedded.contains(&name) {
+                unembedded.push(name);
+            }
+        }
+    }
+
+    unembedded
+}
+
+fn resolve<'a>(document: &'a Document, object: &'a Object) -> Option<&'a Object> {
+    match object {
+        Object::Reference(id) => document.get_object(*id).ok(),
+        object => Some(object),
+    }
+}
@@ -0,0 +1,78 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use super::error::Error;
+use super::selector::has_pdf_header;
+
+const PDF_CONTENT_TYPE: &str = "application/pdf";
+
+#[derive(Debug, Clone, Serialize)]
+struct DownloadProgress {
+    bytes_downloaded: u64,
+    total_bytes: Option<u64>,
+}
+
+#[tauri::command]
+pub async fn download_document(
+    app: AppHandle,
+    url: String,
+    destination: PathBuf,
+) -> Result<PathBuf, Error> {
+    let is_pdf_response = downloading(&app, &url, &destination).await?;
+    if !is_pdf_response && !has_pdf_header(&destination)? {
+        if let Err(err) = fs::remove_file(&destination) {
+            log::warn!("Failed to remove {}: {}", destination.display(), err);
+        }
+        return Err(Error::UnsupportedFormat(url));
+    }
+    Ok(destination)
+}
+
+async fn downloading(app: &AppHandle, url: &str, destination: &Path) -> Result<bool> {
+    log::info!("Downloading {} to {}", url, destination.display());
+    let mut response = reqwest::get(url)
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .with_context(|| format!("Failed to download {}", url))?;
+
+    let is_pdf_response = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(PDF_CONTENT_TYPE));
+    let total_bytes = response.content_length();
+
+    let file = File::create(destination).map_err(|err| Error::io(destination, err))?;
+    let mut writer = BufWriter::new(file);
+    let mut bytes_downloaded = 0;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .context("Failed to read download stream")?
+    {
+        writer
+            .write_all(&chunk)
+            .map_err(|err| Error::io(destination, err))?;
+        bytes_downloaded += chunk.len() as u64;
+        app.emit(
+            "download_progress",
+            DownloadProgress {
+                bytes_downloaded,
+                total_bytes,
+            },
+        )?;
+    }
+    writer.flush().map_err(|err| Error::io(destination, err))?;
+
+    if bytes_downloaded == 0 {
+        return Err(anyhow!("{} returned an empty response", url));
+    }
+    Ok(is_pdf_response)
+}
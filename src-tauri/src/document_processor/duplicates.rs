@@ -0,0 +1,96 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use super::cache::cached_document;
+use super::error::Error;
+use super::selector::get_sidecar_magick;
+
+const HASH_SIZE: usize = 8;
+const MAX_HAMMING_DISTANCE: u32 = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicatePageGroup {
+    pages: Vec<u16>,
+    perceptual_hash: String,
+}
+
+#[tauri::command]
+pub async fn find_duplicate_pages(
+    app: AppHandle,
+    path: PathBuf,
+) -> Result<Vec<DuplicatePageGroup>, Error> {
+    let cache = cached_document(&app, &path)?;
+    let format = cache.manifest().format();
+    let page_count = u16::try_from(cache.page_count()).context("Document has too many pages")?;
+
+    let mut groups: Vec<(u64, Vec<u16>)> = Vec::new();
+    for page in 1..=page_count {
+        let image = cache.page_path(page as usize, format);
+        if !image.exists() {
+            continue;
+        }
+        let hash = average_hash(&app, &image).await?;
+        match groups
+            .iter_mut()
+            .find(|(group_hash, _)| (*group_hash ^ hash).count_ones() <= MAX_HAMMING_DISTANCE)
+        {
+            Some((_, pages)) => pages.push(page),
+            None => groups.push((hash, vec![page])),
+        }
+    }
+
+    Ok(groups
+        .into_iter()
+        .filter(|(_, pages)| pages.len() > 1)
+        .map(|(hash, pages)| DuplicatePageGroup {
+            pages,
+            perceptual_hash: format!("{:016x}", hash),
+        })
+        .collect())
+}
+
+// Average hash: shrink to 8x8 grayscale and set one bit per pixel brighter than the mean.
+async fn average_hash(app: &AppHandle, image: &Path) -> Result<u64> {
+    let size = format!("{0}x{0}!", HASH_SIZE);
+    let output = get_sidecar_magick(app)
+        .arg(image)
+        .args([
+            "-resize",
+            size.as_str(),
+            "-colorspace",
+            "Gray",
+            "-depth",
+            "8",
+            "gray:-",
+        ])
+        .output()
+        .await
+        .context("Failed to run magick command")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Hashing {} failed with exit code {}, stderr: {}",
+            image.display(),
+            output.status.code().unwrap_or(1),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let pixels = output.stdout;
+    if pixels.len() != HASH_SIZE * HASH_SIZE {
+        return Err(anyhow!(
+            "Expected {} pixels from magick, got {}",
+            HASH_SIZE * HASH_SIZE,
+            pixels.len()
+        ));
+    }
+
+    let mean = pixels.iter().map(|&pixel| pixel as u32).sum::<u32>() / pixels.len() as u32;
+    Ok(pixels
+        .iter()
+        .enumerate()
+        .filter(|(_, &pixel)| pixel as u32 > mean)
+        .fold(0, |hash, (bit, _)| hash | 1 << bit))
+}
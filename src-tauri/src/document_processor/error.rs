@@ -0,0 +1,83 @@
+use std::path::PathBuf;
+
+use serde::ser::SerializeMap;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    CommandError(#[from] anyhow::Error),
+    #[error("Page {0} has not been rendered yet")]
+    PageNotRendered(u32),
+    #[error("Page {page} is out of range 1-{page_count}")]
+    PageOutOfRange { page: u32, page_count: u32 },
+    #[error("The document is password protected")]
+    EncryptedDocument,
+    #[error("ImageMagick is not installed or could not be found")]
+    MagickNotFound,
+    #[error("Failed to load PDF document {}: {reason}", path.display())]
+    PdfLoadFailed { path: PathBuf, reason: String },
+    #[error("Failed to render page {page}: {stderr}")]
+    RenderFailed { page: u32, stderr: String },
+    #[error("Unsupported document type: {0}")]
+    UnsupportedFormat(String),
+    #[error("I/O error on {}: {reason}", path.display())]
+    IoError { path: PathBuf, reason: String },
+    #[error("Nothing was selected")]
+    NothingSelected,
+    #[error("No open document session {0}")]
+    SessionNotFound(String),
+}
+
+impl Error {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::CommandError(_) => "command",
+            Error::PageNotRendered(_) => "page_not_rendered",
+            Error::PageOutOfRange { .. } => "page_out_of_range",
+            Error::EncryptedDocument => "encrypted_document",
+            Error::MagickNotFound => "magick_not_found",
+            Error::PdfLoadFailed { .. } => "pdf_load_failed",
+            Error::RenderFailed { .. } => "render_failed",
+            Error::UnsupportedFormat(_) => "unsupported_format",
+            Error::IoError { .. } => "io_error",
+            Error::NothingSelected => "nothing_selected",
+            Error::SessionNotFound(_) => "session_not_found",
+        }
+    }
+
+    pub fn io(path: impl Into<PathBuf>, source: std::io::Error) -> Self {
+        Error::IoError {
+            path: path.into(),
+            reason: source.to_string(),
+        }
+    }
+}
+
+impl serde::Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::ser::Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", self.kind())?;
+        map.serialize_entry("message", &self.to_string())?;
+        match self {
+            Error::PageNotRendered(page) => map.serialize_entry("page", page)?,
+            Error::PageOutOfRange { page, page_count } => {
+                map.serialize_entry("page", page)?;
+                map.serialize_entry("page_count", page_count)?;
+            }
+            Error::PdfLoadFailed { path, reason } | Error::IoError { path, reason } => {
+                map.serialize_entry("path", path)?;
+                map.serialize_entry("source", reason)?;
+            }
+            Error::RenderFailed { page, stderr } => {
+                map.serialize_entry("page", page)?;
+                map.serialize_entry("stderr", stderr)?;
+            }
+            Error::UnsupportedFormat(format) => map.serialize_entry("format", format)?,
+            _ => {}
+        }
+        map.end()
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use super::cache::{blank_marker_path, create_output_paths, page_output_path};
+use super::error::Error;
+use super::manifest::CacheManifest;
+use super::options::{ImageFormat, ProcessingOptions};
+use super::selector::get_sidecar_magick;
+
+#[derive(Debug, Clone, Serialize)]
+struct ExportProgress {
+    current: u32,
+    total: u32,
+}
+
+#[tauri::command]
+pub async fn export_page_range(
+    app: AppHandle,
+    path: PathBuf,
+    start_page: u32,
+    end_page: u32,
+    output_zip: PathBuf,
+) -> Result<(), Error> {
+    if start_page == 0 || start_page > end_page {
+        return Err(anyhow!("Invalid page range {}-{}", start_page, end_page).into());
+    }
+
+    let (data_dir, _output_file_name) =
+        create_output_paths(&app, &path, &ProcessingOptions::default())?;
+    let Some(manifest) = CacheManifest::load(&data_dir)? else {
+        return Err(Error::PageNotRendered(start_page));
+    };
+    let cached_format = manifest.format();
+
+    let mut pages = Vec::new();
+    for page in start_page..=end_page {
+        let image = page_output_path(&data_dir, page as usize, cached_format);
+        if image.exists() {
+            pages.push((page, image));
+        } else if !blank_marker_path(&data_dir, page as usize).exists() {
+            return Err(Error::PageNotRendered(page));
+        }
+    }
+
+    write_zip(&app, &pages, &output_zip, cached_format).map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub async fn export_document_images(
+    app: AppHandle,
+    path: PathBuf,
+    output_dir: PathBuf,
+    format: ImageFormat,
+) -> Result<Vec<PathBuf>, Error> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(&app, &path, &ProcessingOptions::default())?;
+    let Some(manifest) = CacheManifest::load(&data_dir)? else {
+        return Err(Error::PageNotRendered(1));
+    };
+    let cached_format = manifest.format();
+
+    let mut pages = Vec::new();
+    for page in 1..=manifest.page_count() {
+        let image = page_output_path(&data_dir, page, cached_format);
+        if image.exists() {
+            pages.push((page as u32, image));
+        } else if !blank_marker_path(&data_dir, page).exists() {
+            return Err(Error::PageNotRendered(page as u32));
+        }
+    }
+
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "document".to_string());
+    fs::create_dir_all(&output_dir).map_err(|err| Error::io(&output_dir, err))?;
+    log::info!(
+        "Exporting {} page images to {}",
+        pages.len(),
+        output_dir.display()
+    );
+
+    let mut exported = Vec::with_capacity(pages.len());
+    for (index, (page, image)) in pages.iter().enumerate() {
+        let output = unique_output_path(&output_dir, &stem, *page, format.extension());
+        if format.extension() == cached_format.extension() {
+            fs::copy(image, &output).map_err(|err| Error::io(&output, err))?;
+        } else {
+            convert_image(&app, image, &output, format).await?;
+        }
+        exported.push(output);
+
+        app.emit(
+            "export_progress",
+            ExportProgress {
+                current: index as u32 + 1,
+                total: pages.len() as u32,
+            },
+        )
+        .map_err(anyhow::Error::from)?;
+    }
+    Ok(exported)
+}
+
+#[tauri::command]
+pub async fn export_page_image(
+    app: AppHandle,
+    path: PathBuf,
+    page: u32,
+    output: PathBuf,
+) -> Result<(), Error> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(&app, &path, &ProcessingOptions::default())?;
+    let Some(manifest) = CacheManifest::load(&data_dir)? else {
+        return Err(Error::PageNotRendered(page));
+    };
+    let cached_format = manifest.format();
+    let image = page_output_path(&data_dir, page as usize, cached_format);
+    if !image.exists() {
+        return Err(Error::PageNotRendered(page));
+    }
+
+    let same_format = output
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case(cached_format.extension()));
+    if same_format {
+        fs::copy(&image, &output).map_err(|err| Error::io(&output, err))?;
+    } else {
+        // Magick picks the target format from the output extension.
+        convert_image(&app, &image, &output, ImageFormat::Png).await?;
+    }
+    Ok(())
+}
+
+fn unique_output_path(output_dir: &Path, stem: &str, page: u32, extension: &str) -> PathBuf {
+    let output = output_dir.join(format!("{}_page_{}.{}", stem, page, extension));
+    if !output.exists() {
+        return output;
+    }
+    let mut counter = 1;
+    loop {
+        let output = output_dir.join(format!("{}_page_{}_{}.{}", stem, page, counter, extension));
+        if !output.exists() {
+            return output;
+        }
+        counter += 1;
+    }
+}
+
+async fn convert_image(
+    app: &AppHandle,
+    input: &Path,
+    output: &Path,
+    format: ImageFormat,
+) -> Result<()> {
+    let mut command = get_sidecar_magick(app).arg(input);
+    if let ImageFormat::Jpeg { quality } = format {
+        command = command.args(["-quality".to_string(), quality.to_string()]);
+    }
+    let result = command
+        .arg(output)
+        .output()
+        .await
+        .context("Failed to run magick image conversion")?;
+
+    if !result.status.success() {
+        return Err(anyhow!(
+            "Magick image conversion failed with exit code {}, stderr: {}",
+            result.status.code().unwrap_or(1),
+            String::from_utf8_lossy(&result.stderr)
+        ));
+    }
+    Ok(())
+}
+
+fn write_zip(
+    app: &AppHandle,
+    pages: &[(u32, PathBuf)],
+    output_zip: &Path,
+    format: ImageFormat,
+) -> Result<()> {
+    log::info!(
+        "Exporting {} pages to {}",
+        pages.len(),
+        output_zip.display()
+    );
+    let file = File::create(output_zip).context("Failed to create ZIP archive")?;
+    let mut zip = ZipWriter::new(file);
+    let entry_options = FileOptions::default().compression_method(CompressionMethod::Stored);
+
+    for (index, (page, image)) in pages.iter().enumerate() {
+        let data = fs::read(image).context("Failed to read cached page image")?;
+        zip.start_file(
+            format!("page_{}.{}", page, format.extension()),
+            entry_options,
+        )
+        .context("Failed to add page to ZIP archive")?;
+        zip.write_all(&data)
+            .context("Failed to write page to ZIP archive")?;
+
+        app.emit(
+            "export_progress",
+            ExportProgress {
+                current: index as u32 + 1,
+                total: pages.len() as u32,
+            },
+        )?;
+    }
+
+    zip.finish().context("Failed to finish ZIP archive")?;
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use anyhow::Result;
+use lopdf::{Dictionary, Document, Object};
+use serde::Serialize;
+
+use super::error::Error;
+use super::selector::load_pdf_blocking;
+
+const FONT_FILE_KEYS: [&[u8]; 3] = [b"FontFile", b"FontFile2", b"FontFile3"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FontInfo {
+    name: String,
+    subtype: String,
+    encoding: Option<String>,
+    is_embedded: bool,
+    pages: Vec<u16>,
+}
+
+#[tauri::command]
+pub async fn get_embedded_fonts(path: PathBuf) -> Result<Vec<FontInfo>, Error> {
+    read_fonts(path).await.map_err(Error::CommandError)
+}
+
+async fn read_fonts(path: PathBuf) -> Result<Vec<FontInfo>> {
+    let document = load_pdf_blocking(path).await?;
+    let mut fonts: BTreeMap<String, FontInfo> = BTreeMap::new();
+
+    for (page, page_id) in document.get_pages() {
+        let Ok(page) = u16::try_from(page) else {
+            break;
+        };
+        for font in document.get_page_fonts(page_id).into_values() {
+            let name = font_name(font);
+            let info = fonts.entry(name.clone()).or_insert_with(|| FontInfo {
+                name,
+                subtype: name_value(font, b"Subtype").unwrap_or_else(|| "Unknown".to_string()),
+                encoding: font_encoding(&document, font),
+                is_embedded: is_font_embedded(&document, font),
+                pages: Vec::new(),
+            });
+            if info.pages.last() != Some(&page) {
+                info.pages.push(page);
+            }
+        }
+    }
+
+    Ok(fonts.into_values().collect())
+}
+
+pub fn font_name(font: &Dictionary) -> String {
+    name_value(font, b"BaseFont").unwrap_or_else(|| "unnamed".to_string())
+}
+
+// Type3 glyphs live in the font dictionary itself, and Type0 fonts carry their
+// program on the descendant CIDFont.
+pub fn is_font_embedded(document: &Document, font: &Dictionary) -> bool {
+    match font.get(b"Subtype").and_then(Object::as_name).ok() {
+        Some(b"Type3") => true,
+        Some(b"Type0") => {
+            let descendants = descendant_fonts(document, font);
+            !descendants.is_empty()
+                && descendants
+                    .into_iter()
+                    .all(|descendant| has_font_file(document, descendant))
+        }
+        _ => has_font_file(document, font),
+    }
+}
+
+fn descendant_fonts<'a>(document: &'a Document, font: &'a Dictionary) -> Vec<&'a Dictionary> {
+    font.get(b"DescendantFonts")
+        .ok()
+        .and_then(|descendants| resolve(document, descendants))
+        .and_then(|descendants| descendants.as_array().ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|descendant| resolve(document, descendant)?.as_dict().ok())
+        .collect()
+}
+
+fn has_font_file(document: &Document, font: &Dictionary) -> bool {
+    font.get(b"FontDescriptor")
+        .ok()
+        .and_then(|descriptor| resolve(document, descriptor))
+        .and_then(|descriptor| descriptor.as_dict().ok())
+        .is_some_and(|descriptor| FONT_FILE_KEYS.iter().any(|key| descriptor.get(key).is_ok()))
+}
+
+fn font_encoding(document: &Document, font: &Dictionary) -> Option<String> {
+    match resolve(document, font.get(b"Encoding").ok()?)? {
+        Object::Name(name) => Some(String::from_utf8_lossy(name).into_owned()),
+        Object::Dictionary(encoding) => {
+            Some(name_value(encoding, b"BaseEncoding").unwrap_or_else(|| "Custom".to_string()))
+        }
+        Object::Stream(_) => Some("Embedded CMap".to_string()),
+        _ => None,
+    }
+}
+
+fn name_value(dictionary: &Dictionary, key: &[u8]) -> Option<String> {
+    dictionary
+        .get(key)
+        .and_then(Object::as_name_str)
+        .ok()
+        .map(str::to_string)
+}
+
+fn resolve<'a>(document: &'a Document, object: &'a Object) -> Option<&'a Object> {
+    match object {
+        Object::Reference(id) => document.get_object(*id).ok(),
+        object => Some(object),
+    }
+}
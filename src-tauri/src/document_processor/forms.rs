@@ -0,0 +1,350 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Context, Result};
+use lopdf::{Dictionary, Document, Object, ObjectId, StringFormat};
+use serde::Serialize;
+
+use super::error::Error;
+use super::selector::{decode_pdf_string, encode_pdf_string, load_pdf_blocking};
+
+const RADIO_FLAG: i64 = 1 << 15;
+const PUSH_BUTTON_FLAG: i64 = 1 << 16;
+const OFF_STATE: &str = "Off";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum FieldKind {
+    Text,
+    CheckBox,
+    RadioButton,
+    Dropdown,
+    Signature,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FormField {
+    name: String,
+    kind: FieldKind,
+    page: u16,
+    bbox: [f32; 4],
+    value: Option<String>,
+    // Choices for dropdowns, or the "on" state of a checkbox or radio widget.
+    options: Vec<String>,
+}
+
+struct FieldNode {
+    id: ObjectId,
+    name: String,
+    kind: Option<FieldKind>,
+    widgets: Vec<ObjectId>,
+}
+
+#[tauri::command]
+pub async fn detect_forms(path: PathBuf) -> Result<Vec<FormField>, Error> {
+    let document = load_pdf_blocking(path).await?;
+    let pages = widget_pages(&document);
+    let mut fields = Vec::new();
+
+    for node in form_fields(&document)? {
+        let Some(kind) = node.kind else {
+            continue;
+        };
+        let field = document
+            .get_dictionary(node.id)
+            .context("Failed to read form field")?;
+        let value =
+            inherited(&document, field, b"V").and_then(|value| object_text(&document, value));
+        for widget_id in &node.widgets {
+            let Ok(widget) = document.get_dictionary(*widget_id) else {
+                continue;
+            };
+            let Some(bbox) = widget_rect(&document, widget) else {
+                continue;
+            };
+            let Some(page) = pages.get(widget_id).copied() else {
+                continue;
+            };
+            let options = match kind {
+                FieldKind::Dropdown => choice_options(&document, field),
+                FieldKind::CheckBox | FieldKind::RadioButton => {
+                    on_state(&document, widget).into_iter().collect()
+                }
+                FieldKind::Text | FieldKind::Signature => Vec::new(),
+            };
+            fields.push(FormField {
+                name: node.name.clone(),
+                kind,
+                page,
+                bbox,
+                value: value.clone(),
+                options,
+            });
+        }
+    }
+
+    Ok(fields)
+}
+
+#[tauri::command]
+pub async fn fill_form_field(
+    path: PathBuf,
+    field_name: String,
+    value: String,
+) -> Result<(), Error> {
+    let mut document = load_pdf_blocking(path.clone()).await?;
+    if document.is_encrypted() {
+        return Err(Error::EncryptedDocument);
+    }
+
+    let node = form_fields(&document)?
+        .into_iter()
+        .find(|node| node.name == field_name)
+        .ok_or_else(|| anyhow!("Form field {} does not exist", field_name))?;
+    let kind = node
+        .kind
+        .ok_or_else(|| anyhow!("Form field {} cannot be filled", field_name))?;
+
+    let new_value = match kind {
+        FieldKind::Text | FieldKind::Dropdown => {
+            Object::String(encode_pdf_string(&value), StringFormat::Literal)
+        }
+        FieldKind::CheckBox | FieldKind::RadioButton => Object::Name(value.clone().into_bytes()),
+        FieldKind::Signature => {
+            return Err(anyhow!("Signature fields cannot be filled").into());
+        }
+    };
+    if matches!(kind, FieldKind::CheckBox | FieldKind::RadioButton) {
+        for widget_id in &node.widgets {
+            let state = match document.get_dictionary(*widget_id) {
+                Ok(widget) if on_state(&document, widget).as_deref() == Some(value.as_str()) => {
+                    value.as_str()
+                }
+                _ => OFF_STATE,
+            };
+            document
+                .get_object_mut(*widget_id)
+                .and_then(Object::as_dict_mut)
+                .context("Failed to read form widget")?
+                .set("AS", Object::Name(state.as_bytes().to_vec()));
+        }
+    }
+    document
+        .get_object_mut(node.id)
+        .and_then(Object::as_dict_mut)
+        .context("Failed to read form field")?
+        .set("V", new_value);
+
+    // Text appearances are not regenerated here, so viewers are asked to rebuild them.
+    let acro_form_id = match document
+        .catalog()
+        .context("Failed to read document catalog")?
+        .get(b"AcroForm")
+    {
+        Ok(Object::Reference(id)) => *id,
+        _ => document
+            .trailer
+            .get(b"Root")
+            .and_then(Object::as_reference)
+            .context("Failed to read document catalog")?,
+    };
+    let acro_form = document
+        .get_object_mut(acro_form_id)
+        .and_then(Object::as_dict_mut)
+        .context("Failed to read form dictionary")?;
+    let acro_form = if acro_form.has(b"AcroForm") {
+        acro_form
+            .get_mut(b"AcroForm")
+            .and_then(Object::as_dict_mut)
+            .context("Failed to read form dictionary")?
+    } else {
+        acro_form
+    };
+    acro_form.set("NeedAppearances", true);
+
+    document.save(&path).context("Failed to save filled form")?;
+    log::info!("Filled form field {} of {}", field_name, path.display());
+    Ok(())
+}
+
+fn form_fields(document: &Document) -> Result<Vec<FieldNode>> {
+    let acro_form = document
+        .catalog()?
+        .get(b"AcroForm")
+        .ok()
+        .and_then(|acro_form| resolve(document, acro_form))
+        .and_then(|acro_form| acro_form.as_dict().ok());
+    let Some(acro_form) = acro_form else {
+        return Ok(Vec::new());
+    };
+
+    let mut nodes = Vec::new();
+    let mut visited = HashSet::new();
+    for field in references(document, acro_form.get(b"Fields").ok()) {
+        collect_fields(document, field, "", &mut visited, &mut nodes);
+    }
+    Ok(nodes)
+}
+
+// Fields form a tree: named nodes carry the value, while nameless kids are the
+// widgets that place them on pages. A terminal field can also be its own widget.
+fn collect_fields(
+    document: &Document,
+    id: ObjectId,
+    parent_name: &str,
+    visited: &mut HashSet<ObjectId>,
+    nodes: &mut Vec<FieldNode>,
+) {
+    if !visited.insert(id) {
+        return;
+    }
+    let Ok(field) = document.get_dictionary(id) else {
+        return;
+    };
+
+    let name = match field.get(b"T").and_then(Object::as_str) {
+        Ok(partial) if parent_name.is_empty() => decode_pdf_string(partial),
+        Ok(partial) => format!("{}.{}", parent_name, decode_pdf_string(partial)),
+        Err(_) => parent_name.to_string(),
+    };
+
+    let mut widgets = Vec::new();
+    if field.get(b"Rect").is_ok() {
+        widgets.push(id);
+    }
+    for kid_id in references(document, field.get(b"Kids").ok()) {
+        let is_widget = document
+            .get_dictionary(kid_id)
+            .is_ok_and(|kid| kid.get(b"T").is_err());
+        if is_widget {
+            visited.insert(kid_id);
+            widgets.push(kid_id);
+        } else {
+            collect_fields(document, kid_id, &name, visited, nodes);
+        }
+    }
+
+    if !widgets.is_empty() {
+        nodes.push(FieldNode {
+            id,
+            kind: field_kind(document, field),
+            name,
+            widgets,
+        });
+    }
+}
+
+fn field_kind(document: &Document, field: &Dictionary) -> Option<FieldKind> {
+    let flags = inherited(document, field, b"Ff")
+        .and_then(|flags| flags.as_i64().ok())
+        .unwrap_or(0);
+    match inherited(document, field, b"FT")?.as_name().ok()? {
+        b"Tx" => Some(FieldKind::Text),
+        b"Ch" => Some(FieldKind::Dropdown),
+        b"Sig" => Some(FieldKind::Signature),
+        b"Btn" if flags & PUSH_BUTTON_FLAG != 0 => None,
+        b"Btn" if flags & RADIO_FLAG != 0 => Some(FieldKind::RadioButton),
+        b"Btn" => Some(FieldKind::CheckBox),
+        _ => None,
+    }
+}
+
+fn inherited<'a>(document: &'a Document, field: &'a Dictionary, key: &[u8]) -> Option<&'a Object> {
+    let mut node = field;
+    let mut visited = HashSet::new();
+    loop {
+        if let Ok(value) = node.get(key) {
+            return resolve(document, value);
+        }
+        let parent = node.get(b"Parent").and_then(Object::as_reference).ok()?;
+        if !visited.insert(parent) {
+            return None;
+        }
+        node = document.get_dictionary(parent).ok()?;
+    }
+}
+
+fn choice_options(document: &Document, field: &Dictionary) -> Vec<String> {
+    let Some(options) = inherited(document, field, b"Opt").and_then(|opt| opt.as_array().ok())
+    else {
+        return Vec::new();
+    };
+    options
+        .iter()
+        .filter_map(|option| match resolve(document, option)? {
+            // [export value, display text] pairs use the export value.
+            Object::Array(pair) => pair.first().and_then(|value| object_text(document, value)),
+            option => object_text(document, option),
+        })
+        .collect()
+}
+
+fn on_state(document: &Document, widget: &Dictionary) -> Option<String> {
+    let appearances = resolve(document, widget.get(b"AP").ok()?)?.as_dict().ok()?;
+    let normal = resolve(document, appearances.get(b"N").ok()?)?
+        .as_dict()
+        .ok()?;
+    normal
+        .iter()
+        .map(|(state, _)| String::from_utf8_lossy(state).into_owned())
+        .find(|state| state != OFF_STATE)
+}
+
+fn widget_rect(document: &Document, widget: &Dictionary) -> Option<[f32; 4]> {
+    let values = resolve(document, widget.get(b"Rect").ok()?)?
+        .as_array()
+        .ok()?
+        .iter()
+        .map(|value| match value {
+            Object::Integer(value) => Some(*value as f32),
+            Object::Real(value) => Some(*value),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let [x0, y0, x1, y1]: [f32; 4] = values.try_into().ok()?;
+    Some([x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1)])
+}
+
+// Widgets may omit /P, so pages are found through their annotation arrays instead.
+fn widget_pages(document: &Document) -> HashMap<ObjectId, u16> {
+    let mut pages = HashMap::new();
+    for (page, page_id) in document.get_pages() {
+        let Ok(page) = u16::try_from(page) else {
+            break;
+        };
+        let annotations = document
+            .get_dictionary(page_id)
+            .ok()
+            .and_then(|page| page.get(b"Annots").ok());
+        for annotation in references(document, annotations) {
+            pages.insert(annotation, page);
+        }
+    }
+    pages
+}
+
+fn object_text(document: &Document, object: &Object) -> Option<String> {
+    match resolve(document, object)? {
+        Object::String(bytes, _) => Some(decode_pdf_string(bytes)),
+        Object::Name(name) => Some(String::from_utf8_lossy(name).into_owned()),
+        _ => None,
+    }
+}
+
+fn references(document: &Document, object: Option<&Object>) -> Vec<ObjectId> {
+    object
+        .and_then(|object| resolve(document, object))
+        .and_then(|object| object.as_array().ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|item| item.as_reference().ok())
+        .collect()
+}
+
+fn resolve<'a>(document: &'a Document, object: &'a Object) -> Option<&'a Object> {
+    match object {
+        Object::Reference(id) => document.get_object(*id).ok(),
+        object => Some(object),
+    }
+}
@@ -0,0 +1,103 @@
+use std::{fs, path::Path};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::ShellExt;
+
+use super::error::Error;
+use super::magick::get_magick_version;
+use super::ocr::tesseract_languages;
+use super::selector::LIBREOFFICE_BINARY;
+
+const WRITE_PROBE_FILE_NAME: &str = ".write_probe";
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HealthReport {
+    magick_available: bool,
+    magick_version: Option<String>,
+    libreoffice_available: bool,
+    tesseract_available: bool,
+    ocr_languages: Vec<String>,
+    app_data_dir_writable: bool,
+    available_disk_bytes: u64,
+}
+
+#[tauri::command]
+pub async fn health_check(app: AppHandle) -> Result<HealthReport, Error> {
+    let mut report = HealthReport::default();
+
+    match get_magick_version(app.clone()).await {
+        Ok(version) => {
+            report.magick_available = true;
+            report.magick_version = Some(version);
+        }
+        Err(err) => log::warn!("Health check: ImageMagick unavailable: {}", err),
+    }
+
+    report.libreoffice_available = match check_libreoffice(&app).await {
+        Ok(()) => true,
+        Err(err) => {
+            log::warn!("Health check: LibreOffice unavailable: {}", err);
+            false
+        }
+    };
+
+    match tesseract_languages(&app).await {
+        Ok(languages) => {
+            report.tesseract_available = true;
+            report.ocr_languages = languages;
+        }
+        Err(err) => log::warn!("Health check: Tesseract unavailable: {}", err),
+    }
+
+    match app.path().app_data_dir() {
+        Ok(app_data_dir) => {
+            report.app_data_dir_writable = match check_writable(&app_data_dir) {
+                Ok(()) => true,
+                Err(err) => {
+                    log::warn!("Health check: app data directory not writable: {}", err);
+                    false
+                }
+            };
+            report.available_disk_bytes = match fs2::available_space(&app_data_dir) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    log::warn!("Health check: failed to read available disk space: {}", err);
+                    0
+                }
+            };
+        }
+        Err(err) => log::warn!(
+            "Health check: failed to resolve app data directory: {}",
+            err
+        ),
+    }
+
+    Ok(report)
+}
+
+async fn check_libreoffice(app: &AppHandle) -> Result<()> {
+    let output = app
+        .shell()
+        .command(LIBREOFFICE_BINARY)
+        .arg("--version")
+        .output()
+        .await
+        .context("Failed to run LibreOffice")?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "soffice --version failed with exit code {}",
+            output.status.code().unwrap_or(1)
+        ))
+    }
+}
+
+fn check_writable(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).context("Failed to create directory")?;
+    let probe = dir.join(WRITE_PROBE_FILE_NAME);
+    fs::write(&probe, b"").context("Failed to write probe file")?;
+    fs::remove_file(&probe).context("Failed to remove probe file")
+}
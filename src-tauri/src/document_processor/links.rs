@@ -0,0 +1,111 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use lopdf::{Dictionary, Document, Object};
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use super::error::Error;
+use super::selector::{decode_pdf_string, load_pdf_blocking};
+
+const ALLOWED_SCHEMES: [&str; 3] = ["http://", "https://", "mailto:"];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentLink {
+    page: u16,
+    uri: String,
+    bbox: [f32; 4],
+}
+
+#[tauri::command]
+pub async fn list_document_links(path: PathBuf) -> Result<Vec<DocumentLink>, Error> {
+    read_links(path).await.map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub fn open_link(app: AppHandle, uri: String) -> Result<(), Error> {
+    let lowercase = uri.to_lowercase();
+    if !ALLOWED_SCHEMES
+        .iter()
+        .any(|scheme| lowercase.starts_with(scheme))
+    {
+        return Err(anyhow!("Refusing to open link {}", uri).into());
+    }
+
+    log::info!("Opening link {}", uri);
+    app.shell()
+        .open(&uri, None)
+        .context("Failed to open link")?;
+    Ok(())
+}
+
+async fn read_links(path: PathBuf) -> Result<Vec<DocumentLink>> {
+    let document = load_pdf_blocking(path).await?;
+    let mut links = Vec::new();
+
+    for (page, page_id) in document.get_pages() {
+        let Ok(page) = u16::try_from(page) else {
+            break;
+        };
+        let Ok(page_dict) = document.get_dictionary(page_id) else {
+            continue;
+        };
+        let annotations = page_dict
+            .get(b"Annots")
+            .ok()
+            .and_then(|annotations| resolve(&document, annotations))
+            .and_then(|annotations| annotations.as_array().ok());
+
+        for annotation in annotations.into_iter().flatten() {
+            let Some(annotation) =
+                resolve(&document, annotation).and_then(|annotation| annotation.as_dict().ok())
+            else {
+                continue;
+            };
+            if let Some(link) = read_link(&document, page, annotation) {
+                links.push(link);
+            }
+        }
+    }
+
+    Ok(links)
+}
+
+fn read_link(document: &Document, page: u16, annotation: &Dictionary) -> Option<DocumentLink> {
+    if annotation.get(b"Subtype").and_then(Object::as_name).ok()? != b"Link" {
+        return None;
+    }
+    let action = resolve(document, annotation.get(b"A").ok()?)?
+        .as_dict()
+        .ok()?;
+    if action.get(b"S").and_then(Object::as_name).ok()? != b"URI" {
+        return None;
+    }
+    let uri = resolve(document, action.get(b"URI").ok()?)?.as_str().ok()?;
+
+    let rect = resolve(document, annotation.get(b"Rect").ok()?)?
+        .as_array()
+        .ok()?
+        .iter()
+        .map(|value| match value {
+            Object::Integer(value) => Some(*value as f32),
+            Object::Real(value) => Some(*value),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let [x0, y0, x1, y1]: [f32; 4] = rect.try_into().ok()?;
+
+    Some(DocumentLink {
+        page,
+        uri: decode_pdf_string(uri),
+        bbox: [x0.min(x1), y0.min(y1), x0.max(x1), y0.max(y1)],
+    })
+}
+
+fn resolve<'a>(document: &'a Document, object: &'a Object) -> Option<&'a Object> {
+    match object {
+        Object::Reference(id) => document.get_object(*id).ok(),
+        object => Some(object),
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::anyhow;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::ShellExt;
+
+use super::error::Error;
+use super::selector::get_sidecar_magick;
+
+const COMMAND_NOT_FOUND_EXIT_CODES: [i32; 2] = [127, 9009];
+const FALLBACK_BINARY_NAMES: [&str; 2] = ["magick", "convert"];
+
+#[derive(Debug, Clone)]
+pub struct MagickBinaryPath(pub String);
+
+pub fn detect_magick_binary() -> &'static str {
+    #[cfg(target_os = "windows")]
+    {
+        "magick.exe"
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        "magick"
+    }
+}
+
+pub async fn resolve_magick_binary(app: &AppHandle) -> String {
+    if cfg!(target_os = "windows") {
+        return detect_magick_binary().to_string();
+    }
+
+    for name in FALLBACK_BINARY_NAMES {
+        let Ok(output) = app.shell().command("which").arg(name).output().await else {
+            continue;
+        };
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && !path.is_empty() {
+            return path;
+        }
+    }
+    detect_magick_binary().to_string()
+}
+
+pub fn magick_binary(app: &AppHandle) -> String {
+    app.state::<MagickBinaryPath>().0.clone()
+}
+
+#[tauri::command]
+pub async fn get_magick_version(app: AppHandle) -> Result<String, Error> {
+    let output = get_sidecar_magick(&app)
+        .arg("--version")
+        .output()
+        .await
+        .map_err(|_| Error::MagickNotFound)?;
+
+    if !output.status.success() {
+        return match output.status.code() {
+            Some(code) if COMMAND_NOT_FOUND_EXIT_CODES.contains(&code) => {
+                Err(Error::MagickNotFound)
+            }
+            code => Err(anyhow!(
+                "magick --version failed with exit code {}, stderr: {}",
+                code.unwrap_or(1),
+                String::from_utf8_lossy(&output.stderr)
+            )
+            .into()),
+        };
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_version(&stdout)
+        .ok_or_else(|| anyhow!("Unexpected magick --version output: {}", stdout).into())
+}
+
+fn parse_version(stdout: &str) -> Option<String> {
+    let line = stdout.lines().find(|line| line.starts_with("Version:"))?;
+    let mut words = line.split_whitespace();
+    words.find(|word| *word == "ImageMagick")?;
+    words.next().map(str::to_string)
+}
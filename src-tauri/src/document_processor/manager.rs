@@ -0,0 +1,157 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use anyhow::anyhow;
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+
+use super::cache::Cache;
+use super::cancellation::CancellationToken;
+use super::error::Error;
+use super::options::ProcessingOptions;
+
+#[derive(Debug, Clone)]
+pub struct DocumentSession {
+    pub path: PathBuf,
+    // Unset while the document is still being prepared.
+    pub cache: Option<Cache>,
+    pub options: ProcessingOptions,
+    pub cancel_token: CancellationToken,
+    // The frontend session the document's events are addressed to.
+    pub client_id: String,
+}
+
+#[derive(Debug, Default)]
+pub struct DocumentManager {
+    sessions: HashMap<Uuid, DocumentSession>,
+}
+
+impl DocumentManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Reopening a document the client already has replaces its session, which cancels any
+    // preparation still running for the previous one.
+    pub fn open(
+        &mut self,
+        path: PathBuf,
+        options: ProcessingOptions,
+        client_id: &str,
+    ) -> (Uuid, CancellationToken) {
+        let existing = self
+            .sessions
+            .iter()
+            .find(|(_, session)| session.client_id == client_id && session.path == path)
+            .map(|(id, _)| *id);
+        let id = existing.unwrap_or_else(Uuid::new_v4);
+        let cancel_token = CancellationToken::default();
+        let previous = self.sessions.insert(
+            id,
+            DocumentSession {
+                path,
+                cache: None,
+                options,
+                cancel_token: cancel_token.clone(),
+                client_id: client_id.to_string(),
+            },
+        );
+        if let Some(previous) = previous {
+            previous.cancel_token.cancel();
+        }
+        (id, cancel_token)
+    }
+
+    pub fn get(&self, id: &Uuid) -> Result<&DocumentSession, Error> {
+        self.sessions
+            .get(id)
+            .ok_or_else(|| Error::SessionNotFound(id.to_string()))
+    }
+
+    pub fn find(&self, client_id: &str, path: &Path) -> Option<&DocumentSession> {
+        self.sessions
+            .values()
+            .find(|session| session.client_id == client_id && session.path == path)
+    }
+
+    pub fn set_cache(&mut self, id: &Uuid, cache: Cache) {
+        if let Some(session) = self.sessions.get_mut(id) {
+            session.cache = Some(cache);
+        }
+    }
+
+    // Only drops the session if it wasn't replaced by a newer preparation in the meantime.
+    pub fn discard(&mut self, id: &Uuid, cancel_token: &CancellationToken) {
+        if self
+            .sessions
+            .get(id)
+            .is_some_and(|session| session.cancel_token.is(cancel_token))
+        {
+            self.sessions.remove(id);
+        }
+    }
+
+    // Sessions rendered into a cleared data directory are left without a cache until they
+    // are prepared again.
+    pub fn forget_cache(&mut self, data_dir: &Path) {
+        self.sessions
+            .values_mut()
+            .filter(|session| {
+                session
+                    .cache
+                    .as_ref()
+                    .is_some_and(|cache| cache.data_dir() == data_dir)
+            })
+            .for_each(|session| session.cache = None);
+    }
+
+    pub fn cancel_client(&mut self, client_id: &str) {
+        self.sessions
+            .values()
+            .filter(|session| session.client_id == client_id)
+            .for_each(|session| session.cancel_token.cancel());
+    }
+
+    pub fn close(&mut self, id: &Uuid) -> Option<DocumentSession> {
+        let session = self.sessions.remove(id)?;
+        session.cancel_token.cancel();
+        Some(session)
+    }
+}
+
+pub fn document_manager(app: &AppHandle) -> Result<MutexGuard<'_, DocumentManager>, Error> {
+    app.state::<Mutex<DocumentManager>>()
+        .inner()
+        .lock()
+        .map_err(|_| anyhow!("Document manager lock was poisoned").into())
+}
+
+pub fn document_session(app: &AppHandle, id: &Uuid) -> Result<DocumentSession, Error> {
+    document_manager(app)?.get(id).cloned()
+}
+
+pub fn register_document(
+    app: &AppHandle,
+    path: &Path,
+    options: ProcessingOptions,
+    client_id: &str,
+) -> Result<(Uuid, CancellationToken), Error> {
+    let (id, cancel_token) = document_manager(app)?.open(path.to_path_buf(), options, client_id);
+    log::info!("Opened document session {} for {}", id, path.display());
+    Ok((id, cancel_token))
+}
+
+#[tauri::command]
+pub fn close_document(app: AppHandle, document_id: Uuid) -> Result<(), Error> {
+    if let Some(session) = document_manager(&app)?.close(&document_id) {
+        log::info!(
+            "Closed document session {} for {}",
+            document_id,
+            session.path.display()
+        );
+    }
+    Ok(())
+}
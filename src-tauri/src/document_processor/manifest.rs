@@ -0,0 +1,164 @@
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::options::{ImageFormat, ProcessingOptions};
+
+pub const MANIFEST_FILE_NAME: &str = "cache_manifest.json";
+
+// The options that change how a page looks, serialized so the hash is stable across releases.
+#[derive(Serialize)]
+struct RenderOptions<'a> {
+    density: u32,
+    resize: &'a str,
+    format: ImageFormat,
+    grayscale: bool,
+    brightness: i8,
+    contrast: i8,
+    deskew: bool,
+    deskew_threshold: f32,
+    skip_blank_pages: bool,
+    blank_threshold: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheManifest {
+    pdf_modified_at: u64,
+    page_count: usize,
+    format: ImageFormat,
+    render_options_hash: String,
+    #[serde(default)]
+    converted_from: Option<String>,
+    #[serde(default)]
+    document_path: Option<String>,
+    #[serde(default)]
+    page_hashes: BTreeMap<u32, String>,
+}
+
+impl CacheManifest {
+    pub fn new(pdf_path: &Path, page_count: usize, options: &ProcessingOptions) -> Result<Self> {
+        Ok(Self {
+            pdf_modified_at: modified_at(pdf_path)?,
+            page_count,
+            format: options.format,
+            render_options_hash: render_options_hash(options)?,
+            converted_from: None,
+            document_path: Some(pdf_path.display().to_string()),
+            page_hashes: BTreeMap::new(),
+        })
+    }
+
+    pub fn with_converted_from(mut self, source: Option<&Path>) -> Self {
+        self.converted_from = source.map(|source| source.display().to_string());
+        self
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.page_count
+    }
+
+    pub fn pdf_modified_at(&self) -> u64 {
+        self.pdf_modified_at
+    }
+
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    pub fn document_path(&self) -> Option<&str> {
+        self.converted_from
+            .as_deref()
+            .or(self.document_path.as_deref())
+    }
+
+    pub fn page_hashes(&self) -> &BTreeMap<u32, String> {
+        &self.page_hashes
+    }
+
+    pub fn set_page_hash(&mut self, page: u32, hash: String) {
+        self.page_hashes.insert(page, hash);
+    }
+
+    pub fn remove_page(&mut self, page: u32) {
+        self.page_count = self.page_count.saturating_sub(1);
+        self.page_hashes = std::mem::take(&mut self.page_hashes)
+            .into_iter()
+            .filter_map(|(number, hash)| match number.cmp(&page) {
+                Ordering::Less => Some((number, hash)),
+                Ordering::Equal => None,
+                Ordering::Greater => Some((number - 1, hash)),
+            })
+            .collect();
+    }
+
+    // Page hashes are recorded while rendering, so they never make a cache stale.
+    pub fn matches(&self, other: &CacheManifest) -> bool {
+        self.pdf_modified_at == other.pdf_modified_at
+            && self.page_count == other.page_count
+            && self.format == other.format
+            && self.render_options_hash == other.render_options_hash
+            && self.converted_from == other.converted_from
+            && self.document_path == other.document_path
+    }
+
+    pub fn load(data_dir: &Path) -> Result<Option<Self>> {
+        let file = manifest_file(data_dir);
+        if !file.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(&file).context("Failed to read cache manifest")?;
+        let manifest = serde_json::from_str(&contents).context("Failed to parse cache manifest")?;
+        Ok(Some(manifest))
+    }
+
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(manifest_file(data_dir), contents).context("Failed to write cache manifest")
+    }
+
+    pub fn refresh_modified_at(&mut self, pdf_path: &Path) -> Result<()> {
+        self.pdf_modified_at = modified_at(pdf_path)?;
+        Ok(())
+    }
+}
+
+fn manifest_file(data_dir: &Path) -> PathBuf {
+    data_dir.join(MANIFEST_FILE_NAME)
+}
+
+pub fn modified_at(path: &Path) -> Result<u64> {
+    let modified = fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .context("Failed to read document modification time")?;
+    Ok(modified
+        .duration_since(UNIX_EPOCH)
+        .context("Document modification time is before the Unix epoch")?
+        .as_secs())
+}
+
+fn render_options_hash(options: &ProcessingOptions) -> Result<String> {
+    let render_options = RenderOptions {
+        density: options.density,
+        resize: &options.resize,
+        format: options.format,
+        grayscale: options.grayscale,
+        brightness: options.brightness,
+        contrast: options.contrast,
+        deskew: options.deskew,
+        deskew_threshold: options.deskew_threshold,
+        skip_blank_pages: options.skip_blank_pages,
+        blank_threshold: options.blank_threshold,
+    };
+    let serialized =
+        serde_json::to_vec(&render_options).context("Failed to serialize render options")?;
+    Ok(format!("{:x}", Sha256::digest(serialized)))
+}
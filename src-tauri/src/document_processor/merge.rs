@@ -0,0 +1,154 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use lopdf::{Document, Object, ObjectId};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use super::error::Error;
+use super::pages::inherited_attributes;
+use super::selector::load_pdf_blocking;
+
+#[derive(Debug, Clone, Serialize)]
+struct MergeProgress {
+    current: usize,
+    total: usize,
+}
+
+#[tauri::command]
+pub async fn merge_documents(
+    app: AppHandle,
+    paths: Vec<PathBuf>,
+    output: PathBuf,
+) -> Result<String, Error> {
+    if paths.len() < 2 {
+        return Err(anyhow!("At least two documents are required to merge").into());
+    }
+
+    let mut documents = Vec::with_capacity(paths.len());
+    for path in &paths {
+        if !path.exists() {
+            return Err(anyhow!("Document {} does not exist", path.display()).into());
+        }
+        let document = load_pdf_blocking(path.clone())
+            .await
+            .with_context(|| format!("Failed to load {}", path.display()))?;
+        if document.is_encrypted() {
+            return Err(Error::EncryptedDocument);
+        }
+        documents.push(document);
+    }
+
+    let merge_output = output.clone();
+    tokio::task::spawn_blocking(move || merging(&app, documents, &merge_output))
+        .await
+        .context("Merge task panicked")??;
+    Ok(output.display().to_string())
+}
+
+fn merging(app: &AppHandle, documents: Vec<Document>, output: &Path) -> Result<()> {
+    let total = documents.len();
+    let mut max_id = 1;
+    let mut pages: Vec<(ObjectId, Object)> = Vec::new();
+    let mut catalog: Option<(ObjectId, Object)> = None;
+    let mut pages_root: Option<(ObjectId, Object)> = None;
+    let mut merged = Document::with_version("1.5");
+
+    for (index, mut document) in documents.into_iter().enumerate() {
+        document.renumber_objects_with(max_id);
+        max_id = document.max_id + 1;
+
+        // Pages are re-parented under a single root below, so they keep what they
+        // inherited from their own tree.
+        for page_id in document.get_pages().into_values() {
+            let mut page = document.get_dictionary(page_id)?.clone();
+            for (key, value) in inherited_attributes(&document, page_id)? {
+                if !page.has(&key) {
+                    page.set(key, value);
+                }
+            }
+            pages.push((page_id, Object::Dictionary(page)));
+        }
+
+        for (object_id, object) in document.objects {
+            let type_name = object.type_name().unwrap_or_default().to_vec();
+            match type_name.as_slice() {
+                b"Catalog" => {
+                    catalog.get_or_insert((object_id, object));
+                }
+                b"Pages" => {
+                    let Ok(dictionary) = object.as_dict() else {
+                        continue;
+                    };
+                    let mut dictionary = dictionary.clone();
+                    let id = match &pages_root {
+                        Some((id, existing)) => {
+                            if let Ok(existing) = existing.as_dict() {
+                                dictionary.extend(existing);
+                            }
+                            *id
+                        }
+                        None => object_id,
+                    };
+                    pages_root = Some((id, Object::Dictionary(dictionary)));
+                }
+                b"Page" | b"Outlines" | b"Outline" => {}
+                _ => {
+                    merged.objects.insert(object_id, object);
+                }
+            }
+        }
+
+        app.emit(
+            "merge_progress",
+            MergeProgress {
+                current: index + 1,
+                total,
+            },
+        )?;
+    }
+
+    let (pages_id, pages_object) = pages_root.context("Pages root not found")?;
+    let (catalog_id, catalog_object) = catalog.context("Catalog root not found")?;
+
+    for (page_id, page) in &pages {
+        if let Ok(dictionary) = page.as_dict() {
+            let mut dictionary = dictionary.clone();
+            dictionary.set("Parent", pages_id);
+            merged
+                .objects
+                .insert(*page_id, Object::Dictionary(dictionary));
+        }
+    }
+
+    let mut pages_dictionary = pages_object.as_dict()?.clone();
+    pages_dictionary.set("Count", pages.len() as u32);
+    pages_dictionary.set(
+        "Kids",
+        pages
+            .iter()
+            .map(|(page_id, _)| Object::Reference(*page_id))
+            .collect::<Vec<_>>(),
+    );
+    merged
+        .objects
+        .insert(pages_id, Object::Dictionary(pages_dictionary));
+
+    let mut catalog_dictionary = catalog_object.as_dict()?.clone();
+    catalog_dictionary.set("Pages", pages_id);
+    catalog_dictionary.remove(b"Outlines");
+    merged
+        .objects
+        .insert(catalog_id, Object::Dictionary(catalog_dictionary));
+
+    merged.trailer.set("Root", catalog_id);
+    merged.max_id = merged.objects.len() as u32;
+    merged.renumber_objects();
+    merged.compress();
+    merged
+        .save(output)
+        .context("Failed to save merged PDF document")?;
+
+    log::info!("Merged {} documents into {}", total, output.display());
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use tauri::AppHandle;
+use tauri_plugin_shell::ShellExt;
+
+use super::cache::{cached_document, Cache};
+use super::error::Error;
+
+const TESSERACT_BINARY: &str = "tesseract";
+
+type OcrResults = BTreeMap<u16, String>;
+
+#[tauri::command]
+pub async fn ocr_page(
+    app: AppHandle,
+    path: PathBuf,
+    page: u16,
+    lang: String,
+) -> Result<String, Error> {
+    validate_lang(&lang)?;
+    let cache = cached_document(&app, &path)?;
+    let mut results = load_results(cache.data_dir(), &lang)?;
+    if let Some(text) = results.get(&page) {
+        return Ok(text.clone());
+    }
+
+    let text = recognize_page(&app, &cache, page, &lang).await?;
+    results.insert(page, text.clone());
+    save_results(cache.data_dir(), &lang, &results)?;
+    Ok(text)
+}
+
+#[tauri::command]
+pub async fn ocr_all_pages(
+    app: AppHandle,
+    path: PathBuf,
+    lang: String,
+) -> Result<Vec<(u16, String)>, Error> {
+    validate_lang(&lang)?;
+    let cache = cached_document(&app, &path)?;
+    let mut results = load_results(cache.data_dir(), &lang)?;
+    let page_count = u16::try_from(cache.page_count()).context("Document has too many pages")?;
+
+    for page in 1..=page_count {
+        if results.contains_key(&page) || !is_rendered(&cache, page) {
+            continue;
+        }
+        let text = recognize_page(&app, &cache, page, &lang).await?;
+        results.insert(page, text);
+        save_results(cache.data_dir(), &lang, &results)?;
+    }
+
+    log::info!(
+        "Recognized {} pages of {} with language {}",
+        results.len(),
+        path.display(),
+        lang
+    );
+    Ok(results.into_iter().collect())
+}
+
+pub async fn tesseract_languages(app: &AppHandle) -> Result<Vec<String>> {
+    let output = app
+        .shell()
+        .command(TESSERACT_BINARY)
+        .arg("--list-langs")
+        .output()
+        .await
+        .context("Failed to run tesseract")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "tesseract --list-langs failed with exit code {}",
+            output.status.code().unwrap_or(1)
+        ));
+    }
+
+    // The first line is a "List of available languages" header.
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .skip(1)
+        .map(str::trim)
+        .filter(|lang| !lang.is_empty() && *lang != "osd")
+        .map(str::to_string)
+        .collect())
+}
+
+async fn recognize_page(app: &AppHandle, cache: &Cache, page: u16, lang: &str) -> Result<String> {
+    let image = cache.page_path(page as usize, cache.manifest().format());
+    if !image.exists() {
+        return Err(Error::PageNotRendered(page as u32).into());
+    }
+
+    log::info!("Running OCR on {} with language {}", image.display(), lang);
+    let output = app
+        .shell()
+        .command(TESSERACT_BINARY)
+        .arg(&image)
+        .args(["stdout", "-l", lang])
+        .output()
+        .await
+        .context("Failed to run tesseract")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "tesseract failed on page {} with exit code {}, stderr: {}",
+            page,
+            output.status.code().unwrap_or(1),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    String::from_utf8(output.stdout).context("tesseract produced invalid UTF-8")
+}
+
+fn is_rendered(cache: &Cache, page: u16) -> bool {
+    cache
+        .page_path(page as usize, cache.manifest().format())
+        .exists()
+}
+
+// Language codes end up in a file name and on the tesseract command line.
+fn validate_lang(lang: &str) -> Result<()> {
+    if lang.is_empty()
+        || !lang
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '+')
+    {
+        return Err(anyhow!("Invalid OCR language {:?}", lang));
+    }
+    Ok(())
+}
+
+fn results_file(data_dir: &Path, lang: &str) -> PathBuf {
+    data_dir.join(format!("ocr_{}.json", lang))
+}
+
+fn load_results(data_dir: &Path, lang: &str) -> Result<OcrResults> {
+    let file = results_file(data_dir, lang);
+    if !file.exists() {
+        return Ok(OcrResults::new());
+    }
+
+    let contents = fs::read_to_string(&file).context("Failed to read OCR results")?;
+    serde_json::from_str(&contents).context("Failed to parse OCR results")
+}
+
+fn save_results(data_dir: &Path, lang: &str, results: &OcrResults) -> Result<()> {
+    let file = results_file(data_dir, lang);
+    let contents = serde_json::to_string_pretty(results)?;
+    fs::write(&file, contents).context("Failed to write OCR results")
+}
@@ -0,0 +1,161 @@
+use std::{ops::RangeInclusive, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_DENSITY: u32 = 150;
+const DEFAULT_RESIZE: &str = "1000x1000";
+const DEFAULT_MAX_PARALLEL_RENDERS: usize = 4;
+const DEFAULT_MAX_RETRIES: u8 = 3;
+const DEFAULT_MAGICK_TIMEOUT_SECS: u64 = 60;
+const DEFAULT_DESKEW_THRESHOLD: f32 = 40.0;
+const DEFAULT_BLANK_THRESHOLD: f32 = 0.99;
+pub const ADJUSTMENT_RANGE: RangeInclusive<i8> = -100..=100;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    #[default]
+    WebP,
+    Png,
+    Jpeg {
+        quality: u8,
+    },
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::WebP => "webp",
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg { .. } => "jpg",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ImageFormat::WebP => "image/webp",
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg { .. } => "image/jpeg",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcessingOptions {
+    pub density: u32,
+    pub resize: String,
+    pub format: ImageFormat,
+    pub grayscale: bool,
+    pub inverted: bool,
+    pub brightness: i8,
+    pub contrast: i8,
+    pub deskew: bool,
+    pub deskew_threshold: f32,
+    pub skip_blank_pages: bool,
+    pub blank_threshold: f32,
+    pub max_parallel_renders: usize,
+    pub max_retries: u8,
+    pub magick_timeout_secs: u64,
+}
+
+impl Default for ProcessingOptions {
+    fn default() -> Self {
+        Self {
+            density: DEFAULT_DENSITY,
+            resize: DEFAULT_RESIZE.to_string(),
+            format: ImageFormat::default(),
+            grayscale: false,
+            inverted: false,
+            brightness: 0,
+            contrast: 0,
+            deskew: false,
+            deskew_threshold: DEFAULT_DESKEW_THRESHOLD,
+            skip_blank_pages: false,
+            blank_threshold: DEFAULT_BLANK_THRESHOLD,
+            max_parallel_renders: DEFAULT_MAX_PARALLEL_RENDERS,
+            max_retries: DEFAULT_MAX_RETRIES,
+            magick_timeout_secs: DEFAULT_MAGICK_TIMEOUT_SECS,
+        }
+    }
+}
+
+impl ProcessingOptions {
+    pub fn with_density(mut self, density: u32) -> Self {
+        self.density = density;
+        self
+    }
+
+    pub fn with_resize(mut self, resize: impl Into<String>) -> Self {
+        self.resize = resize.into();
+        self
+    }
+
+    pub fn with_format(mut self, format: ImageFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn with_grayscale(mut self, grayscale: bool) -> Self {
+        self.grayscale = grayscale;
+        self
+    }
+
+    pub fn with_inverted(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self
+    }
+
+    pub fn with_brightness(mut self, brightness: i8) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    pub fn with_contrast(mut self, contrast: i8) -> Self {
+        self.contrast = contrast;
+        self
+    }
+
+    pub fn with_deskew(mut self, deskew: bool) -> Self {
+        self.deskew = deskew;
+        self
+    }
+
+    pub fn with_deskew_threshold(mut self, deskew_threshold: f32) -> Self {
+        self.deskew_threshold = deskew_threshold;
+        self
+    }
+
+    pub fn with_skip_blank_pages(mut self, skip_blank_pages: bool) -> Self {
+        self.skip_blank_pages = skip_blank_pages;
+        self
+    }
+
+    pub fn with_blank_threshold(mut self, blank_threshold: f32) -> Self {
+        self.blank_threshold = blank_threshold;
+        self
+    }
+
+    pub fn with_max_parallel_renders(mut self, max_parallel_renders: usize) -> Self {
+        self.max_parallel_renders = max_parallel_renders;
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u8) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn with_magick_timeout_secs(mut self, magick_timeout_secs: u64) -> Self {
+        self.magick_timeout_secs = magick_timeout_secs;
+        self
+    }
+
+    pub fn magick_timeout(&self) -> Duration {
+        Duration::from_secs(self.magick_timeout_secs)
+    }
+
+    pub fn parallelism(&self, page_count: usize) -> usize {
+        self.max_parallel_renders.min(page_count).max(1)
+    }
+}
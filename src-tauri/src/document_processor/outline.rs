@@ -0,0 +1,117 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use anyhow::Result;
+use lopdf::{Dictionary, Document, Object, ObjectId};
+use serde::Serialize;
+
+use super::error::Error;
+use super::selector::{decode_pdf_string, load_pdf_blocking};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OutlineEntry {
+    title: String,
+    page: u32,
+    children: Vec<OutlineEntry>,
+}
+
+#[tauri::command]
+pub async fn extract_outline(path: PathBuf) -> Result<Vec<OutlineEntry>, Error> {
+    read_outline(path).await.map_err(Error::CommandError)
+}
+
+async fn read_outline(path: PathBuf) -> Result<Vec<OutlineEntry>> {
+    let document = load_pdf_blocking(path).await?;
+    let pages: HashMap<ObjectId, u32> = document
+        .get_pages()
+        .into_iter()
+        .map(|(page, id)| (id, page))
+        .collect();
+
+    let outlines = document
+        .catalog()
+        .ok()
+        .and_then(|catalog| catalog.get(b"Outlines").ok())
+        .and_then(|outlines| resolve(&document, outlines))
+        .and_then(|outlines| outlines.as_dict().ok());
+
+    let Some(outlines) = outlines else {
+        return Ok(Vec::new());
+    };
+
+    let mut visited = HashSet::new();
+    Ok(read_entries(
+        &document,
+        &pages,
+        outlines.get(b"First").ok(),
+        &mut visited,
+    ))
+}
+
+fn read_entries(
+    document: &Document,
+    pages: &HashMap<ObjectId, u32>,
+    first: Option<&Object>,
+    visited: &mut HashSet<ObjectId>,
+) -> Vec<OutlineEntry> {
+    let mut entries = Vec::new();
+    let mut current = first.and_then(|first| first.as_reference().ok());
+
+    while let Some(id) = current {
+        if !visited.insert(id) {
+            log::warn!("Outline contains a cycle at object {:?}", id);
+            break;
+        }
+        let Ok(item) = document.get_dictionary(id) else {
+            break;
+        };
+
+        let title = item
+            .get(b"Title")
+            .and_then(Object::as_str)
+            .map(decode_pdf_string)
+            .unwrap_or_default();
+        let page = destination_page(document, pages, item).unwrap_or(1);
+        let children = read_entries(document, pages, item.get(b"First").ok(), visited);
+
+        entries.push(OutlineEntry {
+            title,
+            page,
+            children,
+        });
+        current = item.get(b"Next").and_then(Object::as_reference).ok();
+    }
+
+    entries
+}
+
+fn destination_page(
+    document: &Document,
+    pages: &HashMap<ObjectId, u32>,
+    item: &Dictionary,
+) -> Option<u32> {
+    let destination = match item.get(b"Dest") {
+        Ok(destination) => destination,
+        Err(_) => {
+            let action = resolve(document, item.get(b"A").ok()?)?.as_dict().ok()?;
+            action.get(b"D").ok()?
+        }
+    };
+
+    match resolve(document, destination)? {
+        Object::Array(destination) => {
+            let page_id = destination.first()?.as_reference().ok()?;
+            pages.get(&page_id).copied()
+        }
+        _ => None,
+    }
+}
+
+fn resolve<'a>(document: &'a Document, object: &'a Object) -> Option<&'a Object> {
+    match object {
+        Object::Reference(id) => document.get_object(*id).ok(),
+        object => Some(object),
+    }
+}
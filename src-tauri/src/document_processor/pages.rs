@@ -0,0 +1,286 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use lopdf::{Document, Object, ObjectId};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+
+use super::cache::{
+    blank_marker_path, create_output_paths, inverted_page_output_path, page_output_path, Cache,
+};
+use super::error::Error;
+use super::manager::{document_manager, document_session};
+use super::manifest::CacheManifest;
+use super::options::ProcessingOptions;
+use super::selector::{load_pdf_blocking, MAX_PAGE_TREE_DEPTH};
+
+pub const INHERITABLE_PAGE_KEYS: [&[u8]; 4] = [b"Resources", b"MediaBox", b"CropBox", b"Rotate"];
+
+#[derive(Debug, Clone, Serialize)]
+struct PageDeleted {
+    session_id: String,
+    page: u32,
+}
+
+#[tauri::command]
+pub async fn reorder_pages(
+    path: PathBuf,
+    new_order: Vec<u32>,
+    output: PathBuf,
+) -> Result<(), Error> {
+    let mut document = load_pdf_blocking(path.clone()).await?;
+    if document.is_encrypted() {
+        return Err(Error::EncryptedDocument);
+    }
+
+    let pages = document.get_pages();
+    validate_permutation(&new_order, pages.len())?;
+    let page_ids: Vec<ObjectId> = new_order.iter().map(|page| pages[page]).collect();
+    rebuild_page_tree(&mut document, &page_ids)?;
+    document.prune_objects();
+    document
+        .save(&output)
+        .context("Failed to save reordered document")?;
+
+    log::info!(
+        "Saved {} with pages reordered to {}",
+        path.display(),
+        output.display()
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn delete_page(
+    app: AppHandle,
+    document_id: Uuid,
+    page: u32,
+    output: PathBuf,
+) -> Result<(), Error> {
+    let session = document_session(&app, &document_id)?;
+    let path = session.path;
+    let mut document = load_pdf_blocking(path.clone()).await?;
+    if document.is_encrypted() {
+        return Err(Error::EncryptedDocument);
+    }
+
+    let page_count = document.get_pages().len() as u32;
+    if page == 0 || page > page_count {
+        return Err(Error::PageOutOfRange { page, page_count });
+    }
+    if page_count == 1 {
+        return Err(anyhow!("Cannot delete the only page of a document").into());
+    }
+
+    document.delete_pages(&[page]);
+    document.prune_objects();
+    document
+        .save(&output)
+        .context("Failed to save document without the deleted page")?;
+    log::info!(
+        "Deleted page {} of {} into {}",
+        page,
+        path.display(),
+        output.display()
+    );
+
+    if is_same_file(&path, &output) {
+        remove_cached_page(&app, &output, page)?;
+        let (data_dir, _output_file_name) =
+            create_output_paths(&app, &output, &ProcessingOptions::default())?;
+        if let Some(cache) = Cache::load(&data_dir)? {
+            document_manager(&app)?.set_cache(&document_id, cache);
+        }
+    }
+
+    app.emit(
+        "page_deleted",
+        PageDeleted {
+            session_id: session.client_id,
+            page,
+        },
+    )
+    .map_err(anyhow::Error::from)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn extract_page(path: PathBuf, page: u32, output: PathBuf) -> Result<(), Error> {
+    let mut document = load_pdf_blocking(path.clone()).await?;
+    if document.is_encrypted() {
+        return Err(Error::EncryptedDocument);
+    }
+
+    let page_count = document.get_pages().len() as u32;
+    if page == 0 || page > page_count {
+        return Err(Error::PageOutOfRange { page, page_count });
+    }
+
+    let other_pages: Vec<u32> = (1..=page_count).filter(|other| *other != page).collect();
+    document.delete_pages(&other_pages);
+    // Outlines point at pages that no longer exist and would keep their objects alive.
+    let root = document.trailer.get(b"Root").and_then(Object::as_reference);
+    if let Ok(catalog) = root
+        .and_then(|root| document.get_object_mut(root))
+        .and_then(Object::as_dict_mut)
+    {
+        catalog.remove(b"Outlines");
+    }
+    document.prune_objects();
+    document.compress();
+    document
+        .save(&output)
+        .context("Failed to save extracted page")?;
+
+    log::info!(
+        "Extracted page {} of {} into {}",
+        page,
+        path.display(),
+        output.display()
+    );
+    Ok(())
+}
+
+fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (dunce::canonicalize(a), dunce::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn remove_cached_page(app: &AppHandle, path: &Path, page: u32) -> Result<()> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(app, path, &ProcessingOptions::default())?;
+    let Some(mut manifest) = CacheManifest::load(&data_dir)? else {
+        return Ok(());
+    };
+    let format = manifest.format();
+    let page = page as usize;
+
+    for file in [
+        page_output_path(&data_dir, page, format),
+        inverted_page_output_path(&data_dir, page, format),
+        blank_marker_path(&data_dir, page),
+    ] {
+        if file.exists() {
+            fs::remove_file(&file).context("Failed to remove cached page")?;
+        }
+    }
+
+    for next in page + 1..=manifest.page_count() {
+        let renames = [
+            (
+                page_output_path(&data_dir, next, format),
+                page_output_path(&data_dir, next - 1, format),
+            ),
+            (
+                inverted_page_output_path(&data_dir, next, format),
+                inverted_page_output_path(&data_dir, next - 1, format),
+            ),
+            (
+                blank_marker_path(&data_dir, next),
+                blank_marker_path(&data_dir, next - 1),
+            ),
+        ];
+        for (from, to) in renames {
+            if from.exists() {
+                fs::rename(&from, &to).context("Failed to renumber cached page")?;
+            }
+        }
+    }
+
+    manifest.remove_page(page as u32);
+    manifest.refresh_modified_at(path)?;
+    manifest.save(&data_dir)
+}
+
+fn validate_permutation(order: &[u32], page_count: usize) -> Result<()> {
+    if order.len() != page_count {
+        return Err(anyhow!(
+            "Expected {} pages in the new order, got {}",
+            page_count,
+            order.len()
+        ));
+    }
+
+    let mut seen = vec![false; page_count];
+    for &page in order {
+        let index = (page as usize)
+            .checked_sub(1)
+            .filter(|index| *index < page_count)
+            .ok_or_else(|| anyhow!("Page {} is out of range 1-{}", page, page_count))?;
+        if std::mem::replace(&mut seen[index], true) {
+            return Err(anyhow!("Page {} appears more than once", page));
+        }
+    }
+    Ok(())
+}
+
+// Pages become direct children of the root node, so anything they inherited
+// from intermediate nodes is copied onto the page itself first.
+fn rebuild_page_tree(document: &mut Document, page_ids: &[ObjectId]) -> Result<()> {
+    let root_id = document
+        .catalog()
+        .and_then(|catalog| catalog.get(b"Pages"))
+        .and_then(Object::as_reference)
+        .context("Failed to find the page tree root")?;
+
+    for &page_id in page_ids {
+        let inherited = inherited_attributes(document, page_id)?;
+        let page = document
+            .get_object_mut(page_id)
+            .and_then(Object::as_dict_mut)
+            .context("Failed to read page dictionary")?;
+        for (key, value) in inherited {
+            if !page.has(&key) {
+                page.set(key, value);
+            }
+        }
+        page.set("Parent", root_id);
+    }
+
+    let root = document
+        .get_object_mut(root_id)
+        .and_then(Object::as_dict_mut)
+        .context("Failed to read page tree root")?;
+    root.set(
+        "Kids",
+        page_ids
+            .iter()
+            .map(|&page_id| Object::Reference(page_id))
+            .collect::<Vec<_>>(),
+    );
+    root.set("Count", page_ids.len() as i64);
+    Ok(())
+}
+
+pub fn inherited_attributes(
+    document: &Document,
+    page_id: ObjectId,
+) -> Result<Vec<(Vec<u8>, Object)>> {
+    let mut attributes: Vec<(Vec<u8>, Object)> = Vec::new();
+    let mut node = document
+        .get_dictionary(page_id)
+        .context("Failed to read page dictionary")?;
+    for _ in 0..MAX_PAGE_TREE_DEPTH {
+        let Ok(parent) = node.get(b"Parent").and_then(Object::as_reference) else {
+            break;
+        };
+        node = document
+            .get_dictionary(parent)
+            .context("Failed to read page tree node")?;
+        for key in INHERITABLE_PAGE_KEYS {
+            if attributes.iter().any(|(existing, _)| existing == key) {
+                continue;
+            }
+            if let Ok(value) = node.get(key) {
+                attributes.push((key.to_vec(), value.clone()));
+            }
+        }
+    }
+    Ok(attributes)
+}
@@ -0,0 +1,85 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{anyhow, Context, Result};
+use tauri::AppHandle;
+
+use super::cache::create_output_paths;
+use super::error::Error;
+use super::options::ProcessingOptions;
+
+const PROCESSING_LOG_FILE_NAME: &str = "processing.log";
+
+#[derive(Debug, Clone)]
+pub struct ProcessingLogger(Arc<Mutex<BufWriter<File>>>);
+
+impl ProcessingLogger {
+    pub fn open(data_dir: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log_file(data_dir))
+            .context("Failed to open processing log")?;
+        Ok(Self(Arc::new(Mutex::new(BufWriter::new(file)))))
+    }
+
+    pub fn record(
+        &self,
+        args: &[String],
+        code: Option<i32>,
+        stdout: &[u8],
+        stderr: &[u8],
+        duration: Duration,
+    ) -> Result<()> {
+        let mut writer = self
+            .0
+            .lock()
+            .map_err(|_| anyhow!("Processing log lock poisoned"))?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+
+        writeln!(
+            writer,
+            "[{}] magick {} (exit code {}, {} ms)",
+            timestamp,
+            args.join(" "),
+            code.unwrap_or(1),
+            duration.as_millis()
+        )?;
+        writeln!(
+            writer,
+            "stdout: {}",
+            String::from_utf8_lossy(stdout).trim_end()
+        )?;
+        writeln!(
+            writer,
+            "stderr: {}",
+            String::from_utf8_lossy(stderr).trim_end()
+        )?;
+        writer.flush().context("Failed to write processing log")
+    }
+}
+
+#[tauri::command]
+pub fn get_document_log(app: AppHandle, path: PathBuf) -> Result<String, Error> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(&app, &path, &ProcessingOptions::default())?;
+    let file = log_file(&data_dir);
+    if !file.exists() {
+        return Ok(String::new());
+    }
+
+    let contents = fs::read_to_string(&file).context("Failed to read processing log")?;
+    Ok(contents)
+}
+
+fn log_file(data_dir: &Path) -> PathBuf {
+    data_dir.join(PROCESSING_LOG_FILE_NAME)
+}
@@ -0,0 +1,95 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use super::error::Error;
+
+const RECENTS_FILE_NAME: &str = "recent_documents.json";
+const MAX_RECENT_DOCUMENTS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentDocument {
+    path: String,
+    name: String,
+    last_opened: u64,
+}
+
+impl RecentDocument {
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[tauri::command]
+pub fn add_recent_document(app: AppHandle, path: PathBuf) -> Result<(), Error> {
+    record_recent_document(&app, &path).map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub fn get_recent_documents(app: AppHandle) -> Result<Vec<RecentDocument>, Error> {
+    load_recent_documents(&app).map_err(Error::CommandError)
+}
+
+pub fn record_recent_document(app: &AppHandle, path: &Path) -> Result<()> {
+    let path_string = path.display().to_string();
+    let mut documents = load_recent_documents(app)?;
+    documents.retain(|document| document.path != path_string);
+
+    let last_opened = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("System clock is before the Unix epoch")?
+        .as_secs();
+
+    documents.insert(
+        0,
+        RecentDocument {
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path_string.clone()),
+            path: path_string,
+            last_opened,
+        },
+    );
+    documents.truncate(MAX_RECENT_DOCUMENTS);
+
+    save_recent_documents(app, &documents)
+}
+
+pub fn load_recent_documents(app: &AppHandle) -> Result<Vec<RecentDocument>> {
+    let file = recents_file(app)?;
+    if !file.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&file).context("Failed to read recent documents")?;
+    serde_json::from_str(&contents).context("Failed to parse recent documents")
+}
+
+fn save_recent_documents(app: &AppHandle, documents: &[RecentDocument]) -> Result<()> {
+    let file = recents_file(app)?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).context("Failed to create app data directory")?;
+    }
+
+    let contents = serde_json::to_string_pretty(documents)?;
+    fs::write(&file, contents).context("Failed to write recent documents")
+}
+
+fn recents_file(app: &AppHandle) -> Result<PathBuf> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .context("Failed to resolve app data directory")?;
+    Ok(app_data_dir.join(RECENTS_FILE_NAME))
+}
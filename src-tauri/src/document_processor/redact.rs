@@ -0,0 +1,227 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use lopdf::{
+    content::{Content, Operation},
+    Dictionary, Document, Object, ObjectId, Stream,
+};
+
+use super::error::Error;
+use super::search::{number, translate, AVERAGE_GLYPH_WIDTH, IDENTITY_MATRIX};
+use super::selector::{decode_pdf_string, load_pdf_blocking};
+
+#[tauri::command]
+pub async fn redact_region(
+    path: PathBuf,
+    page: u16,
+    regions: Vec<[f32; 4]>,
+    output: PathBuf,
+) -> Result<(), Error> {
+    if regions.is_empty() {
+        return Err(anyhow!("No regions to redact").into());
+    }
+
+    let mut document = load_pdf_blocking(path.clone()).await?;
+    if document.is_encrypted() {
+        return Err(Error::EncryptedDocument);
+    }
+    let page_id = *document
+        .get_pages()
+        .get(&(page as u32))
+        .ok_or_else(|| anyhow!("Page {} does not exist", page))?;
+
+    let removed = redact_page(&mut document, page_id, &regions)?;
+    // The original content streams are now unreferenced and must not survive in the file.
+    document.prune_objects();
+    document
+        .save(&output)
+        .context("Failed to save redacted document")?;
+
+    log::info!(
+        "Redacted {} regions on page {} of {} into {}, removing {} text runs",
+        regions.len(),
+        page,
+        path.display(),
+        output.display(),
+        removed
+    );
+    Ok(())
+}
+
+fn redact_page(document: &mut Document, page_id: ObjectId, regions: &[[f32; 4]]) -> Result<usize> {
+    let content = document
+        .get_page_content(page_id)
+        .context("Failed to read page content stream")?;
+    let content = Content::decode(&content).context("Failed to decode page content stream")?;
+    let (operations, removed) = remove_text_in_regions(content.operations, regions);
+
+    // The original content is wrapped in q/Q so a dangling transform can't move the boxes.
+    let save_state = Content {
+        operations: vec![Operation::new("q", vec![])],
+    };
+    let save_id = document.add_object(Stream::new(Dictionary::new(), save_state.encode()?));
+    let content_id = document.add_object(Stream::new(
+        Dictionary::new(),
+        Content { operations }.encode()?,
+    ));
+    let boxes_id = document.add_object(Stream::new(
+        Dictionary::new(),
+        redaction_boxes(regions).encode()?,
+    ));
+    document
+        .get_object_mut(page_id)
+        .and_then(Object::as_dict_mut)
+        .context("Failed to read page dictionary")?
+        .set(
+            "Contents",
+            vec![
+                Object::Reference(save_id),
+                Object::Reference(content_id),
+                Object::Reference(boxes_id),
+            ],
+        );
+    Ok(removed)
+}
+
+// Uses the same approximate text placement as search, so a run is dropped whole
+// whenever any part of it touches a region. Text inside form XObjects is not reached.
+fn remove_text_in_regions(
+    operations: Vec<Operation>,
+    regions: &[[f32; 4]],
+) -> (Vec<Operation>, usize) {
+    let mut kept = Vec::with_capacity(operations.len());
+    let mut removed = 0;
+    let mut text_matrix = IDENTITY_MATRIX;
+    let mut line_matrix = IDENTITY_MATRIX;
+    let mut font_size = 0.0;
+    let mut leading = 0.0;
+
+    for mut operation in operations {
+        let operands = &operation.operands;
+        match operation.operator.as_str() {
+            "BT" => {
+                text_matrix = IDENTITY_MATRIX;
+                line_matrix = IDENTITY_MATRIX;
+            }
+            "Tf" => font_size = operands.get(1).and_then(number).unwrap_or(font_size),
+            "TL" => leading = operands.first().and_then(number).unwrap_or(leading),
+            "Td" | "TD" => {
+                if let (Some(tx), Some(ty)) = (
+                    operands.first().and_then(number),
+                    operands.get(1).and_then(number),
+                ) {
+                    if operation.operator == "TD" {
+                        leading = -ty;
+                    }
+                    line_matrix = translate(line_matrix, tx, ty);
+                    text_matrix = line_matrix;
+                }
+            }
+            "Tm" => {
+                let values: Option<Vec<f32>> = operands.iter().map(number).collect();
+                if let Some(Ok(matrix)) = values.map(<[f32; 6]>::try_from) {
+                    line_matrix = matrix;
+                    text_matrix = matrix;
+                }
+            }
+            "T*" => {
+                line_matrix = translate(line_matrix, 0.0, -leading);
+                text_matrix = line_matrix;
+            }
+            "Tj" => {
+                if show_text(&mut text_matrix, font_size, operands.first(), regions) {
+                    removed += 1;
+                    continue;
+                }
+            }
+            "'" | "\"" => {
+                line_matrix = translate(line_matrix, 0.0, -leading);
+                text_matrix = line_matrix;
+                if show_text(&mut text_matrix, font_size, operands.last(), regions) {
+                    removed += 1;
+                    // Keep the line move (and spacing for ") without showing the string.
+                    if operation.operator == "\"" && operands.len() == 3 {
+                        kept.push(Operation::new("Tw", operands[..1].to_vec()));
+                        kept.push(Operation::new("Tc", operands[1..2].to_vec()));
+                    }
+                    kept.push(Operation::new("T*", vec![]));
+                    continue;
+                }
+            }
+            "TJ" => {
+                if let Some(Object::Array(items)) = operation.operands.first_mut() {
+                    for item in items.iter_mut() {
+                        match number(item) {
+                            Some(offset) => {
+                                text_matrix =
+                                    translate(text_matrix, -offset / 1000.0 * font_size, 0.0);
+                            }
+                            None => {
+                                let advance = text_advance(font_size, Some(item));
+                                if show_text(&mut text_matrix, font_size, Some(item), regions) {
+                                    removed += 1;
+                                    // An equivalent kerning offset keeps later glyphs in place.
+                                    *item = if font_size == 0.0 {
+                                        Object::Integer(0)
+                                    } else {
+                                        Object::Real(-advance / font_size * 1000.0)
+                                    };
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        kept.push(operation);
+    }
+
+    (kept, removed)
+}
+
+// Advances the text matrix past the string and reports whether it overlaps a region.
+fn show_text(
+    text_matrix: &mut [f32; 6],
+    font_size: f32,
+    operand: Option<&Object>,
+    regions: &[[f32; 4]],
+) -> bool {
+    let advance = text_advance(font_size, operand);
+    if advance == 0.0 {
+        return false;
+    }
+
+    let [a, b, c, d, x, y] = *text_matrix;
+    let bbox = [x, y, x + advance * a.hypot(b), y + font_size * c.hypot(d)];
+    *text_matrix = translate(*text_matrix, advance, 0.0);
+    regions.iter().any(|region| intersects(bbox, *region))
+}
+
+fn text_advance(font_size: f32, operand: Option<&Object>) -> f32 {
+    let Some(Object::String(bytes, _)) = operand else {
+        return 0.0;
+    };
+    decode_pdf_string(bytes).chars().count() as f32 * font_size * AVERAGE_GLYPH_WIDTH
+}
+
+fn intersects([x0, y0, x1, y1]: [f32; 4], [x, y, width, height]: [f32; 4]) -> bool {
+    x0 < x + width && x1 > x && y0 < y + height && y1 > y
+}
+
+fn redaction_boxes(regions: &[[f32; 4]]) -> Content {
+    let mut operations = vec![
+        Operation::new("Q", vec![]),
+        Operation::new("q", vec![]),
+        Operation::new("g", vec![Object::Integer(0)]),
+    ];
+    for [x, y, width, height] in regions {
+        operations.push(Operation::new(
+            "re",
+            vec![(*x).into(), (*y).into(), (*width).into(), (*height).into()],
+        ));
+    }
+    operations.push(Operation::new("f", vec![]));
+    operations.push(Operation::new("Q", vec![]));
+    Content { operations }
+}
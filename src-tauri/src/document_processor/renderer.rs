@@ -0,0 +1,160 @@
+use std::path::Path;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tauri::AppHandle;
+
+use super::options::ProcessingOptions;
+use super::processing_log::ProcessingLogger;
+use super::selector::{
+    create_magick_args, emit_blank_page_skipped, emit_page_errors, emit_progress, is_blank_page,
+    page_input_arg, run_magick, send_webp_image,
+};
+
+#[async_trait]
+pub trait PageRenderer: Send + Sync {
+    async fn render_page(
+        &self,
+        input: &str,
+        page_index: usize,
+        output: &Path,
+        options: &ProcessingOptions,
+    ) -> Result<()>;
+
+    async fn is_blank(&self, image: &Path, threshold: f32) -> Result<bool>;
+}
+
+// Where `process_pages` reports each page, so rendering doesn't depend on the app handle.
+pub trait RenderEvents: Send + Sync {
+    fn page_rendered(&self, page: usize, image: &Path, mime_type: &str) -> Result<()>;
+
+    fn blank_page_skipped(&self, page: usize) -> Result<()>;
+
+    fn progress(&self, current: usize, total: usize) -> Result<()>;
+
+    fn page_errors(&self, failed_pages: Vec<u32>, errors: Vec<String>) -> Result<()>;
+}
+
+#[derive(Debug, Clone)]
+pub struct MagickRenderer {
+    app: AppHandle,
+    logger: Option<ProcessingLogger>,
+}
+
+impl MagickRenderer {
+    pub fn new(app: AppHandle, logger: Option<ProcessingLogger>) -> Self {
+        Self { app, logger }
+    }
+}
+
+#[async_trait]
+impl PageRenderer for MagickRenderer {
+    async fn render_page(
+        &self,
+        input: &str,
+        page_index: usize,
+        output: &Path,
+        options: &ProcessingOptions,
+    ) -> Result<()> {
+        let args = create_magick_args(&page_input_arg(input, page_index), output, options);
+        if options.deskew {
+            log::info!(
+                "Deskewing page {} of {} with threshold {}%",
+                page_index + 1,
+                input,
+                options.deskew_threshold
+            );
+        }
+        run_magick(
+            &self.app,
+            &args,
+            options.max_retries,
+            options.magick_timeout(),
+            self.logger.as_ref(),
+        )
+        .await
+    }
+
+    async fn is_blank(&self, image: &Path, threshold: f32) -> Result<bool> {
+        is_blank_page(&self.app, image, threshold).await
+    }
+}
+
+// Forwards render events to the frontend session that is preparing `input`.
+pub struct SessionEvents<'a> {
+    app: &'a AppHandle,
+    session_id: &'a str,
+    input: &'a str,
+}
+
+impl<'a> SessionEvents<'a> {
+    pub fn new(app: &'a AppHandle, session_id: &'a str, input: &'a str) -> Self {
+        Self {
+            app,
+            session_id,
+            input,
+        }
+    }
+}
+
+impl RenderEvents for SessionEvents<'_> {
+    fn page_rendered(&self, page: usize, image: &Path, mime_type: &str) -> Result<()> {
+        send_webp_image(self.app, self.session_id, image, page, mime_type)
+    }
+
+    fn blank_page_skipped(&self, page: usize) -> Result<()> {
+        emit_blank_page_skipped(self.app, self.session_id, page)
+    }
+
+    fn progress(&self, current: usize, total: usize) -> Result<()> {
+        emit_progress(self.app, self.session_id, self.input, current, total)
+    }
+
+    fn page_errors(&self, failed_pages: Vec<u32>, errors: Vec<String>) -> Result<()> {
+        emit_page_errors(self.app, self.session_id, failed_pages, errors)
+    }
+}
+
+#[cfg(test)]
+pub use mock::MockRenderer;
+
+#[cfg(test)]
+mod mock {
+    use std::{fs, path::Path};
+
+    use anyhow::{Context, Result};
+    use async_trait::async_trait;
+
+    use super::PageRenderer;
+    use crate::document_processor::options::ProcessingOptions;
+
+    // A 1x1 transparent PNG.
+    const PLACEHOLDER_PNG: [u8; 67] = [
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1f,
+        0x15, 0xc4, 0x89, 0x00, 0x00, 0x00, 0x0a, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0x00,
+        0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0d, 0x0a, 0x2d, 0xb4, 0x00, 0x00, 0x00, 0x00, 0x49,
+        0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+    ];
+
+    // Writes a placeholder image for every page, so rendering can run without ImageMagick.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct MockRenderer;
+
+    #[async_trait]
+    impl PageRenderer for MockRenderer {
+        async fn render_page(
+            &self,
+            _input: &str,
+            _page_index: usize,
+            output: &Path,
+            _options: &ProcessingOptions,
+        ) -> Result<()> {
+            fs::write(output, PLACEHOLDER_PNG).context("Failed to write placeholder page image")
+        }
+
+        async fn is_blank(&self, _image: &Path, _threshold: f32) -> Result<bool> {
+            Ok(false)
+        }
+    }
+}
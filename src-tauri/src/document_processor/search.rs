@@ -0,0 +1,331 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use lopdf::{content::Content, Document, Object, ObjectId};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use uuid::Uuid;
+
+use super::cache::create_output_paths;
+use super::error::Error;
+use super::manager::document_session;
+use super::manifest::modified_at;
+use super::options::ProcessingOptions;
+use super::selector::{decode_pdf_string, load_pdf_blocking, render_input};
+
+const TEXT_INDEX_FILE_NAME: &str = "text_index.json";
+const SNIPPET_CONTEXT_CHARS: usize = 40;
+const TJ_SPACE_THRESHOLD: f32 = 200.0;
+// Without font metrics, glyphs are assumed to be half as wide as the font size.
+pub const AVERAGE_GLYPH_WIDTH: f32 = 0.5;
+pub const IDENTITY_MATRIX: [f32; 6] = [1.0, 0.0, 0.0, 1.0, 0.0, 0.0];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchMatch {
+    page: u32,
+    snippet: String,
+    // Byte offsets of the match inside `snippet`.
+    match_start: usize,
+    match_end: usize,
+    bbox: Option<[f32; 4]>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TextIndex {
+    pdf_modified_at: u64,
+    pages: Vec<PageText>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PageText {
+    page: u32,
+    text: String,
+    runs: Vec<TextRun>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TextRun {
+    start: usize,
+    end: usize,
+    bbox: [f32; 4],
+}
+
+#[tauri::command]
+pub async fn search_document(
+    app: AppHandle,
+    document_id: Uuid,
+    query: String,
+    case_sensitive: bool,
+) -> Result<Vec<SearchMatch>, Error> {
+    if query.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let path = document_session(&app, &document_id)?.path;
+    let index = load_text_index(&app, &path).await?;
+    let matches: Vec<SearchMatch> = index
+        .pages
+        .iter()
+        .flat_map(|page| search_page(page, &query, case_sensitive))
+        .collect();
+    log::info!(
+        "Found {} matches for {:?} in {}",
+        matches.len(),
+        query,
+        path.display()
+    );
+    Ok(matches)
+}
+
+async fn load_text_index(app: &AppHandle, path: &Path) -> Result<TextIndex, Error> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(app, path, &ProcessingOptions::default())?;
+    let input = render_input(path, &data_dir);
+    let pdf_modified_at = modified_at(&input)?;
+    let index_file = data_dir.join(TEXT_INDEX_FILE_NAME);
+
+    if let Some(index) = fs::read_to_string(&index_file)
+        .ok()
+        .and_then(|contents| serde_json::from_str::<TextIndex>(&contents).ok())
+        .filter(|index| index.pdf_modified_at == pdf_modified_at)
+    {
+        return Ok(index);
+    }
+
+    let document = load_pdf_blocking(input.clone()).await?;
+    if document.is_encrypted() {
+        return Err(Error::EncryptedDocument);
+    }
+    log::info!("Building text index for {}", input.display());
+    let pages = document
+        .get_pages()
+        .into_iter()
+        .map(|(page, page_id)| {
+            extract_positioned_text(&document, page, page_id).unwrap_or_else(|err| {
+                log::warn!("Failed to extract text from page {}: {}", page, err);
+                PageText {
+                    page,
+                    ..PageText::default()
+                }
+            })
+        })
+        .collect();
+    let index = TextIndex {
+        pdf_modified_at,
+        pages,
+    };
+
+    if let Err(err) = save_text_index(&data_dir, &index) {
+        log::warn!("Failed to cache text index: {}", err);
+    }
+    Ok(index)
+}
+
+fn save_text_index(data_dir: &Path, index: &TextIndex) -> Result<()> {
+    fs::create_dir_all(data_dir).context("Failed to create data directory")?;
+    let contents = serde_json::to_string(index)?;
+    fs::write(data_dir.join(TEXT_INDEX_FILE_NAME), contents).context("Failed to write text index")
+}
+
+// Tracks just enough of the text state to place each shown string on the page.
+// The CTM is ignored, so positions are only exact for untransformed text.
+fn extract_positioned_text(document: &Document, page: u32, page_id: ObjectId) -> Result<PageText> {
+    let content = document
+        .get_page_content(page_id)
+        .context("Failed to read page content stream")?;
+    let content = Content::decode(&content).context("Failed to decode page content stream")?;
+
+    let mut page_text = PageText {
+        page,
+        ..PageText::default()
+    };
+    let mut text_matrix = IDENTITY_MATRIX;
+    let mut line_matrix = IDENTITY_MATRIX;
+    let mut font_size = 0.0;
+    let mut leading = 0.0;
+
+    for operation in &content.operations {
+        let operands = &operation.operands;
+        match operation.operator.as_str() {
+            "BT" => {
+                text_matrix = IDENTITY_MATRIX;
+                line_matrix = IDENTITY_MATRIX;
+            }
+            "ET" => push_line_break(&mut page_text.text),
+            "Tf" => font_size = operands.get(1).and_then(number).unwrap_or(font_size),
+            "TL" => leading = operands.first().and_then(number).unwrap_or(leading),
+            "Td" | "TD" => {
+                let (Some(tx), Some(ty)) = (
+                    operands.first().and_then(number),
+                    operands.get(1).and_then(number),
+                ) else {
+                    continue;
+                };
+                if operation.operator == "TD" {
+                    leading = -ty;
+                }
+                line_matrix = translate(line_matrix, tx, ty);
+                text_matrix = line_matrix;
+                push_line_break(&mut page_text.text);
+            }
+            "Tm" => {
+                let values: Option<Vec<f32>> = operands.iter().map(number).collect();
+                let Some(Ok(matrix)) = values.map(<[f32; 6]>::try_from) else {
+                    continue;
+                };
+                line_matrix = matrix;
+                text_matrix = matrix;
+                push_line_break(&mut page_text.text);
+            }
+            "T*" => {
+                line_matrix = translate(line_matrix, 0.0, -leading);
+                text_matrix = line_matrix;
+                push_line_break(&mut page_text.text);
+            }
+            "Tj" => show_text(
+                &mut page_text,
+                &mut text_matrix,
+                font_size,
+                operands.first(),
+            ),
+            "'" | "\"" => {
+                line_matrix = translate(line_matrix, 0.0, -leading);
+                text_matrix = line_matrix;
+                push_line_break(&mut page_text.text);
+                show_text(&mut page_text, &mut text_matrix, font_size, operands.last());
+            }
+            "TJ" => {
+                let Some(Object::Array(items)) = operands.first() else {
+                    continue;
+                };
+                for item in items {
+                    match number(item) {
+                        Some(offset) => {
+                            if offset < -TJ_SPACE_THRESHOLD {
+                                page_text.text.push(' ');
+                            }
+                            text_matrix = translate(text_matrix, -offset / 1000.0 * font_size, 0.0);
+                        }
+                        None => show_text(&mut page_text, &mut text_matrix, font_size, Some(item)),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let trimmed = page_text.text.trim_end().len();
+    page_text.text.truncate(trimmed);
+    Ok(page_text)
+}
+
+fn show_text(
+    page_text: &mut PageText,
+    text_matrix: &mut [f32; 6],
+    font_size: f32,
+    operand: Option<&Object>,
+) {
+    let Some(Object::String(bytes, _)) = operand else {
+        return;
+    };
+    let text = decode_pdf_string(bytes);
+    if text.is_empty() {
+        return;
+    }
+
+    let [a, b, c, d, x, y] = *text_matrix;
+    let advance = text.chars().count() as f32 * font_size * AVERAGE_GLYPH_WIDTH;
+    let width = advance * a.hypot(b);
+    let height = font_size * c.hypot(d);
+    let start = page_text.text.len();
+    page_text.text.push_str(&text);
+    page_text.runs.push(TextRun {
+        start,
+        end: page_text.text.len(),
+        bbox: [x, y, x + width, y + height],
+    });
+    *text_matrix = translate(*text_matrix, advance, 0.0);
+}
+
+fn push_line_break(text: &mut String) {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+}
+
+pub fn translate([a, b, c, d, e, f]: [f32; 6], tx: f32, ty: f32) -> [f32; 6] {
+    [a, b, c, d, e + tx * a + ty * c, f + tx * b + ty * d]
+}
+
+pub fn number(object: &Object) -> Option<f32> {
+    match object {
+        Object::Integer(value) => Some(*value as f32),
+        Object::Real(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn search_page(page: &PageText, query: &str, case_sensitive: bool) -> Vec<SearchMatch> {
+    find_matches(&page.text, query, case_sensitive)
+        .into_iter()
+        .map(|(start, end)| {
+            let snippet_start = page.text[..start]
+                .char_indices()
+                .rev()
+                .nth(SNIPPET_CONTEXT_CHARS - 1)
+                .map_or(0, |(offset, _)| offset);
+            let snippet_end = page.text[end..]
+                .char_indices()
+                .nth(SNIPPET_CONTEXT_CHARS)
+                .map_or(page.text.len(), |(offset, _)| end + offset);
+            SearchMatch {
+                page: page.page,
+                snippet: page.text[snippet_start..snippet_end].replace('\n', " "),
+                match_start: start - snippet_start,
+                match_end: end - snippet_start,
+                bbox: match_bbox(&page.runs, start, end),
+            }
+        })
+        .collect()
+}
+
+fn find_matches(text: &str, query: &str, case_sensitive: bool) -> Vec<(usize, usize)> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let query: Vec<char> = query.chars().collect();
+    let same =
+        |a: char, b: char| a == b || (!case_sensitive && a.to_lowercase().eq(b.to_lowercase()));
+
+    let mut matches = Vec::new();
+    let mut index = 0;
+    while index + query.len() <= chars.len() {
+        let candidate = &chars[index..index + query.len()];
+        if candidate.iter().zip(&query).all(|(&(_, a), &b)| same(a, b)) {
+            let start = candidate[0].0;
+            let end = chars
+                .get(index + query.len())
+                .map_or(text.len(), |(offset, _)| *offset);
+            matches.push((start, end));
+            index += query.len();
+        } else {
+            index += 1;
+        }
+    }
+    matches
+}
+
+// Narrows each overlapping run to the matched characters and returns their union.
+fn match_bbox(runs: &[TextRun], start: usize, end: usize) -> Option<[f32; 4]> {
+    runs.iter()
+        .filter(|run| run.start < end && start < run.end)
+        .map(|run| {
+            let length = (run.end - run.start) as f32;
+            let from = (start.max(run.start) - run.start) as f32 / length;
+            let to = (end.min(run.end) - run.start) as f32 / length;
+            let [x0, y0, x1, y1] = run.bbox;
+            let width = x1 - x0;
+            [x0 + width * from, y0, x0 + width * to, y1]
+        })
+        .reduce(|[ax0, ay0, ax1, ay1], [bx0, by0, bx1, by1]| {
+            [ax0.min(bx0), ay0.min(by0), ax1.max(bx1), ay1.max(by1)]
+        })
+}
@@ -1,44 +1,160 @@
 use std::{
+    collections::BTreeMap,
     ffi::OsStr,
     fs::{self, File},
     io::Read,
     path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
 };
 
 use anyhow::{anyhow, Context, Result};
 use log;
-use lopdf::Document;
+use lopdf::{content::Content, Dictionary, Document, Object};
 use serde::Serialize;
 use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
 use tauri_plugin_dialog::DialogExt;
-use tauri_plugin_shell::ShellExt;
+use tauri_plugin_shell::{
+    process::{Command, CommandEvent},
+    ShellExt,
+};
+use tokio::task::JoinSet;
+use uuid::Uuid;
+
+use super::cache::{
+    blank_marker_path, create_output_paths, handle_existing_data_dir, inverted_page_output_path,
+    page_output_path, Cache,
+};
+use super::cancellation::CancellationToken;
+use super::error::Error;
+use super::magick::magick_binary;
+use super::manager::{document_manager, document_session, register_document};
+use super::manifest::CacheManifest;
+use super::options::{ImageFormat, ProcessingOptions, ADJUSTMENT_RANGE};
+use super::processing_log::ProcessingLogger;
+use super::recents::record_recent_document;
+use super::renderer::{MagickRenderer, PageRenderer, RenderEvents, SessionEvents};
+use super::settings::{current_settings, default_options};
+use crate::i18n;
+use crate::tray::ProcessingIndicator;
 
-const IMAGE_DENSITY: &str = "150";
-const IMAGE_RESIZE: &str = "1000x1000";
-const IMAGE_FORMAT: &str = "webp";
+const DECRYPTED_FILE_NAME: &str = "decrypted.pdf";
+const SUPPORTED_EXTENSIONS: [&str; 11] = [
+    "pdf", "png", "jpg", "jpeg", "bmp", "tif", "tiff", "docx", "odt", "pptx", "odp",
+];
+pub const LIBREOFFICE_BINARY: &str = "soffice";
+pub const DEFAULT_MEDIA_BOX: [f32; 4] = [0.0, 0.0, 612.0, 792.0];
+pub const MAX_PAGE_TREE_DEPTH: usize = 64;
+const BLANK_PIXEL_THRESHOLD: &str = "5%";
+const SIDECAR_MAGICK_NAME: &str = "magick";
+const MAGICK_RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
 
 #[derive(Debug, thiserror::Error)]
-pub enum Error {
-    #[error(transparent)]
-    CommandError(#[from] anyhow::Error),
+#[error("timed out after {0}s")]
+struct MagickTimedOut(u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentSource {
+    Pdf,
+    SingleImage(ImageKind),
+    MultiPageTiff,
+    Office,
 }
 
-impl serde::Serialize for Error {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::ser::Serializer,
-    {
-        serializer.serialize_str(self.to_string().as_ref())
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImageKind {
+    Png,
+    Jpeg,
+    Bmp,
+}
+
+impl ImageKind {
+    // Passed as an explicit ImageMagick coder, so files without an extension decode as the
+    // type their header was sniffed as.
+    fn coder(&self) -> &'static str {
+        match self {
+            ImageKind::Png => "png",
+            ImageKind::Jpeg => "jpeg",
+            ImageKind::Bmp => "bmp",
+        }
     }
 }
 
 #[derive(Debug, Clone, Serialize)]
 struct ImageLoaded {
-    page_number: u16,
+    session_id: String,
+    page_number: u32,
+    path: String,
+    mime_type: String,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PageImage {
+    mime_type: String,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DocumentInfo {
+    title: Option<String>,
+    author: Option<String>,
+    subject: Option<String>,
+    creator: Option<String>,
+    producer: Option<String>,
+    creation_date: Option<String>,
+    modification_date: Option<String>,
+    page_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PageDimensions {
+    page: u32,
+    width_pt: f32,
+    height_pt: f32,
+    // The MediaBox's lower left corner and the clockwise `/Rotate`, needed to map points on
+    // the rendered image back into page space.
+    origin_x_pt: f32,
+    origin_y_pt: f32,
+    rotation: u16,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ConversionStarted {
+    session_id: String,
     path: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PageUpdated {
+    session_id: String,
+    page_number: u32,
+    mime_type: String,
     data: Vec<u8>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+struct BlankPageSkipped {
+    session_id: String,
+    page_number: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct PageErrors {
+    session_id: String,
+    failed_pages: Vec<u32>,
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Progress {
+    session_id: String,
+    current: u32,
+    total: u32,
+    path: String,
+}
+
 #[tauri::command]
 pub fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
@@ -46,153 +162,1457 @@ pub fn greet(name: &str) -> String {
 
 #[tauri::command]
 pub fn select_document(app: AppHandle) -> Result<PathBuf, Error> {
-    let path = app.dialog()
+    let path = app
+        .dialog()
         .file()
+        .add_filter(
+            i18n::t(&app, "backend.documents_filter"),
+            &SUPPORTED_EXTENSIONS,
+        )
+        .add_filter("PDF", &["pdf"])
+        .add_filter("TIFF", &["tif", "tiff"])
         .blocking_pick_file()
         .map(|selection| selection.path);
 
     match path {
         Some(path) => Ok(path),
-        None => Err(Error::CommandError(anyhow!("No document selected"))),
+        None => Err(Error::NothingSelected),
+    }
+}
+
+#[tauri::command]
+pub fn select_documents(app: AppHandle) -> Result<Vec<PathBuf>, Error> {
+    let paths = app
+        .dialog()
+        .file()
+        .add_filter("PDF", &["pdf"])
+        .blocking_pick_files()
+        .map(|selection| selection.into_iter().map(|file| file.path).collect());
+
+    match paths {
+        Some(paths) => Ok(paths),
+        None => Err(Error::NothingSelected),
+    }
+}
+
+#[tauri::command]
+pub fn select_save_file(
+    app: AppHandle,
+    filter_name: String,
+    extensions: Vec<String>,
+) -> Result<PathBuf, Error> {
+    let extensions: Vec<&str> = extensions.iter().map(String::as_str).collect();
+    let path = app
+        .dialog()
+        .file()
+        .add_filter(filter_name, &extensions)
+        .blocking_save_file();
+
+    match path {
+        Some(path) => Ok(path),
+        None => Err(Error::NothingSelected),
     }
 }
 
 #[tauri::command]
-pub async fn prepare_document(app: AppHandle, path: PathBuf) -> Result<String, Error> {
-    preparation(app, path).await.map_err(Error::CommandError)
+pub fn select_directory(app: AppHandle) -> Result<PathBuf, Error> {
+    match app.dialog().file().blocking_pick_folder() {
+        Some(path) => Ok(path),
+        None => Err(Error::NothingSelected),
+    }
 }
 
-async fn preparation(app: tauri::AppHandle, path: PathBuf) -> Result<String> {
+#[tauri::command]
+pub async fn prepare_document(
+    app: AppHandle,
+    path: PathBuf,
+    session_id: String,
+) -> Result<Uuid, Error> {
+    let path = validate_document_path(&path)?;
+    let options = default_options(&app);
+    open_document(&app, &path, &session_id, options).await
+}
+
+#[tauri::command]
+pub async fn prepare_document_with_options(
+    app: AppHandle,
+    path: PathBuf,
+    session_id: String,
+    options: ProcessingOptions,
+) -> Result<Uuid, Error> {
+    let path = validate_document_path(&path)?;
+    let options = current_settings(&app).apply_to(options);
+    open_document(&app, &path, &session_id, options).await
+}
+
+// The session is registered before preparing, so closing the document cancels its renders.
+async fn open_document(
+    app: &AppHandle,
+    path: &Path,
+    session_id: &str,
+    options: ProcessingOptions,
+) -> Result<Uuid, Error> {
+    let (document_id, cancel_token) = register_document(app, path, options.clone(), session_id)?;
+    match preparation(
+        app.clone(),
+        path.to_path_buf(),
+        session_id,
+        options,
+        &cancel_token,
+    )
+    .await
+    {
+        Ok(cache) => {
+            document_manager(app)?.set_cache(&document_id, cache);
+            Ok(document_id)
+        }
+        Err(err) => {
+            document_manager(app)?.discard(&document_id, &cancel_token);
+            Err(preparation_error(err))
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn decrypt_document(
+    app: AppHandle,
+    path: PathBuf,
+    password: String,
+) -> Result<PathBuf, Error> {
+    decryption(&app, &path, &password)
+        .await
+        .map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub async fn convert_to_pdf(app: AppHandle, path: PathBuf) -> Result<PathBuf, Error> {
+    let path = validate_document_path(&path)?;
+    if detect_source_type(&path).map_err(preparation_error)? != DocumentSource::Office {
+        return Err(Error::UnsupportedFormat(path.display().to_string()));
+    }
+    let (data_dir, _output_file_name) =
+        create_output_paths(&app, &path, &ProcessingOptions::default())?;
+    conversion(&app, &path, &data_dir)
+        .await
+        .map_err(preparation_error)
+}
+
+#[tauri::command]
+pub async fn extract_text_page(path: PathBuf, page: u32) -> Result<String, Error> {
+    let document = load_text_document(&path).await?;
+    extract_page_text(&document, page).map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub async fn extract_text_all(path: PathBuf) -> Result<Vec<(u32, String)>, Error> {
+    let document = load_text_document(&path).await?;
+    let pages = document
+        .get_pages()
+        .into_keys()
+        .map(|page| extract_page_text(&document, page).map(|text| (page, text)))
+        .collect::<Result<_>>()?;
+    Ok(pages)
+}
+
+#[tauri::command]
+pub async fn get_document_info(path: PathBuf) -> Result<DocumentInfo, Error> {
+    read_document_info(&path).await.map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub async fn get_page_dimensions(path: PathBuf) -> Result<Vec<PageDimensions>, Error> {
+    read_page_dimensions(&path)
+        .await
+        .map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub async fn split_document(
+    path: PathBuf,
+    split_after_page: u32,
+    output_dir: PathBuf,
+) -> Result<(PathBuf, PathBuf), Error> {
+    splitting(&path, split_after_page, &output_dir)
+        .await
+        .map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub async fn rotate_page(
+    app: AppHandle,
+    document_id: Uuid,
+    page: u32,
+    degrees: i16,
+) -> Result<(), Error> {
+    let session = document_session(&app, &document_id)?;
+    rotation(
+        &app,
+        &session.path,
+        &session.client_id,
+        page,
+        degrees,
+        &session.options,
+    )
+    .await
+    .map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub async fn retry_page(
+    app: AppHandle,
+    path: PathBuf,
+    session_id: String,
+    page: u32,
+) -> Result<(), Error> {
+    retrying(&app, &path, &session_id, page)
+        .await
+        .map_err(preparation_error)
+}
+
+#[tauri::command]
+pub async fn rerender_page(
+    app: AppHandle,
+    path: PathBuf,
+    session_id: String,
+    page: u32,
+    options: ProcessingOptions,
+) -> Result<(), Error> {
+    if !ADJUSTMENT_RANGE.contains(&options.brightness)
+        || !ADJUSTMENT_RANGE.contains(&options.contrast)
+    {
+        return Err(anyhow!("Brightness and contrast must be between -100 and 100").into());
+    }
+    let options = current_settings(&app).apply_to(options);
+    refresh_page(&app, &path, &session_id, page, &options)
+        .await
+        .map_err(preparation_error)
+}
+
+#[tauri::command]
+pub async fn get_page(
+    app: AppHandle,
+    path: PathBuf,
+    session_id: String,
+    page_number: u32,
+) -> Result<bool, Error> {
+    let options = default_options(&app);
+    let (data_dir, _output_file_name) = create_output_paths(&app, &path, &options)?;
+    let image = page_output_path(&data_dir, page_number as usize, options.format);
+    if !image.exists() {
+        return Ok(false);
+    }
+
+    send_webp_image(
+        &app,
+        &session_id,
+        &image,
+        page_number as usize,
+        options.format.mime_type(),
+    )?;
+    Ok(true)
+}
+
+#[tauri::command]
+pub async fn get_inverted_page(
+    app: AppHandle,
+    path: PathBuf,
+    page_number: u32,
+) -> Result<PageImage, Error> {
+    inverted_page(&app, &path, page_number)
+        .await
+        .map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub async fn print_page(app: AppHandle, path: PathBuf, page: u32) -> Result<(), Error> {
+    printing(&app, &path, page)
+        .await
+        .map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub async fn copy_page_to_clipboard(app: AppHandle, path: PathBuf, page: u32) -> Result<(), Error> {
+    let options = default_options(&app);
+    let (data_dir, _output_file_name) = create_output_paths(&app, &path, &options)?;
+    let image = page_output_path(&data_dir, page as usize, options.format);
+    if !image.exists() {
+        return Err(Error::PageNotRendered(page));
+    }
+
+    copy_to_clipboard(&app, &image, &data_dir)
+        .await
+        .map_err(Error::CommandError)
+}
+
+pub async fn preparation(
+    app: tauri::AppHandle,
+    path: PathBuf,
+    session_id: &str,
+    options: ProcessingOptions,
+    cancel_token: &CancellationToken,
+) -> Result<Cache> {
     log::info!("Preparing document: {}", path.display());
-    let (data_dir, _output_file_name) = create_output_paths(&path)?;
-    let page_count = get_page_count(&path)?;
-    let input = path.to_string_lossy();
-    
-    if data_dir.exists() {
-        handle_existing_data_dir(&data_dir, page_count, &app, &input).await?;
+    let source = detect_source_type(&path)?;
+    let (data_dir, _output_file_name) = create_output_paths(&app, &path, &options)?;
+
+    let cache = match source {
+        DocumentSource::SingleImage(kind) => {
+            let mut cache = Cache::new(data_dir, CacheManifest::new(&path, 1, &options)?);
+            prepare_single_image(&app, &path, kind, session_id, &mut cache, &options).await?;
+            cache
+        }
+        DocumentSource::Pdf | DocumentSource::MultiPageTiff | DocumentSource::Office => {
+            let (pdf_path, converted_from) = if source == DocumentSource::Office {
+                app.emit(
+                    "converting",
+                    ConversionStarted {
+                        session_id: session_id.to_string(),
+                        path: path.display().to_string(),
+                    },
+                )?;
+                (
+                    conversion(&app, &path, &data_dir).await?,
+                    Some(path.as_path()),
+                )
+            } else {
+                (path.clone(), None)
+            };
+            let input = pdf_path.to_string_lossy();
+            let page_count = match source {
+                DocumentSource::MultiPageTiff => get_frame_count(&app, &input).await?,
+                _ => get_page_count(&pdf_path).await?,
+            };
+            let manifest = CacheManifest::new(&pdf_path, page_count, &options)?
+                .with_converted_from(converted_from);
+
+            let cached = data_dir.exists();
+            if !cached {
+                fs::create_dir_all(&data_dir).map_err(|err| Error::io(&data_dir, err))?;
+            }
+            let renderer: Arc<dyn PageRenderer> = Arc::new(MagickRenderer::new(
+                app.clone(),
+                Some(ProcessingLogger::open(&data_dir)?),
+            ));
+            let mut cache = Cache::new(data_dir, manifest);
+            let events = SessionEvents::new(&app, session_id, &input);
+            let _indicator = ProcessingIndicator::start(&app);
+            if cached {
+                handle_existing_data_dir(
+                    &mut cache,
+                    &pdf_path,
+                    &events,
+                    &options,
+                    renderer,
+                    cancel_token,
+                )
+                .await?;
+            } else {
+                process_pages(
+                    &events,
+                    &input,
+                    &mut cache,
+                    &options,
+                    renderer,
+                    cancel_token,
+                )
+                .await?;
+            }
+            cache
+        }
+    };
+
+    if let Err(err) = record_recent_document(&app, &path) {
+        log::warn!("Failed to record recent document: {}", err);
+    }
+
+    Ok(cache)
+}
+
+async fn conversion(app: &AppHandle, path: &Path, data_dir: &Path) -> Result<PathBuf> {
+    let output = converted_pdf_path(path, data_dir);
+    if is_newer_than(&output, path) {
+        log::info!("Reusing converted PDF {}", output.display());
+        return Ok(output);
+    }
+
+    fs::create_dir_all(data_dir).map_err(|err| Error::io(data_dir, err))?;
+    log::info!("Converting {} to PDF", path.display());
+    let result = app
+        .shell()
+        .command(LIBREOFFICE_BINARY)
+        .args(["--headless", "--convert-to", "pdf", "--outdir"])
+        .arg(data_dir)
+        .arg(path)
+        .output()
+        .await
+        .context("Failed to run LibreOffice")?;
+
+    if !result.status.success() {
+        return Err(anyhow!(
+            "LibreOffice conversion failed with exit code {}, stderr: {}",
+            result.status.code().unwrap_or(1),
+            String::from_utf8_lossy(&result.stderr)
+        ));
+    }
+    if !output.exists() {
+        return Err(anyhow!("LibreOffice did not produce {}", output.display()));
+    }
+    Ok(output)
+}
+
+fn converted_pdf_path(path: &Path, data_dir: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "document".to_string());
+    data_dir.join(format!("{}.pdf", stem))
+}
+
+fn is_newer_than(path: &Path, source: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|metadata| metadata.modified());
+    match (modified(path), modified(source)) {
+        (Ok(path), Ok(source)) => path >= source,
+        _ => false,
+    }
+}
+
+pub fn render_input(path: &Path, data_dir: &Path) -> PathBuf {
+    match detect_source_type(path) {
+        Ok(DocumentSource::Office) => converted_pdf_path(path, data_dir),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn preparation_error(err: anyhow::Error) -> Error {
+    err.downcast::<Error>().unwrap_or_else(Error::CommandError)
+}
+
+async fn decryption(app: &AppHandle, path: &Path, password: &str) -> Result<PathBuf> {
+    let (data_dir, _output_file_name) =
+        create_output_paths(app, path, &ProcessingOptions::default())?;
+    fs::create_dir_all(&data_dir).context("Failed to create data directory")?;
+
+    let mut document = load_pdf_blocking(path.to_path_buf()).await?;
+    document
+        .decrypt(password)
+        .map_err(|_| anyhow!("Incorrect password"))?;
+    document.trailer.remove(b"Encrypt");
+
+    let output = data_dir.join(DECRYPTED_FILE_NAME);
+    document
+        .save(&output)
+        .context("Failed to save decrypted PDF document")?;
+    Ok(output)
+}
+
+async fn splitting(
+    path: &Path,
+    split_after_page: u32,
+    output_dir: &Path,
+) -> Result<(PathBuf, PathBuf)> {
+    let document = load_pdf_blocking(path.to_path_buf()).await?;
+    let page_count = document.get_pages().len() as u32;
+    if split_after_page == 0 || split_after_page >= page_count {
+        return Err(anyhow!(
+            "Split page must be between 1 and {}",
+            page_count.saturating_sub(1)
+        ));
+    }
+
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "document".to_string());
+    let first_output = output_dir.join(format!("{}_part1.pdf", stem));
+    let second_output = output_dir.join(format!("{}_part2.pdf", stem));
+
+    let mut first = document.clone();
+    first.delete_pages(&(split_after_page + 1..=page_count).collect::<Vec<_>>());
+    first.prune_objects();
+    first
+        .save(&first_output)
+        .context("Failed to save first part")?;
+
+    let mut second = document;
+    second.delete_pages(&(1..=split_after_page).collect::<Vec<_>>());
+    second.prune_objects();
+    second
+        .save(&second_output)
+        .context("Failed to save second part")?;
+
+    log::info!(
+        "Split {} into {} and {}",
+        path.display(),
+        first_output.display(),
+        second_output.display()
+    );
+    Ok((first_output, second_output))
+}
+
+async fn retrying(app: &AppHandle, path: &Path, session_id: &str, page: u32) -> Result<()> {
+    // Retries render with the options the document was prepared with, not today's defaults.
+    let canonical_path = dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let prepared_options = document_manager(app)?
+        .find(session_id, &canonical_path)
+        .map(|session| session.options.clone());
+    let options = prepared_options.unwrap_or_else(|| default_options(app));
+    let (data_dir, _output_file_name) = create_output_paths(app, path, &options)?;
+    fs::create_dir_all(&data_dir).map_err(|err| Error::io(&data_dir, err))?;
+    let page_index = page_index(&render_input(path, &data_dir), &data_dir, page).await?;
+
+    let page_number = render_page(
+        Arc::new(MagickRenderer::new(
+            app.clone(),
+            ProcessingLogger::open(&data_dir).ok(),
+        )),
+        render_input(path, &data_dir).to_string_lossy().into_owned(),
+        data_dir.clone(),
+        page_index,
+        options.clone(),
+    )
+    .await?;
+
+    if let Some(mut cache) = Cache::load(&data_dir)? {
+        cache.record_page_hash(page_number)?;
+        cache.write_manifest()?;
+    }
+    let output = page_output_path(&data_dir, page_number, options.format);
+    send_webp_image(
+        app,
+        session_id,
+        &output,
+        page_number,
+        options.format.mime_type(),
+    )
+}
+
+async fn rotation(
+    app: &AppHandle,
+    path: &Path,
+    session_id: &str,
+    page: u32,
+    degrees: i16,
+    options: &ProcessingOptions,
+) -> Result<()> {
+    if degrees % 90 != 0 {
+        return Err(anyhow!("Rotation must be a multiple of 90 degrees"));
+    }
+
+    let mut document = load_pdf_blocking(path.to_path_buf()).await?;
+    let page_id = *document
+        .get_pages()
+        .get(&page)
+        .ok_or_else(|| anyhow!("Page {} does not exist", page))?;
+    let page_dict = document
+        .get_object_mut(page_id)
+        .and_then(Object::as_dict_mut)
+        .context("Failed to read page dictionary")?;
+
+    let current = page_dict
+        .get(b"Rotate")
+        .and_then(Object::as_i64)
+        .unwrap_or(0);
+    let rotation = (current + degrees as i64).rem_euclid(360);
+    log::info!("Rotating page {} to {} degrees", page, rotation);
+    page_dict.set("Rotate", rotation);
+
+    document
+        .save(path)
+        .context("Failed to save rotated document")?;
+
+    // Re-rendered with the document's own options so the page keeps its look and format.
+    refresh_page(app, path, session_id, page, options).await
+}
+
+// Pages are numbered from 1, so anything outside the document is rejected before rendering.
+async fn page_index(input: &Path, data_dir: &Path, page: u32) -> Result<usize> {
+    let page_count = match Cache::load(data_dir)? {
+        Some(cache) => cache.page_count(),
+        None => get_page_count(input).await?,
+    };
+    if page == 0 || page as usize > page_count {
+        return Err(Error::PageOutOfRange {
+            page,
+            page_count: page_number_u32(page_count)?,
+        }
+        .into());
+    }
+    Ok(page as usize - 1)
+}
+
+async fn refresh_page(
+    app: &AppHandle,
+    path: &Path,
+    session_id: &str,
+    page: u32,
+    options: &ProcessingOptions,
+) -> Result<()> {
+    let (data_dir, _output_file_name) = create_output_paths(app, path, options)?;
+    fs::create_dir_all(&data_dir).context("Failed to create data directory")?;
+    let page_index = page_index(&render_input(path, &data_dir), &data_dir, page).await?;
+
+    let page_number = render_page(
+        Arc::new(MagickRenderer::new(
+            app.clone(),
+            ProcessingLogger::open(&data_dir).ok(),
+        )),
+        render_input(path, &data_dir).to_string_lossy().into_owned(),
+        data_dir.clone(),
+        page_index,
+        options.clone(),
+    )
+    .await?;
+
+    let output = page_output_path(&data_dir, page_number, options.format);
+    let inverted = inverted_page_output_path(&data_dir, page_number, options.format);
+    if inverted.exists() {
+        fs::remove_file(&inverted).context("Failed to remove outdated inverted page")?;
+    }
+    if let Some(mut cache) = Cache::load(&data_dir)? {
+        cache.refresh_modified_at(&render_input(path, &data_dir))?;
+        cache.record_page_hash(page_number)?;
+        cache.write_manifest()?;
+    }
+
+    let data = fs::read(&output).context("Failed to read re-rendered page")?;
+    log::info!("Sending updated page: {}", output.display());
+
+    app.emit(
+        "page_updated",
+        PageUpdated {
+            session_id: session_id.to_string(),
+            page_number: page,
+            mime_type: options.format.mime_type().to_string(),
+            data,
+        },
+    )?;
+    Ok(())
+}
+
+async fn inverted_page(app: &AppHandle, path: &Path, page: u32) -> Result<PageImage> {
+    let options = default_options(app).with_inverted(true);
+    let (data_dir, _output_file_name) = create_output_paths(app, path, &options)?;
+    let output = inverted_page_output_path(&data_dir, page as usize, options.format);
+
+    if !output.exists() {
+        if page == 0 || !page_output_path(&data_dir, page as usize, options.format).exists() {
+            return Err(anyhow!("Page {} has not been rendered yet", page));
+        }
+        log::info!("Rendering inverted page {} of {}", page, path.display());
+        let input = render_input(path, &data_dir).to_string_lossy().into_owned();
+        MagickRenderer::new(app.clone(), ProcessingLogger::open(&data_dir).ok())
+            .render_page(&input, page as usize - 1, &output, &options)
+            .await?;
+    }
+
+    Ok(PageImage {
+        mime_type: options.format.mime_type().to_string(),
+        data: fs::read(&output).context("Failed to read inverted page")?,
+    })
+}
+
+async fn printing(app: &AppHandle, path: &Path, page: u32) -> Result<()> {
+    let options = default_options(app);
+    let (data_dir, _output_file_name) = create_output_paths(app, path, &options)?;
+    let image = page_output_path(&data_dir, page as usize, options.format);
+    if !image.exists() {
+        return Err(anyhow!("Page {} has not been rendered yet", page));
+    }
+
+    let print_file = data_dir.join("page_print.pdf");
+    run_magick(
+        app,
+        &[
+            image.to_string_lossy().into_owned(),
+            print_file.to_string_lossy().into_owned(),
+        ],
+        options.max_retries,
+        options.magick_timeout(),
+        None,
+    )
+    .await?;
+
+    let (program, args) = if cfg!(target_os = "windows") {
+        ("SumatraPDF", vec!["-print-to-default".to_string()])
+    } else {
+        ("lpr", Vec::new())
+    };
+
+    log::info!("Printing page {} of {}", page, path.display());
+    let output = app
+        .shell()
+        .command(program)
+        .args(args)
+        .arg(print_file.to_string_lossy().into_owned())
+        .output()
+        .await
+        .with_context(|| format!("Failed to run {}", program))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Print command failed with exit code {}, stderr: {}",
+            output.status.code().unwrap_or(1),
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
+
+async fn copy_to_clipboard(app: &AppHandle, image: &Path, data_dir: &Path) -> Result<()> {
+    let png = if image.extension() == Some(OsStr::new(ImageFormat::Png.extension())) {
+        image.to_path_buf()
     } else {
-        fs::create_dir(&data_dir).context("Failed to create data directory")?;
-        process_pages(&app, &input, &data_dir, page_count).await?;
+        let png = data_dir.join("page_clipboard.png");
+        let options = default_options(app);
+        run_magick(
+            app,
+            &[
+                image.to_string_lossy().into_owned(),
+                png.to_string_lossy().into_owned(),
+            ],
+            options.max_retries,
+            options.magick_timeout(),
+            None,
+        )
+        .await?;
+        png
+    };
+
+    let bytes = fs::read(&png).context("Failed to read page image")?;
+    let image = tauri::image::Image::from_bytes(&bytes).context("Failed to decode page image")?;
+    app.clipboard()
+        .write_image(&image)
+        .context("Failed to write page image to the clipboard")?;
+
+    log::info!("Copied {} to the clipboard", png.display());
+    Ok(())
+}
+
+// The extension decides the document type when it is known; anything else is accepted only
+// if its header sniffs as a supported format.
+pub fn validate_document_path(path: &Path) -> Result<PathBuf, Error> {
+    let canonical_path = dunce::canonicalize(path).map_err(|err| Error::io(path, err))?;
+    if !canonical_path.is_file() {
+        return Err(Error::IoError {
+            path: path.to_path_buf(),
+            reason: "Path does not point to a file".to_string(),
+        });
+    }
+
+    detect_source_type(&canonical_path).map_err(preparation_error)?;
+    Ok(canonical_path)
+}
+
+fn detect_source_type(path: &Path) -> Result<DocumentSource> {
+    let extension = path
+        .extension()
+        .and_then(OsStr::to_str)
+        .map(str::to_lowercase);
+
+    match extension.as_deref() {
+        Some("pdf") => return Ok(DocumentSource::Pdf),
+        Some("png") => return Ok(DocumentSource::SingleImage(ImageKind::Png)),
+        Some("jpg" | "jpeg") => return Ok(DocumentSource::SingleImage(ImageKind::Jpeg)),
+        Some("bmp") => return Ok(DocumentSource::SingleImage(ImageKind::Bmp)),
+        Some("tif" | "tiff") => return Ok(DocumentSource::MultiPageTiff),
+        Some("docx" | "odt" | "pptx" | "odp") => return Ok(DocumentSource::Office),
+        _ => {}
+    }
+
+    let mut header = [0u8; 8];
+    let read = File::open(path)
+        .and_then(|mut file| file.read(&mut header))
+        .context("Failed to read document header")?;
+
+    match &header[..read] {
+        [b'%', b'P', b'D', b'F', b'-', ..] => Ok(DocumentSource::Pdf),
+        [0x89, b'P', b'N', b'G', ..] => Ok(DocumentSource::SingleImage(ImageKind::Png)),
+        [0xFF, 0xD8, 0xFF, ..] => Ok(DocumentSource::SingleImage(ImageKind::Jpeg)),
+        [b'B', b'M', ..] => Ok(DocumentSource::SingleImage(ImageKind::Bmp)),
+        [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => {
+            Ok(DocumentSource::MultiPageTiff)
+        }
+        _ => Err(Error::UnsupportedFormat(path.display().to_string()).into()),
+    }
+}
+
+// The image is rendered like any other page, so it is stored in the manifest's format with
+// the document's options applied.
+async fn prepare_single_image(
+    app: &AppHandle,
+    path: &Path,
+    kind: ImageKind,
+    session_id: &str,
+    cache: &mut Cache,
+    options: &ProcessingOptions,
+) -> Result<()> {
+    log::info!(
+        "Document is a single {} image. Rendering it as the only page.",
+        kind.coder()
+    );
+    let data_dir = cache.data_dir().to_path_buf();
+    fs::create_dir_all(&data_dir).context("Failed to create data directory")?;
+
+    render_page(
+        Arc::new(MagickRenderer::new(
+            app.clone(),
+            ProcessingLogger::open(&data_dir).ok(),
+        )),
+        format!("{}:{}", kind.coder(), path.to_string_lossy()),
+        data_dir,
+        0,
+        options.clone(),
+    )
+    .await?;
+    cache.record_page_hash(1)?;
+    cache.write_manifest()?;
+
+    send_webp_image(
+        app,
+        session_id,
+        &cache.page_path(1, options.format),
+        1,
+        options.format.mime_type(),
+    )?;
+    emit_progress(app, session_id, &path.to_string_lossy(), 1, 1)
+}
+
+async fn get_frame_count(app: &AppHandle, input: &str) -> Result<usize> {
+    let output = get_sidecar_magick(app)
+        .args(["identify", "-format", "%n\\n", input])
+        .output()
+        .await
+        .context("Failed to run magick identify command")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Magick identify failed with exit code {}, stderr: {}",
+            output.status.code().unwrap_or(1),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let frame_count: usize = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .and_then(|line| line.trim().parse().ok())
+        .context("Failed to parse frame count from magick identify output")?;
+    if frame_count == 0 {
+        return Err(anyhow!("{} contains no frames", input));
     }
-    
-    Ok(path.display().to_string())
+    Ok(frame_count)
 }
 
-fn create_output_paths(path: &Path) -> Result<(PathBuf, PathBuf)> {
-    let path_without_ext = path.with_extension("");
-    let file_name = path_without_ext.file_name().unwrap().to_string_lossy();
-    let data_dir = path_without_ext.with_file_name(format!("{}_data", &file_name));
-    let output_file_name = data_dir.join("page").with_extension(IMAGE_FORMAT);
-    Ok((data_dir, output_file_name))
+async fn get_page_count(path: &Path) -> Result<usize> {
+    if !has_pdf_header(path)? {
+        return Err(Error::UnsupportedFormat(path.display().to_string()).into());
+    }
+
+    let load_failed = |reason: String| Error::PdfLoadFailed {
+        path: path.to_path_buf(),
+        reason,
+    };
+
+    match load_pdf_blocking(path.to_path_buf()).await {
+        Ok(doc) if doc.is_encrypted() => Err(Error::EncryptedDocument.into()),
+        Ok(doc) => match doc.get_pages().len() {
+            0 => Err(load_failed("Document contains no pages".to_string()).into()),
+            page_count => Ok(page_count),
+        },
+        Err(err) => match err.downcast_ref::<lopdf::Error>() {
+            Some(lopdf::Error::Decryption(_)) => Err(Error::EncryptedDocument.into()),
+            Some(err) => Err(load_failed(err.to_string()).into()),
+            None => Err(err),
+        },
+    }
 }
 
-fn get_page_count(path: &Path) -> Result<usize> {
-    Document::load(path)
-        .map(|doc| doc.get_pages().len())
+pub async fn load_pdf_blocking(path: PathBuf) -> Result<Document> {
+    tokio::task::spawn_blocking(move || Document::load(path))
+        .await
+        .context("lopdf task panicked")?
         .context("Failed to load PDF document")
 }
 
-fn create_magick_args<'a>(input: &'a str, output: &'a str) -> Vec<&'a str> {
-    vec![
-        "-density",
-        IMAGE_DENSITY,
-        input,
-        "-resize",
-        IMAGE_RESIZE,
-        "-scene",
-        "1",
-        "+adjoin",
-        output,
-    ]
-}
-
-async fn handle_existing_data_dir(
-    data_dir: &Path,
-    page_count: usize,
-    app: &AppHandle,
+pub fn has_pdf_header(path: &Path) -> Result<bool> {
+    let mut header = [0u8; 5];
+    let read = File::open(path)
+        .and_then(|mut file| file.read(&mut header))
+        .map_err(|err| Error::io(path, err))?;
+    Ok(&header[..read] == b"%PDF-")
+}
+
+async fn read_document_info(path: &Path) -> Result<DocumentInfo> {
+    let document = load_pdf_blocking(path.to_path_buf()).await?;
+    let info = match document.trailer.get(b"Info") {
+        Ok(Object::Reference(id)) => document.get_dictionary(*id).ok(),
+        Ok(Object::Dictionary(info)) => Some(info),
+        _ => None,
+    };
+
+    let field = |key: &[u8]| {
+        info.and_then(|info| info.get(key).ok())
+            .and_then(|value| value.as_str().ok())
+            .map(decode_pdf_string)
+    };
+
+    Ok(DocumentInfo {
+        title: field(b"Title"),
+        author: field(b"Author"),
+        subject: field(b"Subject"),
+        creator: field(b"Creator"),
+        producer: field(b"Producer"),
+        creation_date: field(b"CreationDate"),
+        modification_date: field(b"ModDate"),
+        page_count: document.get_pages().len() as u32,
+    })
+}
+
+async fn read_page_dimensions(path: &Path) -> Result<Vec<PageDimensions>> {
+    let document = load_pdf_blocking(path.to_path_buf()).await?;
+    Ok(document
+        .get_pages()
+        .into_iter()
+        .map(|(page, id)| {
+            let dictionary = document.get_dictionary(id).ok();
+            let [x0, y0, x1, y1] = dictionary
+                .and_then(|page| inherited_media_box(&document, page))
+                .unwrap_or(DEFAULT_MEDIA_BOX);
+            let rotation = dictionary
+                .map(|page| inherited_rotation(&document, page))
+                .unwrap_or(0);
+            PageDimensions {
+                page,
+                width_pt: (x1 - x0).abs(),
+                height_pt: (y1 - y0).abs(),
+                origin_x_pt: x0.min(x1),
+                origin_y_pt: y0.min(y1),
+                rotation,
+            }
+        })
+        .collect())
+}
+
+pub fn inherited_media_box(document: &Document, page: &Dictionary) -> Option<[f32; 4]> {
+    parse_media_box(document, inherited_attribute(document, page, b"MediaBox")?)
+}
+
+// Normalized to 0, 90, 180 or 270 degrees clockwise.
+pub fn inherited_rotation(document: &Document, page: &Dictionary) -> u16 {
+    let rotation = inherited_attribute(document, page, b"Rotate")
+        .and_then(|rotation| rotation.as_i64().ok())
+        .unwrap_or(0);
+    (rotation.rem_euclid(360) / 90 * 90) as u16
+}
+
+fn inherited_attribute<'a>(
+    document: &'a Document,
+    page: &'a Dictionary,
+    key: &[u8],
+) -> Option<&'a Object> {
+    let mut node = page;
+    for _ in 0..MAX_PAGE_TREE_DEPTH {
+        if let Ok(value) = node.get(key) {
+            return Some(value);
+        }
+        let parent = node.get(b"Parent").and_then(Object::as_reference).ok()?;
+        node = document.get_dictionary(parent).ok()?;
+    }
+    None
+}
+
+fn parse_media_box(document: &Document, media_box: &Object) -> Option<[f32; 4]> {
+    let media_box = match media_box {
+        Object::Reference(id) => document.get_object(*id).ok()?,
+        media_box => media_box,
+    };
+    let values = media_box
+        .as_array()
+        .ok()?
+        .iter()
+        .map(|value| match value {
+            Object::Integer(value) => Some(*value as f32),
+            Object::Real(value) => Some(*value),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    values.try_into().ok()
+}
+
+pub async fn load_text_document(path: &Path) -> Result<Document> {
+    let document = load_pdf_blocking(path.to_path_buf()).await?;
+    if document.is_encrypted() {
+        return Err(anyhow!("Cannot extract text from an encrypted document"));
+    }
+    Ok(document)
+}
+
+pub fn extract_page_text(document: &Document, page: u32) -> Result<String> {
+    let page_id = *document
+        .get_pages()
+        .get(&page)
+        .ok_or_else(|| anyhow!("Page {} does not exist", page))?;
+    let content = document
+        .get_page_content(page_id)
+        .context("Failed to read page content stream")?;
+    let content = Content::decode(&content).context("Failed to decode page content stream")?;
+
+    let mut text = String::new();
+    for operation in &content.operations {
+        match operation.operator.as_str() {
+            "Tj" => push_text_operand(&mut text, operation.operands.first()),
+            "'" | "\"" => {
+                text.push('\n');
+                push_text_operand(&mut text, operation.operands.last());
+            }
+            "TJ" => {
+                let Some(Object::Array(items)) = operation.operands.first() else {
+                    continue;
+                };
+                for item in items {
+                    match item {
+                        Object::Integer(offset) if *offset < -200 => text.push(' '),
+                        Object::Real(offset) if *offset < -200.0 => text.push(' '),
+                        _ => push_text_operand(&mut text, Some(item)),
+                    }
+                }
+            }
+            "T*" | "Td" | "TD" | "ET" if !text.ends_with('\n') && !text.is_empty() => {
+                text.push('\n');
+            }
+            _ => {}
+        }
+    }
+
+    Ok(text.trim_end().to_string())
+}
+
+fn push_text_operand(text: &mut String, operand: Option<&Object>) {
+    if let Some(Object::String(bytes, _)) = operand {
+        text.push_str(&decode_pdf_string(bytes));
+    }
+}
+
+// Text strings are UTF-16BE with a byte order mark, UTF-8 with one, or PDFDocEncoding.
+// BOM-less UTF-8 is still accepted because earlier versions wrote form values that way.
+pub fn decode_pdf_string(bytes: &[u8]) -> String {
+    match bytes {
+        [0xFE, 0xFF, rest @ ..] => {
+            let units: Vec<u16> = rest
+                .chunks(2)
+                .map(|pair| u16::from_be_bytes([pair[0], *pair.get(1).unwrap_or(&0)]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest).into_owned(),
+        _ => match std::str::from_utf8(bytes) {
+            Ok(text) => text.to_string(),
+            Err(_) => bytes.iter().map(|&byte| pdf_doc_char(byte)).collect(),
+        },
+    }
+}
+
+// ASCII is written as is; anything else as UTF-16BE, which every reader understands.
+pub fn encode_pdf_string(text: &str) -> Vec<u8> {
+    if text.is_ascii() {
+        return text.as_bytes().to_vec();
+    }
+    let mut bytes = vec![0xFE, 0xFF];
+    bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+    bytes
+}
+
+// PDFDocEncoding matches Latin-1 except for the accents at 0x18-0x1F and the
+// punctuation at 0x80-0xA0.
+fn pdf_doc_char(byte: u8) -> char {
+    const ACCENTS: [char; 8] = ['˘', 'ˇ', 'ˆ', '˙', '˝', '˛', '˚', '˜'];
+    const PUNCTUATION: [char; 33] = [
+        '•', '†', '‡', '…', '—', '–', 'ƒ', '⁄', '‹', '›', '−', '‰', '„', '“', '”', '‘', '’', '‚',
+        '™', 'ﬁ', 'ﬂ', 'Ł', 'Œ', 'Š', 'Ÿ', 'Ž', 'ı', 'ł', 'œ', 'š', 'ž', '\u{FFFD}', '€',
+    ];
+    match byte {
+        0x18..=0x1F => ACCENTS[(byte - 0x18) as usize],
+        0x80..=0xA0 => PUNCTUATION[(byte - 0x80) as usize],
+        0xAD => char::REPLACEMENT_CHARACTER,
+        _ => char::from(byte),
+    }
+}
+
+// Each argument reaches magick as its own argv entry, so paths with spaces need no
+// quoting; quotes would be passed through literally and break the file lookup.
+pub fn create_magick_args(input: &str, output: &Path, options: &ProcessingOptions) -> Vec<String> {
+    let mut args = vec![
+        "-density".to_string(),
+        options.density.to_string(),
+        input.to_string(),
+        "-resize".to_string(),
+        options.resize.clone(),
+    ];
+
+    if options.grayscale {
+        args.extend(["-colorspace".to_string(), "Gray".to_string()]);
+    }
+
+    if options.inverted {
+        args.push("-negate".to_string());
+    }
+
+    if options.deskew {
+        args.extend([
+            "-deskew".to_string(),
+            format!("{}%", options.deskew_threshold),
+            "-trim".to_string(),
+            "+repage".to_string(),
+        ]);
+    }
+
+    if options.brightness != 0 || options.contrast != 0 {
+        args.extend([
+            "-brightness-contrast".to_string(),
+            format!("{}x{}", options.brightness, options.contrast),
+        ]);
+    }
+
+    if let ImageFormat::Jpeg { quality } = options.format {
+        args.extend(["-quality".to_string(), quality.to_string()]);
+    }
+
+    args.extend([
+        "-scene".to_string(),
+        "1".to_string(),
+        "+adjoin".to_string(),
+        output.to_string_lossy().into_owned(),
+    ]);
+    args
+}
+
+pub async fn process_pages(
+    events: &dyn RenderEvents,
     input: &str,
+    cache: &mut Cache,
+    options: &ProcessingOptions,
+    renderer: Arc<dyn PageRenderer>,
+    cancel_token: &CancellationToken,
 ) -> Result<()> {
-    log::info!("Data dir already exists. Verifying...");
-    let webp_file_count = count_webp_files(data_dir)?;
+    let page_count = cache.page_count();
+    let data_dir = cache.data_dir().to_path_buf();
+    let mut pending = 0..page_count;
+    let mut renders = JoinSet::new();
+    let mut completed = BTreeMap::new();
+    let mut failures = Vec::new();
+    let mut next_page = 1;
 
-    if webp_file_count == page_count {
-        log::info!("All pages are already processed. Emitting existing images.");
-        emit_existing_images(app, data_dir, page_count)?;
-    } else {
-        log::warn!(
-            "Mismatch in page count. PDF has {} pages, but found {} webp files.",
-            page_count,
-            webp_file_count
+    let spawn_render = |renders: &mut JoinSet<(usize, Result<bool>)>, page: usize| {
+        if cancel_token.is_cancelled() {
+            return;
+        }
+        let render = render_and_check_blank(
+            renderer.clone(),
+            input.to_string(),
+            data_dir.clone(),
+            page,
+            options.clone(),
         );
-        remove_existing_webp_files(data_dir)?;
-        process_pages(app, input, data_dir, page_count).await?;
+        renders.spawn(async move { (page + 1, render.await) });
+    };
+
+    for page in pending.by_ref().take(options.parallelism(page_count)) {
+        spawn_render(&mut renders, page);
+    }
+
+    while let Some(result) = renders.join_next().await {
+        if cancel_token.is_cancelled() {
+            break;
+        }
+
+        let (page_number, rendered) = result.context("Render task panicked")?;
+        completed.insert(page_number, rendered);
+
+        while let Some(rendered) = completed.remove(&next_page) {
+            match rendered {
+                Ok(true) => events.blank_page_skipped(next_page)?,
+                Ok(false) => {
+                    let output = cache.page_path(next_page, options.format);
+                    cache.record_page_hash(next_page)?;
+                    events.page_rendered(next_page, &output, options.format.mime_type())?;
+                }
+                Err(err) => {
+                    log::error!("Failed to render page {}: {}", next_page, err);
+                    failures.push((page_number_u32(next_page)?, err));
+                }
+            }
+            events.progress(next_page, page_count)?;
+            next_page += 1;
+        }
+
+        if let Some(page) = pending.next() {
+            spawn_render(&mut renders, page);
+        }
+    }
+
+    if cancel_token.is_cancelled() {
+        renders.abort_all();
+        while renders.join_next().await.is_some() {}
+        drop(renderer);
+        log::info!("Preparation of {} was cancelled", input);
+        cache.clear()?;
+        return Err(anyhow!("Document preparation was cancelled"));
+    }
+
+    cache.write_manifest()?;
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    events.page_errors(
+        failures.iter().map(|(page, _)| *page).collect(),
+        failures.iter().map(|(_, err)| err.to_string()).collect(),
+    )?;
+
+    if failures.len() == page_count {
+        let (_, err) = failures.swap_remove(0);
+        return Err(err.context("No page could be rendered"));
     }
     Ok(())
 }
 
-fn emit_existing_images(app: &AppHandle, data_dir: &Path, page_count: usize) -> Result<()> {
-    for page in 1..=page_count {
-        let file_path = data_dir.join(format!("{}.{}", page, IMAGE_FORMAT));
-        send_webp_image(app, &file_path, page)?;
+async fn render_page(
+    renderer: Arc<dyn PageRenderer>,
+    input: String,
+    data_dir: PathBuf,
+    page: usize,
+    options: ProcessingOptions,
+) -> Result<usize> {
+    let output = page_output_path(&data_dir, page + 1, options.format);
+    let result = renderer.render_page(&input, page, &output, &options).await;
+
+    if let Err(err) = result {
+        if err.is::<MagickTimedOut>() {
+            log::warn!("Rendering page {} of {} {}", page + 1, input, err);
+            if output.exists() {
+                if let Err(err) = fs::remove_file(&output) {
+                    log::warn!(
+                        "Failed to remove partial output {}: {}",
+                        output.display(),
+                        err
+                    );
+                }
+            }
+        }
+        return Err(Error::RenderFailed {
+            page: page_number_u32(page + 1).unwrap_or(u32::MAX),
+            stderr: err.to_string(),
+        }
+        .into());
+    }
+    Ok(page + 1)
+}
+
+async fn render_and_check_blank(
+    renderer: Arc<dyn PageRenderer>,
+    input: String,
+    data_dir: PathBuf,
+    page: usize,
+    options: ProcessingOptions,
+) -> Result<bool> {
+    let page_number = render_page(
+        renderer.clone(),
+        input,
+        data_dir.clone(),
+        page,
+        options.clone(),
+    )
+    .await?;
+    if !options.skip_blank_pages {
+        return Ok(false);
+    }
+
+    let output = page_output_path(&data_dir, page_number, options.format);
+    match renderer.is_blank(&output, options.blank_threshold).await {
+        Ok(false) => Ok(false),
+        Ok(true) => {
+            log::info!("Page {} is blank, skipping it", page_number);
+            fs::remove_file(&output).context("Failed to remove blank page image")?;
+            fs::write(blank_marker_path(&data_dir, page_number), b"")
+                .context("Failed to write blank page marker")?;
+            Ok(true)
+        }
+        Err(err) => {
+            log::warn!("Blank page check failed for page {}: {}", page_number, err);
+            Ok(false)
+        }
     }
-    Ok(())
 }
 
-async fn process_pages(app: &AppHandle, input: &str, data_dir: &Path, page_count: usize) -> Result<()> {
-    for page in 0..page_count {
-        let output = data_dir.join(format!("{}.{}", page + 1, IMAGE_FORMAT));
-        let page_arg = format!("{}[{}]", input, page);
-        let args = create_magick_args(&page_arg, output.to_str().unwrap());
-        run_magick(app, &args).await?;
-        send_webp_image(app, &output, page + 1)?;
+pub async fn is_blank_page(app: &AppHandle, image: &Path, threshold: f32) -> Result<bool> {
+    let output = get_sidecar_magick(app)
+        .arg(image)
+        .args([
+            "-threshold",
+            BLANK_PIXEL_THRESHOLD,
+            "-format",
+            "%[fx:mean]",
+            "info:",
+        ])
+        .output()
+        .await
+        .context("Failed to run magick blank page check")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Magick blank page check failed with exit code {}, stderr: {}",
+            output.status.code().unwrap_or(1),
+            String::from_utf8_lossy(&output.stderr)
+        ));
     }
+
+    let mean: f32 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .context("Failed to parse mean pixel value")?;
+    Ok(mean > threshold)
+}
+
+pub fn emit_blank_page_skipped(
+    app: &AppHandle,
+    session_id: &str,
+    page_number: usize,
+) -> Result<()> {
+    app.emit(
+        "blank_page_skipped",
+        BlankPageSkipped {
+            session_id: session_id.to_string(),
+            page_number: page_number_u32(page_number)?,
+        },
+    )?;
     Ok(())
 }
 
-fn count_webp_files(dir: &Path) -> Result<usize> {
-    Ok(fs::read_dir(dir)
-        .context("Failed to read data directory")?
-        .filter_map(Result::ok)
-        .filter(|e| e.path().extension() == Some(OsStr::new(IMAGE_FORMAT)))
-        .count())
+pub fn page_input_arg(input: &str, page: usize) -> String {
+    format!("{}[{}]", input, page)
 }
 
-fn remove_existing_webp_files(dir: &Path) -> Result<()> {
-    for entry in fs::read_dir(dir).context("Failed to read data directory")? {
-        let path = entry?.path();
-        if path.extension() == Some(OsStr::new(IMAGE_FORMAT)) {
-            log::info!("Removing {}", path.display());
-            fs::remove_file(&path).context("Failed to remove existing webp file")?;
+pub fn get_sidecar_magick(app: &AppHandle) -> Command {
+    if let Some(binary) = current_settings(app).magick_binary {
+        return app.shell().command(binary);
+    }
+    if sidecar_exists() {
+        match app.shell().sidecar(SIDECAR_MAGICK_NAME) {
+            Ok(command) => return command,
+            Err(err) => log::warn!("Failed to resolve magick sidecar: {}", err),
         }
     }
-    Ok(())
+    app.shell().command(magick_binary(app))
 }
 
-async fn run_magick(app: &AppHandle, args: &[&str]) -> Result<()> {
-    let output = app
-        .shell()
-        .command("magick.exe")
+fn sidecar_exists() -> bool {
+    let file_name = if cfg!(target_os = "windows") {
+        format!("{}.exe", SIDECAR_MAGICK_NAME)
+    } else {
+        SIDECAR_MAGICK_NAME.to_string()
+    };
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(file_name)))
+        .is_some_and(|sidecar| sidecar.is_file())
+}
+
+pub async fn run_magick(
+    app: &AppHandle,
+    args: &[String],
+    max_retries: u8,
+    timeout: Duration,
+    logger: Option<&ProcessingLogger>,
+) -> Result<()> {
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match run_magick_once(app, args, timeout, logger).await {
+            Ok(()) => return Ok(()),
+            Err(err) if err.is::<MagickTimedOut>() => return Err(err),
+            Err(err) if attempt <= max_retries as u32 => {
+                let delay = MAGICK_RETRY_BASE_DELAY * 2u32.pow(attempt - 1);
+                log::warn!(
+                    "Magick attempt {} failed, retrying in {:?}: {}",
+                    attempt,
+                    delay,
+                    err
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(err) => return Err(anyhow!("{} (after {} attempts)", err, attempt)),
+        }
+    }
+}
+
+async fn run_magick_once(
+    app: &AppHandle,
+    args: &[String],
+    timeout: Duration,
+    logger: Option<&ProcessingLogger>,
+) -> Result<()> {
+    let started = Instant::now();
+    let (mut events, child) = get_sidecar_magick(app)
         .args(args)
-        .output()
-        .await
+        .spawn()
         .context("Failed to run magick command")?;
 
-    if output.status.success() {
+    let mut stdout = Vec::new();
+    let mut stderr = Vec::new();
+    let collect = async {
+        let mut code = None;
+        while let Some(event) = events.recv().await {
+            match event {
+                CommandEvent::Stdout(line) => stdout.extend(line),
+                CommandEvent::Stderr(line) => stderr.extend(line),
+                CommandEvent::Error(err) => stderr.extend(err.into_bytes()),
+                CommandEvent::Terminated(payload) => code = payload.code,
+                _ => {}
+            }
+        }
+        code
+    };
+
+    let code = match tokio::time::timeout(timeout, collect).await {
+        Ok(code) => code,
+        Err(_) => {
+            if let Err(err) = child.kill() {
+                log::warn!("Failed to kill timed out magick process: {}", err);
+            }
+            return Err(MagickTimedOut(timeout.as_secs()).into());
+        }
+    };
+
+    if let Some(logger) = logger {
+        if let Err(err) = logger.record(args, code, &stdout, &stderr, started.elapsed()) {
+            log::warn!("Failed to write processing log: {}", err);
+        }
+    }
+
+    if code == Some(0) {
         log::info!(
             "Magick command succeeded: {}",
-            String::from_utf8_lossy(&output.stdout)
+            String::from_utf8_lossy(&stdout)
         );
         Ok(())
     } else {
         Err(anyhow!(
             "Magick command failed with exit code {}, stderr: {}",
-            output.status.code().unwrap_or(1),
-            String::from_utf8_lossy(&output.stderr)
+            code.unwrap_or(1),
+            String::from_utf8_lossy(&stderr)
         ))
     }
 }
 
-fn send_webp_image(app: &AppHandle, path: &Path, page_number: usize) -> Result<()> {
+pub fn send_webp_image(
+    app: &AppHandle,
+    session_id: &str,
+    path: &Path,
+    page_number: usize,
+    mime_type: &str,
+) -> Result<()> {
     let mut file = File::open(path)?;
     let mut buffer = Vec::new();
     file.read_to_end(&mut buffer)?;
@@ -203,11 +1623,179 @@ fn send_webp_image(app: &AppHandle, path: &Path, page_number: usize) -> Result<(
     app.emit(
         "image",
         ImageLoaded {
-            page_number: page_number as u16,
+            session_id: session_id.to_string(),
+            page_number: page_number_u32(page_number)?,
             path: path.display().to_string(),
+            mime_type: mime_type.to_string(),
             data: buffer,
         },
     )?;
-    
+
+    Ok(())
+}
+
+pub fn page_number_u32(page_number: usize) -> Result<u32> {
+    u32::try_from(page_number).with_context(|| format!("Page number {} is too large", page_number))
+}
+
+pub fn emit_page_errors(
+    app: &AppHandle,
+    session_id: &str,
+    failed_pages: Vec<u32>,
+    errors: Vec<String>,
+) -> Result<()> {
+    app.emit(
+        "page_errors",
+        PageErrors {
+            session_id: session_id.to_string(),
+            failed_pages,
+            errors,
+        },
+    )?;
     Ok(())
 }
+
+pub fn emit_progress(
+    app: &AppHandle,
+    session_id: &str,
+    input: &str,
+    current: usize,
+    total: usize,
+) -> Result<()> {
+    app.emit(
+        "progress",
+        Progress {
+            session_id: session_id.to_string(),
+            current: page_number_u32(current)?,
+            total: page_number_u32(total)?,
+            path: input.to_string(),
+        },
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::document_processor::renderer::MockRenderer;
+
+    #[derive(Default)]
+    struct RecordedEvents {
+        rendered: Mutex<Vec<usize>>,
+        progress: Mutex<Vec<(usize, usize)>>,
+    }
+
+    impl RenderEvents for RecordedEvents {
+        fn page_rendered(&self, page: usize, _image: &Path, _mime_type: &str) -> Result<()> {
+            self.rendered.lock().unwrap().push(page);
+            Ok(())
+        }
+
+        fn blank_page_skipped(&self, _page: usize) -> Result<()> {
+            Ok(())
+        }
+
+        fn progress(&self, current: usize, total: usize) -> Result<()> {
+            self.progress.lock().unwrap().push((current, total));
+            Ok(())
+        }
+
+        fn page_errors(&self, _failed_pages: Vec<u32>, _errors: Vec<String>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn test_cache(name: &str, page_count: usize, options: &ProcessingOptions) -> Cache {
+        let data_dir =
+            std::env::temp_dir().join(format!("process_pages_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&data_dir);
+        fs::create_dir_all(&data_dir).unwrap();
+        let input = data_dir.join("input.pdf");
+        fs::write(&input, b"%PDF-1.4").unwrap();
+        Cache::new(
+            data_dir,
+            CacheManifest::new(&input, page_count, options).unwrap(),
+        )
+    }
+
+    #[test]
+    fn accented_text_strings_round_trip() {
+        let encoded = encode_pdf_string("Conceição");
+        assert_eq!(encoded[..2], [0xFE, 0xFF]);
+        assert_eq!(decode_pdf_string(&encoded), "Conceição");
+        assert_eq!(encode_pdf_string("Joao"), b"Joao");
+        assert_eq!(decode_pdf_string(b"Jo\xe3o \x80"), "João •");
+    }
+
+    #[tokio::test]
+    async fn process_pages_renders_and_reports_every_page_in_order() {
+        let options = ProcessingOptions::default();
+        let mut cache = test_cache("renders", 3, &options);
+        let events = RecordedEvents::default();
+
+        process_pages(
+            &events,
+            "input.pdf",
+            &mut cache,
+            &options,
+            Arc::new(MockRenderer),
+            &CancellationToken::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(*events.rendered.lock().unwrap(), [1, 2, 3]);
+        assert_eq!(*events.progress.lock().unwrap(), [(1, 3), (2, 3), (3, 3)]);
+        assert_eq!(cache.manifest().page_hashes().len(), 3);
+        assert!((1..=3).all(|page| cache.page_path(page, options.format).exists()));
+        fs::remove_dir_all(cache.data_dir()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn cancelled_process_pages_renders_nothing() {
+        let options = ProcessingOptions::default();
+        let mut cache = test_cache("cancelled", 3, &options);
+        let events = RecordedEvents::default();
+        let cancel_token = CancellationToken::default();
+        cancel_token.cancel();
+
+        let result = process_pages(
+            &events,
+            "input.pdf",
+            &mut cache,
+            &options,
+            Arc::new(MockRenderer),
+            &cancel_token,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(events.rendered.lock().unwrap().is_empty());
+        assert!(events.progress.lock().unwrap().is_empty());
+        assert!(!cache.page_path(1, options.format).exists());
+        fs::remove_dir_all(cache.data_dir()).unwrap();
+    }
+
+    #[test]
+    fn paths_with_spaces_are_passed_as_single_arguments() {
+        let input = page_input_arg("/home/user/My Documents/scan 2024.pdf", 0);
+        let output = Path::new("/tmp/cache dir/1.webp");
+        let args = create_magick_args(&input, output, &ProcessingOptions::default());
+
+        let inputs: Vec<_> = args
+            .iter()
+            .filter(|arg| arg.contains("My Documents"))
+            .collect();
+        assert_eq!(inputs, ["/home/user/My Documents/scan 2024.pdf[0]"]);
+        assert_eq!(
+            args.last().map(String::as_str),
+            Some("/tmp/cache dir/1.webp")
+        );
+        assert!(args
+            .iter()
+            .all(|arg| !arg.starts_with(['"', '\'']) && !arg.ends_with(['"', '\''])));
+    }
+}
@@ -0,0 +1,212 @@
+use std::{fs, path::PathBuf, sync::RwLock};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use super::error::Error;
+use super::options::{ImageFormat, ProcessingOptions};
+
+const SETTINGS_FILE_NAME: &str = "settings.json";
+const DEFAULT_LOCALE: &str = "pt-BR";
+const SUPPORTED_LOCALES: [&str; 2] = ["pt-BR", "en-US"];
+const DISPLAY_MODES: [&str; 3] = ["fit_width", "fit_height", "actual_size"];
+const CUSTOM_ZOOM_PREFIX: &str = "custom_zoom:";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub density: u32,
+    pub format: ImageFormat,
+    pub resize: String,
+    pub cache_dir: Option<PathBuf>,
+    pub max_parallel_renders: usize,
+    pub magick_binary: Option<String>,
+    pub theme: Option<String>,
+    pub locale: Option<String>,
+    pub display_mode: Option<String>,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        let options = ProcessingOptions::default();
+        Self {
+            density: options.density,
+            format: options.format,
+            resize: options.resize,
+            cache_dir: None,
+            max_parallel_renders: options.max_parallel_renders,
+            magick_binary: None,
+            theme: None,
+            locale: None,
+            display_mode: None,
+        }
+    }
+}
+
+impl AppSettings {
+    pub fn apply_to(&self, options: ProcessingOptions) -> ProcessingOptions {
+        options
+            .with_density(self.density)
+            .with_resize(self.resize.clone())
+            .with_format(self.format)
+            .with_max_parallel_renders(self.max_parallel_renders)
+    }
+
+    pub fn processing_options(&self) -> ProcessingOptions {
+        self.apply_to(ProcessingOptions::default())
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.density == 0 {
+            return Err(anyhow!("Density must be greater than zero"));
+        }
+        if self.max_parallel_renders == 0 {
+            return Err(anyhow!("At least one parallel render is required"));
+        }
+        if self.resize.trim().is_empty() {
+            return Err(anyhow!("Resize dimensions must not be empty"));
+        }
+        if let Some(locale) = &self.locale {
+            if !SUPPORTED_LOCALES.contains(&locale.as_str()) {
+                return Err(anyhow!("Unsupported locale {}", locale));
+            }
+        }
+        if let Some(display_mode) = &self.display_mode {
+            if !is_valid_display_mode(display_mode) {
+                return Err(anyhow!("Unsupported display mode {}", display_mode));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Settings(RwLock<AppSettings>);
+
+impl Settings {
+    pub fn load(app: &AppHandle) -> Self {
+        match read_settings(app) {
+            Ok(settings) => Self(RwLock::new(settings)),
+            Err(err) => {
+                log::warn!("Failed to load settings, using defaults: {}", err);
+                Self::default()
+            }
+        }
+    }
+}
+
+pub fn current_settings(app: &AppHandle) -> AppSettings {
+    app.state::<Settings>()
+        .0
+        .read()
+        .map(|settings| settings.clone())
+        .unwrap_or_default()
+}
+
+pub fn default_options(app: &AppHandle) -> ProcessingOptions {
+    current_settings(app).processing_options()
+}
+
+#[tauri::command]
+pub fn get_settings(app: AppHandle) -> Result<AppSettings, Error> {
+    Ok(current_settings(&app))
+}
+
+#[tauri::command]
+pub fn save_settings(app: AppHandle, settings: AppSettings) -> Result<(), Error> {
+    settings.validate()?;
+    write_settings(&app, settings)
+}
+
+#[tauri::command]
+pub fn get_locale(app: AppHandle) -> String {
+    current_settings(&app)
+        .locale
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+#[tauri::command]
+pub fn set_locale(app: AppHandle, locale: String) -> Result<(), Error> {
+    let settings = AppSettings {
+        locale: Some(locale),
+        ..current_settings(&app)
+    };
+    settings.validate()?;
+    write_settings(&app, settings)
+}
+
+#[tauri::command]
+pub fn get_display_mode(app: AppHandle) -> Option<String> {
+    current_settings(&app).display_mode
+}
+
+#[tauri::command]
+pub fn set_display_mode(app: AppHandle, display_mode: String) -> Result<(), Error> {
+    let settings = AppSettings {
+        display_mode: Some(display_mode),
+        ..current_settings(&app)
+    };
+    settings.validate()?;
+    write_settings(&app, settings)
+}
+
+fn is_valid_display_mode(display_mode: &str) -> bool {
+    match display_mode.strip_prefix(CUSTOM_ZOOM_PREFIX) {
+        Some(zoom) => zoom.parse::<f64>().is_ok_and(|zoom| zoom > 0.0),
+        None => DISPLAY_MODES.contains(&display_mode),
+    }
+}
+
+fn write_settings(app: &AppHandle, settings: AppSettings) -> Result<(), Error> {
+    let file = settings_file(app)?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|err| Error::io(parent, err))?;
+    }
+    let contents =
+        serde_json::to_string_pretty(&settings).context("Failed to serialize settings")?;
+    fs::write(&file, contents).map_err(|err| Error::io(&file, err))?;
+
+    log::info!("Saved settings to {}", file.display());
+    replace_settings(app, settings)
+}
+
+#[tauri::command]
+pub fn reset_settings(app: AppHandle) -> Result<AppSettings, Error> {
+    let file = settings_file(&app)?;
+    if file.exists() {
+        fs::remove_file(&file).map_err(|err| Error::io(&file, err))?;
+    }
+
+    log::info!("Reset settings to defaults");
+    replace_settings(&app, AppSettings::default())?;
+    Ok(AppSettings::default())
+}
+
+fn replace_settings(app: &AppHandle, settings: AppSettings) -> Result<(), Error> {
+    let state = app.state::<Settings>();
+    let mut current = state
+        .0
+        .write()
+        .map_err(|_| anyhow!("Settings lock was poisoned"))?;
+    *current = settings;
+    Ok(())
+}
+
+fn read_settings(app: &AppHandle) -> Result<AppSettings> {
+    let file = settings_file(app)?;
+    if !file.exists() {
+        return Ok(AppSettings::default());
+    }
+
+    let contents = fs::read_to_string(&file).context("Failed to read settings")?;
+    serde_json::from_str(&contents).context("Failed to parse settings")
+}
+
+fn settings_file(app: &AppHandle) -> Result<PathBuf> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .context("Failed to resolve app data directory")?;
+    Ok(app_data_dir.join(SETTINGS_FILE_NAME))
+}
@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use super::error::Error;
+use super::selector::{extract_page_text, load_text_document};
+
+const WORDS_PER_MINUTE: u32 = 200;
+
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct PageStatistics {
+    word_count: u32,
+    char_count: u32,
+    estimated_reading_seconds: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentStatistics {
+    total: PageStatistics,
+    pages: Vec<(u32, PageStatistics)>,
+}
+
+impl PageStatistics {
+    fn from_text(text: &str) -> Self {
+        let word_count = text.split_whitespace().count() as u32;
+        Self {
+            word_count,
+            // Whitespace is left out so the count does not depend on how lines were joined.
+            char_count: text.chars().filter(|c| !c.is_whitespace()).count() as u32,
+            estimated_reading_seconds: reading_seconds(word_count),
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_page_statistics(path: PathBuf, page: u32) -> Result<PageStatistics, Error> {
+    let document = load_text_document(&path).await?;
+    let text = extract_page_text(&document, page)?;
+    Ok(PageStatistics::from_text(&text))
+}
+
+#[tauri::command]
+pub async fn get_document_statistics(path: PathBuf) -> Result<DocumentStatistics, Error> {
+    let document = load_text_document(&path).await?;
+    let mut total = PageStatistics::default();
+    let mut pages = Vec::new();
+
+    for page in document.get_pages().into_keys() {
+        let statistics = PageStatistics::from_text(&extract_page_text(&document, page)?);
+        total.word_count += statistics.word_count;
+        total.char_count += statistics.char_count;
+        pages.push((page, statistics));
+    }
+    total.estimated_reading_seconds = reading_seconds(total.word_count);
+
+    Ok(DocumentStatistics { total, pages })
+}
+
+fn reading_seconds(word_count: u32) -> u32 {
+    (word_count * 60).div_ceil(WORDS_PER_MINUTE)
+}
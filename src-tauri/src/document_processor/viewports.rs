@@ -0,0 +1,82 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+
+use super::error::Error;
+
+const VIEWPORTS_FILE_NAME: &str = "viewports.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewportState {
+    path_hash: String,
+    last_page: u32,
+    zoom: f64,
+}
+
+#[tauri::command]
+pub fn save_viewport(app: AppHandle, path: PathBuf, page: u32, zoom: f64) -> Result<(), Error> {
+    if page == 0 || !zoom.is_finite() || zoom <= 0.0 {
+        return Err(anyhow!("Invalid viewport for {}", path.display()).into());
+    }
+
+    let path_hash = path_hash(&path);
+    let mut viewports = load_viewports(&app)?;
+    viewports.retain(|viewport| viewport.path_hash != path_hash);
+    viewports.push(ViewportState {
+        path_hash,
+        last_page: page,
+        zoom,
+    });
+    save_viewports(&app, &viewports).map_err(Error::CommandError)
+}
+
+#[tauri::command]
+pub fn get_viewport(app: AppHandle, path: PathBuf) -> Result<Option<ViewportState>, Error> {
+    let path_hash = path_hash(&path);
+    Ok(load_viewports(&app)?
+        .into_iter()
+        .find(|viewport| viewport.path_hash == path_hash))
+}
+
+// Keyed the same way as the page cache, so the file never stores document paths.
+fn path_hash(path: &Path) -> String {
+    let canonical_path = dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    format!(
+        "{:x}",
+        Sha256::digest(canonical_path.to_string_lossy().as_bytes())
+    )
+}
+
+fn load_viewports(app: &AppHandle) -> Result<Vec<ViewportState>> {
+    let file = viewports_file(app)?;
+    if !file.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&file).context("Failed to read viewports")?;
+    serde_json::from_str(&contents).context("Failed to parse viewports")
+}
+
+fn save_viewports(app: &AppHandle, viewports: &[ViewportState]) -> Result<()> {
+    let file = viewports_file(app)?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).context("Failed to create app data directory")?;
+    }
+
+    let contents = serde_json::to_string_pretty(viewports)?;
+    fs::write(&file, contents).context("Failed to write viewports")
+}
+
+fn viewports_file(app: &AppHandle) -> Result<PathBuf> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .context("Failed to resolve app data directory")?;
+    Ok(app_data_dir.join(VIEWPORTS_FILE_NAME))
+}
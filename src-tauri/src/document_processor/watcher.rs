@@ -0,0 +1,68 @@
+use std::{collections::HashMap, path::PathBuf, sync::Mutex};
+
+use anyhow::{anyhow, Context};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use super::error::Error;
+
+#[derive(Default)]
+pub struct DocumentWatchers(Mutex<HashMap<PathBuf, RecommendedWatcher>>);
+
+#[derive(Debug, Clone, Serialize)]
+struct DocumentChanged {
+    path: String,
+}
+
+#[tauri::command]
+pub fn watch_document(app: AppHandle, path: PathBuf) -> Result<(), Error> {
+    let watchers = app.state::<DocumentWatchers>();
+    let mut watchers = watchers
+        .0
+        .lock()
+        .map_err(|_| anyhow!("Document watchers lock was poisoned"))?;
+    if watchers.contains_key(&path) {
+        return Ok(());
+    }
+
+    let handle = app.clone();
+    let changed_path = path.display().to_string();
+    let mut watcher =
+        notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+            Ok(event) if matches!(event.kind, EventKind::Modify(_)) => {
+                log::info!("Document changed on disk: {}", changed_path);
+                if let Err(err) = handle.emit(
+                    "document_changed",
+                    DocumentChanged {
+                        path: changed_path.clone(),
+                    },
+                ) {
+                    log::error!("Failed to emit document_changed: {}", err);
+                }
+            }
+            Ok(_) => {}
+            Err(err) => log::warn!("Watcher error on {}: {}", changed_path, err),
+        })
+        .context("Failed to create file watcher")?;
+    watcher
+        .watch(&path, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", path.display()))?;
+
+    log::info!("Watching {}", path.display());
+    watchers.insert(path, watcher);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn unwatch_document(app: AppHandle, path: PathBuf) -> Result<(), Error> {
+    let watchers = app.state::<DocumentWatchers>();
+    let mut watchers = watchers
+        .0
+        .lock()
+        .map_err(|_| anyhow!("Document watchers lock was poisoned"))?;
+    if watchers.remove(&path).is_some() {
+        log::info!("Stopped watching {}", path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,218 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use lopdf::{
+    content::{Content, Operation},
+    dictionary, Dictionary, Document, Object, ObjectId, Stream, StringFormat,
+};
+
+use super::error::Error;
+use super::selector::{
+    inherited_media_box, load_pdf_blocking, DEFAULT_MEDIA_BOX, MAX_PAGE_TREE_DEPTH,
+};
+
+const FONT_NAME: &str = "WatermarkFont";
+const GRAPHICS_STATE_NAME: &str = "WatermarkState";
+// Helvetica glyphs average about half an em, which is close enough to centre the text.
+const AVERAGE_GLYPH_WIDTH: f32 = 0.5;
+const DIAGONAL_COVERAGE: f32 = 0.7;
+const MIN_FONT_SIZE: f32 = 12.0;
+const MAX_FONT_SIZE: f32 = 200.0;
+const WATERMARK_GRAY: f32 = 0.5;
+
+#[tauri::command]
+pub async fn watermark_pages(
+    path: PathBuf,
+    text: String,
+    opacity: f32,
+    output: PathBuf,
+) -> Result<(), Error> {
+    if text.trim().is_empty() {
+        return Err(anyhow!("Watermark text must not be empty").into());
+    }
+    if !(0.0..=1.0).contains(&opacity) {
+        return Err(anyhow!("Opacity must be between 0 and 1").into());
+    }
+
+    let mut document = load_pdf_blocking(path.clone()).await?;
+    if document.is_encrypted() {
+        return Err(Error::EncryptedDocument);
+    }
+
+    let font_id = document.add_object(dictionary! {
+        "Type" => "Font",
+        "Subtype" => "Type1",
+        "BaseFont" => "Helvetica",
+        "Encoding" => "WinAnsiEncoding",
+    });
+    let state_id = document.add_object(dictionary! {
+        "Type" => "ExtGState",
+        "ca" => opacity,
+        "CA" => opacity,
+    });
+
+    let page_ids: Vec<ObjectId> = document.get_pages().into_values().collect();
+    for page_id in &page_ids {
+        watermark_page(&mut document, *page_id, &text, font_id, state_id)?;
+    }
+    document
+        .save(&output)
+        .context("Failed to save watermarked document")?;
+
+    log::info!(
+        "Watermarked {} pages of {} into {}",
+        page_ids.len(),
+        path.display(),
+        output.display()
+    );
+    Ok(())
+}
+
+fn watermark_page(
+    document: &mut Document,
+    page_id: ObjectId,
+    text: &str,
+    font_id: ObjectId,
+    state_id: ObjectId,
+) -> Result<()> {
+    let page = document
+        .get_dictionary(page_id)
+        .context("Failed to read page dictionary")?;
+    let media_box = inherited_media_box(document, page).unwrap_or(DEFAULT_MEDIA_BOX);
+    let mut resources = inherited_resources(document, page);
+    let existing_contents = match page.get(b"Contents") {
+        Ok(Object::Array(contents)) => contents.clone(),
+        Ok(Object::Reference(id)) => match document.get_object(*id) {
+            Ok(Object::Array(contents)) => contents.clone(),
+            _ => vec![Object::Reference(*id)],
+        },
+        _ => Vec::new(),
+    };
+
+    add_resource(document, &mut resources, b"Font", FONT_NAME, font_id);
+    add_resource(
+        document,
+        &mut resources,
+        b"ExtGState",
+        GRAPHICS_STATE_NAME,
+        state_id,
+    );
+
+    // The original content is wrapped in q/Q so a dangling transform can't skew the watermark.
+    let save_state = Content {
+        operations: vec![Operation::new("q", vec![])],
+    };
+    let save_id = document.add_object(Stream::new(Dictionary::new(), save_state.encode()?));
+    let watermark_id = document.add_object(Stream::new(
+        Dictionary::new(),
+        watermark_content(text, media_box).encode()?,
+    ));
+
+    let mut contents = vec![Object::Reference(save_id)];
+    contents.extend(existing_contents);
+    contents.push(Object::Reference(watermark_id));
+
+    let page = document
+        .get_object_mut(page_id)
+        .and_then(Object::as_dict_mut)
+        .context("Failed to read page dictionary")?;
+    page.set("Resources", resources);
+    page.set("Contents", contents);
+    Ok(())
+}
+
+fn watermark_content(text: &str, [x0, y0, x1, y1]: [f32; 4]) -> Content {
+    let width = (x1 - x0).abs();
+    let height = (y1 - y0).abs();
+    let diagonal = width.hypot(height);
+    let angle = height.atan2(width);
+    let glyphs = text.chars().count() as f32;
+    let font_size = (diagonal * DIAGONAL_COVERAGE / (glyphs * AVERAGE_GLYPH_WIDTH))
+        .clamp(MIN_FONT_SIZE, MAX_FONT_SIZE);
+    let text_width = glyphs * AVERAGE_GLYPH_WIDTH * font_size;
+    let (sin, cos) = angle.sin_cos();
+    let centre_x = (x0 + x1) / 2.0;
+    let centre_y = (y0 + y1) / 2.0;
+
+    Content {
+        operations: vec![
+            Operation::new("Q", vec![]),
+            Operation::new("q", vec![]),
+            Operation::new("gs", vec![Object::Name(GRAPHICS_STATE_NAME.into())]),
+            Operation::new("g", vec![WATERMARK_GRAY.into()]),
+            Operation::new("BT", vec![]),
+            Operation::new("Tf", vec![Object::Name(FONT_NAME.into()), font_size.into()]),
+            Operation::new(
+                "Tm",
+                vec![
+                    cos.into(),
+                    sin.into(),
+                    (-sin).into(),
+                    cos.into(),
+                    centre_x.into(),
+                    centre_y.into(),
+                ],
+            ),
+            Operation::new(
+                "Td",
+                vec![(-text_width / 2.0).into(), (-font_size / 3.0).into()],
+            ),
+            Operation::new(
+                "Tj",
+                vec![Object::String(win_ansi(text), StringFormat::Literal)],
+            ),
+            Operation::new("ET", vec![]),
+            Operation::new("Q", vec![]),
+        ],
+    }
+}
+
+// Helvetica is set up with WinAnsiEncoding, which matches Latin-1 for accented letters.
+fn win_ansi(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| u8::try_from(c as u32).unwrap_or(b'?'))
+        .collect()
+}
+
+fn inherited_resources(document: &Document, page: &Dictionary) -> Dictionary {
+    let mut node = page;
+    for _ in 0..MAX_PAGE_TREE_DEPTH {
+        if let Ok(resources) = node.get(b"Resources") {
+            return resolve_dictionary(document, resources).unwrap_or_default();
+        }
+        let Some(parent) = node
+            .get(b"Parent")
+            .and_then(Object::as_reference)
+            .ok()
+            .and_then(|parent| document.get_dictionary(parent).ok())
+        else {
+            break;
+        };
+        node = parent;
+    }
+    Dictionary::new()
+}
+
+fn add_resource(
+    document: &Document,
+    resources: &mut Dictionary,
+    category: &[u8],
+    name: &str,
+    id: ObjectId,
+) {
+    let mut entries = resources
+        .get(category)
+        .ok()
+        .and_then(|entries| resolve_dictionary(document, entries))
+        .unwrap_or_default();
+    entries.set(name, Object::Reference(id));
+    resources.set(category.to_vec(), entries);
+}
+
+fn resolve_dictionary(document: &Document, object: &Object) -> Option<Dictionary> {
+    match object {
+        Object::Reference(id) => document.get_dictionary(*id).ok().cloned(),
+        Object::Dictionary(dictionary) => Some(dictionary.clone()),
+        _ => None,
+    }
+}
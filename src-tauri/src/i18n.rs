@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use tauri::AppHandle;
+
+use crate::document_processor::settings::get_locale;
+
+// The backend reads the same catalogues the frontend fetches from `public/`.
+const PT_BR_CATALOGUE: &str = include_str!("../../public/locales/pt-BR.json");
+const EN_US_CATALOGUE: &str = include_str!("../../public/locales/en-US.json");
+
+fn catalogue(locale: &str) -> &'static str {
+    match locale {
+        "en-US" => EN_US_CATALOGUE,
+        _ => PT_BR_CATALOGUE,
+    }
+}
+
+// Falls back to the key itself, like the frontend, so a missing entry is visible.
+pub fn t(app: &AppHandle, key: &str) -> String {
+    serde_json::from_str::<HashMap<String, String>>(catalogue(&get_locale(app.clone())))
+        .ok()
+        .and_then(|mut messages| messages.remove(key))
+        .unwrap_or_else(|| key.to_string())
+}
+
+// Replaces each `{}` in the message with the next argument, in order.
+pub fn format(app: &AppHandle, key: &str, args: &[String]) -> String {
+    let template = t(app, key);
+    let mut args = args.iter();
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template.as_str();
+    while let Some((before, after)) = rest.split_once("{}") {
+        message.push_str(before);
+        message.push_str(args.next().map(String::as_str).unwrap_or("{}"));
+        rest = after;
+    }
+    message.push_str(rest);
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalogues_define_the_same_keys() {
+        let keys = |locale| {
+            let mut keys = serde_json::from_str::<HashMap<String, String>>(catalogue(locale))
+                .unwrap()
+                .into_keys()
+                .collect::<Vec<_>>();
+            keys.sort();
+            keys
+        };
+        assert_eq!(keys("pt-BR"), keys("en-US"));
+    }
+}
@@ -0,0 +1,78 @@
+use std::{path::PathBuf, sync::Mutex};
+
+use anyhow::anyhow;
+use serde::Serialize;
+use tauri::{AppHandle, Manager, RunEvent};
+
+use crate::document_processor::error::Error;
+
+const OPEN_FLAG: &str = "--open";
+
+#[derive(Debug, Clone, Serialize)]
+struct OpenDocument {
+    path: PathBuf,
+}
+
+#[derive(Debug, Default)]
+struct LaunchState {
+    frontend_ready: bool,
+    pending: Vec<PathBuf>,
+}
+
+// Paths handed over before the frontend is listening are queued until it asks for them.
+#[derive(Debug, Default)]
+pub struct LaunchDocuments(Mutex<LaunchState>);
+
+pub fn paths_from_args(args: impl IntoIterator<Item = String>) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == OPEN_FLAG {
+            paths.extend(args.next().map(PathBuf::from));
+        } else if !arg.starts_with('-') {
+            paths.push(PathBuf::from(arg));
+        }
+    }
+    paths
+}
+
+pub fn handle_open_path(app: &AppHandle, path: PathBuf) {
+    log::info!("Opening {} on request of the OS", path.display());
+    let documents = app.state::<LaunchDocuments>();
+    let Ok(mut state) = documents.0.lock() else {
+        log::error!("Launch documents lock was poisoned");
+        return;
+    };
+    if !state.frontend_ready {
+        state.pending.push(path);
+        return;
+    }
+    if let Err(err) = app.emit("open_document", OpenDocument { path }) {
+        log::error!("Failed to emit open_document: {}", err);
+    }
+}
+
+pub fn handle_run_event(app: &AppHandle, event: RunEvent) {
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    if let RunEvent::Opened { urls } = event {
+        for url in urls {
+            match url.to_file_path() {
+                Ok(path) => handle_open_path(app, path),
+                Err(_) => log::warn!("Ignoring non-file URL {}", url),
+            }
+        }
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+    let _ = (app, event);
+}
+
+#[tauri::command]
+pub fn take_pending_documents(app: AppHandle) -> Result<Vec<PathBuf>, Error> {
+    let documents = app.state::<LaunchDocuments>();
+    let mut state = documents
+        .0
+        .lock()
+        .map_err(|_| anyhow!("Launch documents lock was poisoned"))?;
+    state.frontend_ready = true;
+    Ok(std::mem::take(&mut state.pending))
+}
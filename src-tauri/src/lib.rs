@@ -1,47 +1,244 @@
 mod document_processor;
+mod i18n;
+mod launch;
+mod logging;
+mod notifications;
+mod tray;
+mod window;
+use document_processor::annotations::*;
+use document_processor::anonymize::*;
+use document_processor::batch::*;
+use document_processor::bookmarks::*;
+use document_processor::cache::*;
+use document_processor::cancellation::*;
+use document_processor::compliance::*;
+use document_processor::download::*;
+use document_processor::duplicates::*;
+use document_processor::export::*;
+use document_processor::fonts::*;
+use document_processor::forms::*;
+use document_processor::health::*;
+use document_processor::links::*;
+use document_processor::magick::*;
+use document_processor::manager::*;
+use document_processor::merge::*;
+use document_processor::ocr::*;
+use document_processor::outline::*;
+use document_processor::pages::*;
+use document_processor::processing_log::*;
+use document_processor::recents::*;
+use document_processor::redact::*;
+use document_processor::search::*;
 use document_processor::selector::*;
+use document_processor::settings::*;
+use document_processor::statistics::*;
+use document_processor::viewports::*;
+use document_processor::watcher::*;
+use document_processor::watermark::*;
+use launch::*;
+use log::Level;
+use logging::*;
+use notifications::*;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::Manager;
+use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
 use tauri_plugin_log::{Target, TargetKind};
+use tray::*;
+use window::*;
 
 #[tauri::command]
-fn log_trace(message: String) -> String {
-    log::trace!("{}", message);
+fn log_trace(message: String, fields: Option<HashMap<String, String>>) -> String {
+    log_with_fields(
+        Level::Trace,
+        module_path!(),
+        &message,
+        &fields.unwrap_or_default(),
+    );
     "Logged".to_string()
 }
 
 #[tauri::command]
-fn log_info(message: String) -> String {
-    log::info!("{}", message);
+fn log_info(message: String, fields: Option<HashMap<String, String>>) -> String {
+    log_with_fields(
+        Level::Info,
+        module_path!(),
+        &message,
+        &fields.unwrap_or_default(),
+    );
     "Logged".to_string()
 }
 
 #[tauri::command]
-fn log_error(message: String) -> String {
-    log::error!("{}", message);
+fn log_warn(message: String, fields: Option<HashMap<String, String>>) -> String {
+    log_with_fields(
+        Level::Warn,
+        module_path!(),
+        &message,
+        &fields.unwrap_or_default(),
+    );
     "Logged".to_string()
 }
 
+#[tauri::command]
+fn log_error(message: String, fields: Option<HashMap<String, String>>) -> String {
+    log_with_fields(
+        Level::Error,
+        module_path!(),
+        &message,
+        &fields.unwrap_or_default(),
+    );
+    "Logged".to_string()
+}
+
+async fn check_magick(app: tauri::AppHandle) {
+    match get_magick_version(app.clone()).await {
+        Ok(version) => log::info!("Found ImageMagick {}", version),
+        Err(err) => {
+            log::error!("ImageMagick check failed: {}", err);
+            app.dialog()
+                .message(i18n::format(
+                    &app,
+                    "backend.magick_missing",
+                    &[err.to_string()],
+                ))
+                .title(i18n::t(&app, "backend.magick_missing_title"))
+                .kind(MessageDialogKind::Error)
+                .show(|_| {});
+        }
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let mut log_builder = tauri_plugin_log::Builder::new().targets([
+        Target::new(TargetKind::Stdout),
+        Target::new(TargetKind::LogDir { file_name: None }),
+        Target::new(TargetKind::Webview),
+    ]);
+    if LogFormat::from_env() == LogFormat::Json {
+        log_builder = log_builder.format(json_format);
+    }
+
     tauri::Builder::default()
-        .plugin(
-            tauri_plugin_log::Builder::new()
-                .targets([
-                    Target::new(TargetKind::Stdout),
-                    Target::new(TargetKind::LogDir { file_name: None }),
-                    Target::new(TargetKind::Webview),
-                ])
-                .build(),
-        )
+        .plugin(log_builder.build())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_shell::init())
+        .manage(DocumentWatchers::default())
+        .manage(Mutex::new(DocumentManager::new()))
+        .manage(LaunchDocuments::default())
+        .manage(TrayStatus::default())
+        .manage(ReadyNotifications::default())
+        .setup(|app| {
+            app.manage(Settings::load(app.handle()));
+            if let Err(err) = restore_window_state(app.handle()) {
+                log::warn!("Failed to restore window state: {}", err);
+            }
+            let binary = tauri::async_runtime::block_on(resolve_magick_binary(app.handle()));
+            log::info!("Using ImageMagick binary {}", binary);
+            app.manage(MagickBinaryPath(binary));
+            create_tray(app.handle())?;
+            tauri::async_runtime::spawn(check_magick(app.handle().clone()));
+            for path in paths_from_args(std::env::args().skip(1)) {
+                handle_open_path(app.handle(), path);
+            }
+            Ok(())
+        })
+        .on_window_event(|window, event| {
+            handle_window_event(window, event);
+            handle_focus_event(window, event);
+        })
         .invoke_handler(tauri::generate_handler![
             log_trace,
             log_info,
+            log_warn,
             log_error,
             greet,
+            get_magick_version,
+            health_check,
+            toggle_fullscreen,
+            is_fullscreen,
+            save_window_state,
             select_document,
-            prepare_document
+            select_documents,
+            select_save_file,
+            select_directory,
+            cancel_preparation,
+            prepare_document,
+            prepare_document_with_options,
+            close_document,
+            decrypt_document,
+            download_document,
+            convert_to_pdf,
+            get_cache_dir,
+            clear_cache,
+            clear_all_caches,
+            verify_cache,
+            get_cache_info,
+            get_all_caches,
+            process_directory,
+            list_pdfs_in_dir,
+            extract_text_page,
+            extract_text_all,
+            ocr_page,
+            ocr_all_pages,
+            find_duplicate_pages,
+            search_document,
+            get_document_info,
+            get_page_dimensions,
+            get_page_statistics,
+            get_document_statistics,
+            get_document_log,
+            extract_outline,
+            list_document_links,
+            open_link,
+            detect_forms,
+            fill_form_field,
+            check_pdf_a_compliance,
+            get_embedded_fonts,
+            split_document,
+            merge_documents,
+            reorder_pages,
+            delete_page,
+            extract_page,
+            anonymize_document,
+            redact_region,
+            retry_page,
+            rerender_page,
+            get_page,
+            get_inverted_page,
+            rotate_page,
+            print_page,
+            copy_page_to_clipboard,
+            export_page_range,
+            export_document_images,
+            export_page_image,
+            watermark_pages,
+            add_annotation,
+            get_annotations,
+            delete_annotation,
+            add_bookmark,
+            get_bookmarks,
+            add_recent_document,
+            get_recent_documents,
+            get_settings,
+            save_settings,
+            reset_settings,
+            get_locale,
+            set_locale,
+            get_display_mode,
+            set_display_mode,
+            save_viewport,
+            get_viewport,
+            watch_document,
+            unwatch_document,
+            take_pending_documents,
+            set_tray_document,
+            notify_document_ready
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(handle_run_event);
 }
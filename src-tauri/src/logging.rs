@@ -0,0 +1,90 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Arguments,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use log::{
+    kv::{self, Key, Value, VisitSource},
+    Level, Record,
+};
+use serde::Serialize;
+use tauri_plugin_log::fern::FormatCallback;
+
+const LOG_FORMAT_VAR: &str = "LOG_FORMAT";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+impl LogFormat {
+    // Text stays the default; JSON is opt-in with `LOG_FORMAT=json`.
+    pub fn from_env() -> Self {
+        match std::env::var(LOG_FORMAT_VAR) {
+            Ok(format) if format.eq_ignore_ascii_case("json") => LogFormat::Json,
+            _ => LogFormat::Text,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct LogEntry<'a> {
+    timestamp: u128,
+    level: &'a str,
+    target: &'a str,
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+#[derive(Default)]
+struct FieldCollector(BTreeMap<String, String>);
+
+impl<'kvs> VisitSource<'kvs> for FieldCollector {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+}
+
+pub fn json_format(out: FormatCallback, message: &Arguments, record: &Record) {
+    let mut fields = FieldCollector::default();
+    if let Err(err) = record.key_values().visit(&mut fields) {
+        fields.0.insert("fields_error".to_string(), err.to_string());
+    }
+
+    let entry = LogEntry {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis())
+            .unwrap_or_default(),
+        level: record.level().as_str(),
+        target: record.target(),
+        message: message.to_string(),
+        fields: fields.0,
+    };
+    match serde_json::to_string(&entry) {
+        Ok(line) => out.finish(format_args!("{}", line)),
+        Err(_) => out.finish(*message),
+    }
+}
+
+pub fn log_with_fields(
+    level: Level,
+    target: &str,
+    message: &str,
+    fields: &HashMap<String, String>,
+) {
+    if level > log::max_level() {
+        return;
+    }
+    log::logger().log(
+        &Record::builder()
+            .level(level)
+            .target(target)
+            .args(format_args!("{}", message))
+            .key_values(fields)
+            .build(),
+    );
+}
@@ -0,0 +1,65 @@
+use std::{path::PathBuf, sync::Mutex};
+
+use anyhow::anyhow;
+use serde::Serialize;
+use tauri::{AppHandle, Manager, Window, WindowEvent};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::document_processor::error::Error;
+use crate::i18n;
+
+const MIN_NOTIFIED_PAGES: u32 = 50;
+
+#[derive(Debug, Clone, Serialize)]
+struct ReadyDocument {
+    path: PathBuf,
+}
+
+// Desktop notifications don't report clicks, so the notified document is brought forward
+// the next time the main window gains focus.
+#[derive(Debug, Default)]
+pub struct ReadyNotifications(Mutex<Option<PathBuf>>);
+
+#[tauri::command]
+pub fn notify_document_ready(
+    app: AppHandle,
+    path: PathBuf,
+    name: String,
+    page_count: u32,
+) -> Result<(), Error> {
+    if page_count < MIN_NOTIFIED_PAGES {
+        return Ok(());
+    }
+
+    app.notification()
+        .builder()
+        .title(i18n::t(&app, "backend.document_ready"))
+        .body(i18n::format(&app, "backend.document_ready_body", &[name]))
+        .show()
+        .map_err(anyhow::Error::from)?;
+
+    let notifications = app.state::<ReadyNotifications>();
+    let mut pending = notifications
+        .0
+        .lock()
+        .map_err(|_| anyhow!("Ready notifications lock was poisoned"))?;
+    *pending = Some(path);
+    Ok(())
+}
+
+pub fn handle_focus_event(window: &Window, event: &WindowEvent) {
+    if !matches!(event, WindowEvent::Focused(true)) || window.label() != "main" {
+        return;
+    }
+    let notifications = window.state::<ReadyNotifications>();
+    let Ok(mut pending) = notifications.0.lock() else {
+        log::error!("Ready notifications lock was poisoned");
+        return;
+    };
+    let Some(path) = pending.take() else {
+        return;
+    };
+    if let Err(err) = window.emit("document_ready_opened", ReadyDocument { path }) {
+        log::error!("Failed to emit document_ready_opened: {}", err);
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use tauri::{
+    image::Image,
+    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu},
+    tray::TrayIconBuilder,
+    AppHandle, Manager, Wry,
+};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::document_processor::error::Error;
+use crate::document_processor::recents::load_recent_documents;
+use crate::i18n;
+use crate::launch::handle_open_path;
+
+const TRAY_ID: &str = "main";
+const APP_NAME: &str = "Conectbras - DMA";
+const OPEN_FILE_ID: &str = "open_file";
+const SETTINGS_ID: &str = "settings";
+const QUIT_ID: &str = "quit";
+const RECENT_ID_PREFIX: &str = "recent:";
+const BUSY_FRAME_INTERVAL: Duration = Duration::from_millis(500);
+const BUSY_ALPHA_DIVISOR: u8 = 3;
+
+#[derive(Debug, Default)]
+pub struct TrayStatus {
+    processing: AtomicUsize,
+}
+
+// Keeps the tray icon animated for as long as any document is being processed.
+pub struct ProcessingIndicator(AppHandle);
+
+impl ProcessingIndicator {
+    pub fn start(app: &AppHandle) -> Self {
+        let previous = app
+            .state::<TrayStatus>()
+            .processing
+            .fetch_add(1, Ordering::SeqCst);
+        if previous == 0 {
+            tauri::async_runtime::spawn(animate_icon(app.clone()));
+        }
+        Self(app.clone())
+    }
+}
+
+impl Drop for ProcessingIndicator {
+    fn drop(&mut self) {
+        self.0
+            .state::<TrayStatus>()
+            .processing
+            .fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn create_tray(app: &AppHandle) -> tauri::Result<()> {
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(APP_NAME)
+        .menu(&build_menu(app)?)
+        .on_menu_event(handle_menu_event);
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_tray_document(app: AppHandle, name: Option<String>) -> Result<(), Error> {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return Ok(());
+    };
+    let tooltip = match name {
+        Some(name) => format!("{} — {}", APP_NAME, name),
+        None => APP_NAME.to_string(),
+    };
+    tray.set_tooltip(Some(tooltip))
+        .map_err(anyhow::Error::from)?;
+    tray.set_menu(Some(build_menu(&app).map_err(anyhow::Error::from)?))
+        .map_err(anyhow::Error::from)?;
+    Ok(())
+}
+
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let recent_documents = load_recent_documents(app).unwrap_or_else(|err| {
+        log::warn!("Failed to load recent documents for the tray: {}", err);
+        Vec::new()
+    });
+    let recent_items = recent_documents
+        .iter()
+        .map(|document| {
+            MenuItem::with_id(
+                app,
+                format!("{}{}", RECENT_ID_PREFIX, document.path()),
+                document.name(),
+                true,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let recent_refs: Vec<&dyn tauri::menu::IsMenuItem<Wry>> = recent_items
+        .iter()
+        .map(|item| item as &dyn tauri::menu::IsMenuItem<Wry>)
+        .collect();
+    let recent = Submenu::with_items(
+        app,
+        i18n::t(app, "tray.recent"),
+        !recent_items.is_empty(),
+        &recent_refs,
+    )?;
+
+    Menu::with_items(
+        app,
+        &[
+            &MenuItem::with_id(
+                app,
+                OPEN_FILE_ID,
+                i18n::t(app, "tray.open_file"),
+                true,
+                None::<&str>,
+            )?,
+            &recent,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(
+                app,
+                SETTINGS_ID,
+                i18n::t(app, "palette.settings"),
+                true,
+                None::<&str>,
+            )?,
+            &MenuItem::with_id(app, QUIT_ID, i18n::t(app, "tray.quit"), true, None::<&str>)?,
+        ],
+    )
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    let id = event.id().as_ref();
+    match id {
+        OPEN_FILE_ID => {
+            let handle = app.clone();
+            app.dialog()
+                .file()
+                .add_filter("PDF", &["pdf"])
+                .pick_file(move |selection| {
+                    if let Some(selection) = selection {
+                        show_main_window(&handle);
+                        handle_open_path(&handle, selection.path);
+                    }
+                });
+        }
+        SETTINGS_ID => {
+            show_main_window(app);
+            if let Err(err) = app.emit("open_settings", ()) {
+                log::error!("Failed to emit open_settings: {}", err);
+            }
+        }
+        QUIT_ID => app.exit(0),
+        _ => {
+            if let Some(path) = id.strip_prefix(RECENT_ID_PREFIX) {
+                show_main_window(app);
+                handle_open_path(app, PathBuf::from(path));
+            }
+        }
+    }
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        if let Err(err) = window.show().and_then(|()| window.set_focus()) {
+            log::warn!("Failed to show main window: {}", err);
+        }
+    }
+}
+
+async fn animate_icon(app: AppHandle) {
+    let (Some(tray), Some(icon)) = (app.tray_by_id(TRAY_ID), app.default_window_icon().cloned())
+    else {
+        return;
+    };
+    let dimmed = dimmed_icon(&icon);
+
+    let mut busy_frame = false;
+    while app.state::<TrayStatus>().processing.load(Ordering::SeqCst) > 0 {
+        busy_frame = !busy_frame;
+        let frame = if busy_frame { &dimmed } else { &icon };
+        if let Err(err) = tray.set_icon(Some(frame.clone())) {
+            log::warn!("Failed to update tray icon: {}", err);
+            return;
+        }
+        tokio::time::sleep(BUSY_FRAME_INTERVAL).await;
+    }
+
+    if let Err(err) = tray.set_icon(Some(icon)) {
+        log::warn!("Failed to restore tray icon: {}", err);
+    }
+}
+
+fn dimmed_icon(icon: &Image<'_>) -> Image<'static> {
+    let mut rgba = icon.rgba().to_vec();
+    for pixel in rgba.chunks_exact_mut(4) {
+        pixel[3] /= BUSY_ALPHA_DIVISOR;
+    }
+    Image::new_owned(rgba, icon.width(), icon.height())
+}
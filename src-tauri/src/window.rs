@@ -0,0 +1,113 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Manager, PhysicalPosition, PhysicalSize, WebviewWindow, Window, WindowEvent,
+};
+
+use crate::document_processor::error::Error;
+
+const WINDOW_STATE_FILE_NAME: &str = "window_state.json";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct WindowState {
+    width: u32,
+    height: u32,
+    x: i32,
+    y: i32,
+}
+
+#[tauri::command]
+pub fn toggle_fullscreen(app: AppHandle) -> Result<bool, Error> {
+    let window = main_window(&app)?;
+    let fullscreen = !window
+        .is_fullscreen()
+        .context("Failed to read fullscreen state")?;
+    window
+        .set_fullscreen(fullscreen)
+        .context("Failed to change fullscreen state")?;
+    Ok(fullscreen)
+}
+
+#[tauri::command]
+pub fn is_fullscreen(app: AppHandle) -> Result<bool, Error> {
+    let fullscreen = main_window(&app)?
+        .is_fullscreen()
+        .context("Failed to read fullscreen state")?;
+    Ok(fullscreen)
+}
+
+fn main_window(app: &AppHandle) -> Result<WebviewWindow> {
+    app.get_webview_window("main")
+        .context("Main window is not available")
+}
+
+#[tauri::command]
+pub fn save_window_state(app: AppHandle) -> Result<(), Error> {
+    let window = main_window(&app)?;
+    // A minimised window reports an off-screen position on some platforms.
+    if window.is_minimized().unwrap_or(false) {
+        return Ok(());
+    }
+
+    let size = window.outer_size().context("Failed to read window size")?;
+    let position = window
+        .outer_position()
+        .context("Failed to read window position")?;
+    let state = WindowState {
+        width: size.width,
+        height: size.height,
+        x: position.x,
+        y: position.y,
+    };
+
+    let file = window_state_file(&app)?;
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).context("Failed to create app data directory")?;
+    }
+    let contents =
+        serde_json::to_string_pretty(&state).context("Failed to serialize window state")?;
+    fs::write(&file, contents).context("Failed to write window state")?;
+    Ok(())
+}
+
+pub fn restore_window_state(app: &AppHandle) -> Result<()> {
+    let file = window_state_file(app)?;
+    if !file.exists() {
+        return Ok(());
+    }
+
+    let contents = fs::read_to_string(&file).context("Failed to read window state")?;
+    let state: WindowState =
+        serde_json::from_str(&contents).context("Failed to parse window state")?;
+    if state.width == 0 || state.height == 0 {
+        return Ok(());
+    }
+
+    let window = main_window(app)?;
+    window
+        .set_size(PhysicalSize::new(state.width, state.height))
+        .context("Failed to restore window size")?;
+    window
+        .set_position(PhysicalPosition::new(state.x, state.y))
+        .context("Failed to restore window position")?;
+    Ok(())
+}
+
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    if !matches!(event, WindowEvent::CloseRequested { .. }) || window.label() != "main" {
+        return;
+    }
+    if let Err(err) = save_window_state(window.app_handle().clone()) {
+        log::warn!("Failed to save window state: {}", err);
+    }
+}
+
+fn window_state_file(app: &AppHandle) -> Result<PathBuf> {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .context("Failed to resolve app data directory")?;
+    Ok(app_data_dir.join(WINDOW_STATE_FILE_NAME))
+}
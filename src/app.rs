@@ -1,13 +1,25 @@
 use leptos::*;
 
 mod components;
-use components::hero::*;
+mod i18n;
+mod session;
+mod shortcuts;
+mod tauri;
+use components::document_tabs::*;
+use components::shortcut_reference::*;
+use components::toast::*;
+use i18n::provide_i18n;
 
 #[component]
 pub fn App() -> impl IntoView {
+    provide_toasts();
+    provide_i18n();
+
     view! {
         <main class="container mx-auto">
-            <Hero/>
+            <DocumentTabs/>
+            <ToastNotification/>
+            <ShortcutReference/>
         </main>
     }
 }
@@ -1 +1,32 @@
-pub mod hero;
\ No newline at end of file
+pub mod adjustment_toolbar;
+pub mod annotation_layer;
+pub mod batch_dialog;
+pub mod cache_manager;
+pub mod command_palette;
+pub mod context_menu;
+pub mod continuous_view;
+pub mod document_info;
+pub mod document_tabs;
+pub mod export_dialog;
+pub mod folder_panel;
+pub mod form_layer;
+pub mod health_banner;
+pub mod hero;
+pub mod image_export_dialog;
+pub mod link_layer;
+pub mod merge_dialog;
+pub mod minimap;
+pub mod ocr_panel;
+pub mod outline_panel;
+pub mod password_dialog;
+pub mod recent_documents;
+pub mod redaction_layer;
+pub mod render_settings;
+pub mod search_panel;
+pub mod settings_dialog;
+pub mod shortcut_reference;
+pub mod split_dialog;
+pub mod theme_toggle;
+pub mod thumbnail_strip;
+pub mod toast;
+pub mod url_dialog;
@@ -0,0 +1,86 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use crate::app::i18n::t;
+use crate::app::tauri::{invoke, log_error};
+
+const MIN_ADJUSTMENT: i8 = -100;
+const MAX_ADJUSTMENT: i8 = 100;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default)]
+struct AdjustmentOptions {
+    brightness: i8,
+    contrast: i8,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RerenderPageArgs {
+    path: String,
+    session_id: String,
+    page: u32,
+    options: AdjustmentOptions,
+}
+
+#[component]
+pub fn AdjustmentToolbar(
+    path: String,
+    session_id: String,
+    #[prop(into)] page: Signal<u32>,
+) -> impl IntoView {
+    let brightness = create_rw_signal(0i8);
+    let contrast = create_rw_signal(0i8);
+
+    let rerender = create_action(move |options: &AdjustmentOptions| {
+        let args = RerenderPageArgs {
+            path: path.clone(),
+            session_id: session_id.clone(),
+            page: page.get_untracked(),
+            options: *options,
+        };
+        async move {
+            if let Ok(args) = to_value(&args) {
+                if let Err(err) = invoke::<()>("rerender_page", &args).await {
+                    log_error(err.to_string());
+                }
+            }
+        }
+    });
+
+    let apply = move || {
+        rerender.dispatch(AdjustmentOptions {
+            brightness: brightness.get_untracked(),
+            contrast: contrast.get_untracked(),
+        })
+    };
+
+    let slider = move |label: &'static str, value: RwSignal<i8>| {
+        view! {
+            <label class="flex items-center gap-2 text-sm">
+                <span class="w-20">{move || t!(label)}</span>
+                <input
+                    type="range"
+                    class="range range-xs w-32"
+                    min=MIN_ADJUSTMENT
+                    max=MAX_ADJUSTMENT
+                    prop:value=move || value().to_string()
+                    on:input=move |ev| {
+                        if let Ok(parsed) = event_target_value(&ev).parse::<i8>() {
+                            value.set(parsed);
+                        }
+                    }
+                    on:change=move |_| apply()
+                />
+                <span class="w-8 text-right">{value}</span>
+            </label>
+        }
+    };
+
+    view! {
+        <div class="flex items-center gap-4 px-3 py-1 bg-base-100 rounded-box shadow">
+            {slider("adjustments.brightness", brightness)}
+            {slider("adjustments.contrast", contrast)}
+        </div>
+    }
+}
@@ -0,0 +1,114 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use crate::app::tauri::invoke;
+
+#[derive(Serialize, Deserialize)]
+struct PageArgs {
+    path: String,
+    page: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+enum AnnotationKind {
+    Highlight,
+    Rectangle,
+    Text,
+    FreeHand,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct AnnotationData {
+    id: String,
+    kind: AnnotationKind,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    color: String,
+    text: Option<String>,
+}
+
+#[component]
+pub fn AnnotationLayer(path: String, #[prop(into)] page: Signal<u32>) -> impl IntoView {
+    let annotations = create_resource(page, move |page| {
+        let path = path.clone();
+        async move {
+            let Ok(args) = to_value(&PageArgs { path, page }) else {
+                return Vec::new();
+            };
+            invoke::<Vec<AnnotationData>>("get_annotations", &args)
+                .await
+                .unwrap_or_default()
+        }
+    });
+
+    view! {
+        <svg
+            class="absolute inset-0 w-full h-full pointer-events-none"
+            viewBox="0 0 1 1"
+            preserveAspectRatio="none"
+        >
+            <For
+                each=move || annotations().unwrap_or_default()
+                key=|annotation| annotation.id.clone()
+                children=annotation_shape
+            />
+        </svg>
+    }
+}
+
+fn annotation_shape(annotation: AnnotationData) -> impl IntoView {
+    let AnnotationData {
+        kind,
+        x,
+        y,
+        width,
+        height,
+        color,
+        text,
+        ..
+    } = annotation;
+
+    match kind {
+        AnnotationKind::Highlight => view! {
+            <rect x=x y=y width=width height=height fill=color fill-opacity="0.35"></rect>
+        }
+        .into_view(),
+        AnnotationKind::Rectangle => view! {
+            <rect
+                x=x
+                y=y
+                width=width
+                height=height
+                fill="none"
+                stroke=color
+                stroke-width="2"
+                vector-effect="non-scaling-stroke"
+            ></rect>
+        }
+        .into_view(),
+        AnnotationKind::FreeHand => view! {
+            <ellipse
+                cx=x + width / 2.0
+                cy=y + height / 2.0
+                rx=width / 2.0
+                ry=height / 2.0
+                fill="none"
+                stroke=color
+                stroke-width="2"
+                vector-effect="non-scaling-stroke"
+            ></ellipse>
+        }
+        .into_view(),
+        AnnotationKind::Text => view! {
+            <foreignObject x=x y=y width=width height=height>
+                <div style=format!("color: {}; font-size: 12px;", color)>
+                    {text.unwrap_or_default()}
+                </div>
+            </foreignObject>
+        }
+        .into_view(),
+    }
+}
@@ -0,0 +1,111 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::JsValue;
+
+use crate::app::i18n::t;
+use crate::app::tauri::{invoke, listen, EventListener};
+
+#[derive(Serialize, Deserialize, Default)]
+struct ProcessingOptions {}
+
+#[derive(Serialize, Deserialize)]
+struct ProcessDirectoryArgs {
+    dir: String,
+    options: ProcessingOptions,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct BatchProgress {
+    completed: usize,
+    total: usize,
+    current_path: String,
+}
+
+#[component]
+pub fn BatchDialog(#[prop(into)] on_close: Callback<()>) -> impl IntoView {
+    let progress = create_rw_signal(None::<BatchProgress>);
+    let failed = create_rw_signal(None::<Vec<String>>);
+
+    let listener = store_value(None::<EventListener>);
+    create_effect(move |_| {
+        on_cleanup(move || listener.set_value(None));
+        spawn_local(async move {
+            let callback = listen("batch_progress", move |update: BatchProgress| {
+                progress.set(Some(update));
+            })
+            .await
+            .unwrap();
+            listener.set_value(Some(callback));
+        });
+    });
+
+    let process = create_action(move |_: &()| async move {
+        let Ok(dir) = invoke::<String>("select_directory", &JsValue::default()).await else {
+            on_close.call(());
+            return;
+        };
+        let args = ProcessDirectoryArgs {
+            dir,
+            options: ProcessingOptions::default(),
+        };
+        let Ok(args) = to_value(&args) else {
+            return;
+        };
+        let result = invoke::<Vec<String>>("process_directory", &args).await;
+        failed.set(Some(result.unwrap_or_default()));
+    });
+    process.dispatch(());
+
+    view! {
+        <dialog class="modal modal-open">
+            <div class="modal-box text-left">
+                <h3 class="font-bold text-lg">{move || t!("home.process_folder")}</h3>
+                <div class="py-4">
+                    {move || match progress() {
+                        Some(progress) => {
+                            view! {
+                                <progress
+                                    class="progress progress-primary w-full"
+                                    value=progress.completed
+                                    max=progress.total
+                                ></progress>
+                                <p class="text-sm truncate" title=progress.current_path.clone()>
+                                    {t!(
+                                        "batch.progress",
+                                        progress.completed,
+                                        progress.total,
+                                        progress.current_path,
+                                    )}
+                                </p>
+                            }
+                                .into_view()
+                        }
+                        None => {
+                            view! { <progress class="progress w-full"></progress> }.into_view()
+                        }
+                    }}
+                </div>
+                <Show when=move || failed.with(|failed| failed.as_ref().is_some_and(|failed| !failed.is_empty()))>
+                    <p class="text-error">{move || t!("batch.failed")}</p>
+                    <ul class="text-sm list-disc pl-4">
+                        <For
+                            each=move || failed().unwrap_or_default()
+                            key=|path| path.clone()
+                            children=|path| view! { <li>{path}</li> }
+                        />
+                    </ul>
+                </Show>
+                <div class="modal-action">
+                    <button
+                        class="btn"
+                        disabled=move || process.pending()()
+                        on:click=move |_| on_close.call(())
+                    >
+                        {move || t!("common.close")}
+                    </button>
+                </div>
+            </div>
+        </dialog>
+    }
+}
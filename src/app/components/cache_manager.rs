@@ -0,0 +1,168 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::JsValue;
+
+use super::document_info::{format_bytes, format_timestamp, CacheInfo};
+use crate::app::i18n::t;
+use crate::app::tauri::{invoke, log_error};
+
+#[derive(Serialize, Deserialize)]
+struct DocumentPath {
+    path: String,
+}
+
+#[component]
+pub fn CacheManager(
+    #[prop(into)] on_cleared: Callback<Option<String>>,
+    #[prop(into)] on_close: Callback<()>,
+) -> impl IntoView {
+    let caches = create_resource(
+        || (),
+        |_| async move {
+            invoke::<Vec<CacheInfo>>("get_all_caches", &JsValue::default())
+                .await
+                .unwrap_or_default()
+        },
+    );
+    let confirming = create_rw_signal(false);
+
+    let total_bytes = move || {
+        caches
+            .get()
+            .map(|caches| caches.iter().map(|cache| cache.total_bytes).sum::<u64>())
+            .unwrap_or_default()
+    };
+
+    let clear_cache = create_action(move |path: &String| {
+        let path = path.clone();
+        async move {
+            let Ok(args) = to_value(&DocumentPath { path: path.clone() }) else {
+                return;
+            };
+            match invoke::<()>("clear_cache", &args).await {
+                Ok(()) => {
+                    on_cleared.call(Some(path));
+                    caches.refetch();
+                }
+                Err(err) => log_error(err.to_string()),
+            }
+        }
+    });
+
+    let clear_all = create_action(move |_: &()| async move {
+        confirming.set(false);
+        match invoke::<usize>("clear_all_caches", &JsValue::default()).await {
+            Ok(_) => {
+                on_cleared.call(None);
+                caches.refetch();
+            }
+            Err(err) => log_error(err.to_string()),
+        }
+    });
+
+    view! {
+        <dialog class="modal modal-open">
+            <div class="modal-box max-w-2xl text-left">
+                <h3 class="font-bold text-lg">{move || t!("palette.cache")}</h3>
+                <p class="py-2">{move || t!("cache.used_space", format_bytes(total_bytes()))}</p>
+                <Suspense fallback=|| view! { <span class="loading loading-spinner"></span> }>
+                    {move || {
+                        caches
+                            .get()
+                            .map(|caches| {
+                                if caches.is_empty() {
+                                    return view! { <p class="text-sm">{t!("cache.empty")}</p> }
+                                        .into_view();
+                                }
+                                view! {
+                                    <table class="table table-sm">
+                                        <thead>
+                                            <tr>
+                                                <th>{t!("cache.document")}</th>
+                                                <th>{t!("info.pages")}</th>
+                                                <th>{t!("info.size")}</th>
+                                                <th>{t!("info.last_rendered")}</th>
+                                                <th></th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {caches
+                                                .into_iter()
+                                                .map(|cache| {
+                                                    let name = cache
+                                                        .document_path
+                                                        .as_deref()
+                                                        .map(|path| {
+                                                            path.rsplit(['/', '\\']).next().unwrap_or(path).to_string()
+                                                        })
+                                                        .unwrap_or_else(|| t!("cache.unknown_document"));
+                                                    let title = cache.document_path.clone().unwrap_or(cache.data_dir);
+                                                    let document_path = cache.document_path;
+                                                    let clearable = document_path.is_some();
+                                                    view! {
+                                                        <tr>
+                                                            <td class="truncate max-w-xs" title=title>
+                                                                {name}
+                                                            </td>
+                                                            <td>{cache.page_count}</td>
+                                                            <td>{format_bytes(cache.total_bytes)}</td>
+                                                            <td>
+                                                                {cache
+                                                                    .last_rendered
+                                                                    .map(format_timestamp)
+                                                                    .unwrap_or_else(|| "—".to_string())}
+                                                            </td>
+                                                            <td>
+                                                                <button
+                                                                    class="btn btn-ghost btn-xs"
+                                                                    disabled=!clearable
+                                                                    on:click=move |_| {
+                                                                        if let Some(path) = document_path.clone() {
+                                                                            clear_cache.dispatch(path);
+                                                                        }
+                                                                    }
+                                                                >
+                                                                    {move || t!("common.clear")}
+                                                                </button>
+                                                            </td>
+                                                        </tr>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </tbody>
+                                    </table>
+                                }
+                                    .into_view()
+                            })
+                    }}
+                </Suspense>
+                <Show when=confirming>
+                    <div role="alert" class="alert alert-warning mt-4">
+                        <span>{move || t!("cache.clear_all_confirm")}</span>
+                        <div class="flex gap-2">
+                            <button class="btn btn-sm" on:click=move |_| confirming.set(false)>
+                                {move || t!("common.cancel")}
+                            </button>
+                            <button class="btn btn-sm btn-error" on:click=move |_| clear_all.dispatch(())>
+                                {move || t!("cache.clear_all")}
+                            </button>
+                        </div>
+                    </div>
+                </Show>
+                <div class="modal-action">
+                    <button class="btn" on:click=move |_| on_close.call(())>
+                        {move || t!("common.close")}
+                    </button>
+                    <button
+                        class="btn btn-error"
+                        disabled=move || confirming() || clear_all.pending()()
+                        on:click=move |_| confirming.set(true)
+                    >
+                        {move || t!("cache.clear_all")}
+                    </button>
+                </div>
+            </div>
+        </dialog>
+    }
+}
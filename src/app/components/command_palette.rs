@@ -0,0 +1,189 @@
+use leptos::*;
+
+use crate::app::i18n::t;
+use crate::app::shortcuts::{shortcut_action, ShortcutAction};
+
+#[derive(Clone)]
+pub struct Command {
+    pub label: String,
+    pub keywords: Vec<String>,
+    pub action: Callback<()>,
+}
+
+impl Command {
+    pub fn new(label: String, keywords: &[&str], action: impl Into<Callback<()>>) -> Self {
+        Self {
+            label,
+            keywords: keywords.iter().map(|keyword| keyword.to_string()).collect(),
+            action: action.into(),
+        }
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        self.label.to_lowercase().contains(query)
+            || self
+                .keywords
+                .iter()
+                .any(|keyword| keyword.to_lowercase().contains(query))
+    }
+}
+
+// Commands of the open document's viewer, registered while its `Hero` is mounted.
+#[derive(Clone, Copy)]
+pub struct ViewerCommands(RwSignal<Option<Signal<Vec<Command>>>>);
+
+impl ViewerCommands {
+    pub fn register(&self, commands: Signal<Vec<Command>>) {
+        let registered = self.0;
+        registered.set(Some(commands));
+        on_cleanup(move || registered.set(None));
+    }
+}
+
+pub fn provide_viewer_commands() -> ViewerCommands {
+    let commands = ViewerCommands(create_rw_signal(None));
+    provide_context(commands);
+    commands
+}
+
+pub fn use_viewer_commands() -> Option<ViewerCommands> {
+    use_context::<ViewerCommands>()
+}
+
+#[component]
+pub fn CommandPalette(
+    #[prop(into)] commands: Signal<Vec<Command>>,
+    viewer_commands: ViewerCommands,
+) -> impl IntoView {
+    let open = create_rw_signal(false);
+    let query = create_rw_signal(String::new());
+    let selected = create_rw_signal(0usize);
+    let input = create_node_ref::<html::Input>();
+
+    let filtered = Signal::derive(move || {
+        let query = query().trim().to_lowercase();
+        let mut all = commands();
+        if let Some(viewer) = viewer_commands.0.get() {
+            all.extend(viewer.get());
+        }
+        all.into_iter()
+            .filter(|command| query.is_empty() || command.matches(&query))
+            .collect::<Vec<_>>()
+    });
+    create_effect(move |_| {
+        query.track();
+        selected.set(0);
+    });
+
+    let close = move || {
+        open.set(false);
+        query.set(String::new());
+    };
+    let run = move |command: Command| {
+        close();
+        command.action.call(());
+    };
+
+    let toggle_palette = window_event_listener(ev::keydown, move |ev| {
+        if shortcut_action(&ev) != Some(ShortcutAction::CommandPalette) {
+            return;
+        }
+        ev.prevent_default();
+        if open.get_untracked() {
+            close();
+        } else {
+            open.set(true);
+        }
+    });
+    on_cleanup(move || toggle_palette.remove());
+    create_effect(move |_| {
+        if let (true, Some(input)) = (open(), input()) {
+            let _ = input.focus();
+        }
+    });
+
+    let navigate = move |ev: ev::KeyboardEvent| {
+        let count = filtered.with_untracked(Vec::len);
+        match ev.key().as_str() {
+            "ArrowDown" if count > 0 => selected.update(|index| *index = (*index + 1) % count),
+            "ArrowUp" if count > 0 => {
+                selected.update(|index| *index = (*index + count - 1) % count)
+            }
+            "Enter" => {
+                let command = filtered
+                    .with_untracked(|commands| commands.get(selected.get_untracked()).cloned());
+                if let Some(command) = command {
+                    run(command);
+                }
+            }
+            "Escape" => close(),
+            _ => return,
+        }
+        ev.prevent_default();
+    };
+
+    view! {
+        <Show when=open>
+            <div
+                class="fixed inset-0 z-[100] flex items-start justify-center pt-[15vh] bg-base-300/60"
+                on:click=move |_| close()
+            >
+                <div
+                    class="bg-base-100 rounded-box shadow-xl w-full max-w-lg"
+                    role="dialog"
+                    aria-modal="true"
+                    aria-label=move || t!("palette.title")
+                    on:click=|ev| ev.stop_propagation()
+                >
+                    <input
+                        node_ref=input
+                        type="text"
+                        class="input input-bordered w-full rounded-b-none"
+                        role="combobox"
+                        aria-expanded="true"
+                        aria-controls="command-palette-list"
+                        placeholder=move || t!("palette.placeholder")
+                        prop:value=query
+                        on:input=move |ev| query.set(event_target_value(&ev))
+                        on:keydown=navigate
+                    />
+                    <ul
+                        id="command-palette-list"
+                        role="listbox"
+                        class="menu max-h-80 overflow-y-auto flex-nowrap"
+                    >
+                        {move || {
+                            let commands = filtered();
+                            if commands.is_empty() {
+                                return view! {
+                                    <li class="p-2 text-sm opacity-70">
+                                        {move || t!("palette.empty")}
+                                    </li>
+                                }
+                                    .into_view();
+                            }
+                            commands
+                                .into_iter()
+                                .enumerate()
+                                .map(|(index, command)| {
+                                    let label = command.label.clone();
+                                    view! {
+                                        <li role="option" aria-selected=move || (selected() == index).to_string()>
+                                            <a
+                                                class=("active", move || selected() == index)
+                                                on:mouseenter=move |_| selected.set(index)
+                                                on:click=move |_| run(command.clone())
+                                            >
+                                                {label}
+                                            </a>
+                                        </li>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </ul>
+                </div>
+            </div>
+        </Show>
+    }
+}
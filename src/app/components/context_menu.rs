@@ -0,0 +1,72 @@
+use leptos::*;
+
+#[derive(Clone)]
+pub struct ContextMenuItem {
+    pub label: Signal<String>,
+    pub action: Callback<()>,
+}
+
+impl ContextMenuItem {
+    pub fn new(label: impl Into<Signal<String>>, action: impl Into<Callback<()>>) -> Self {
+        Self {
+            label: label.into(),
+            action: action.into(),
+        }
+    }
+}
+
+// The host opens the menu by setting `position` to the cursor's client coordinates.
+#[component]
+pub fn ContextMenu(
+    position: RwSignal<Option<(i32, i32)>>,
+    items: Vec<ContextMenuItem>,
+    #[prop(into)] label: Signal<String>,
+) -> impl IntoView {
+    let dismiss_on_click = window_event_listener(ev::click, move |_| {
+        if position.with_untracked(Option::is_some) {
+            position.set(None);
+        }
+    });
+    on_cleanup(move || dismiss_on_click.remove());
+    let dismiss_on_escape = window_event_listener(ev::keydown, move |ev| {
+        if ev.key() == "Escape" && position.with_untracked(Option::is_some) {
+            ev.prevent_default();
+            position.set(None);
+        }
+    });
+    on_cleanup(move || dismiss_on_escape.remove());
+
+    move || {
+        position().map(|(x, y)| {
+            view! {
+                <ul
+                    role="menu"
+                    aria-label=label
+                    class="menu bg-base-100 rounded-box shadow fixed z-50"
+                    style=format!("left: {}px; top: {}px;", x, y)
+                >
+                    {items
+                        .iter()
+                        .cloned()
+                        .map(|item| {
+                            view! {
+                                <li role="none">
+                                    <a
+                                        role="menuitem"
+                                        on:click=move |ev| {
+                                            ev.stop_propagation();
+                                            position.set(None);
+                                            item.action.call(());
+                                        }
+                                    >
+                                        {item.label}
+                                    </a>
+                                </li>
+                            }
+                        })
+                        .collect_view()}
+                </ul>
+            }
+        })
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::BTreeSet;
+
+use js_sys::Array;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{IntersectionObserver, IntersectionObserverEntry, IntersectionObserverInit};
+
+use super::minimap::Minimap;
+use crate::app::i18n::t;
+use crate::app::session::ImageUrl;
+use crate::app::tauri::invoke;
+
+const DEFAULT_PAGE_ASPECT: f64 = 1.414;
+const MINIMAP_MIN_PAGES: u32 = 10;
+
+#[derive(Serialize, Deserialize)]
+struct DocumentPath {
+    path: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct PageDimensions {
+    page: u32,
+    width_pt: f32,
+    height_pt: f32,
+}
+
+pub fn scroll_to_page(page: u32) {
+    if let Some(element) = document().get_element_by_id(&page_element_id(page)) {
+        element.scroll_into_view();
+    }
+}
+
+fn page_element_id(page: u32) -> String {
+    format!("scroll-page-{}", page)
+}
+
+#[component]
+pub fn ContinuousView(
+    path: String,
+    images: ReadSignal<Vec<ImageUrl>>,
+    #[prop(into)] total_pages: Signal<u32>,
+    page_number: WriteSignal<u32>,
+    #[prop(into)] zoom: Signal<f64>,
+) -> impl IntoView {
+    let container = create_node_ref::<html::Div>();
+    let page_aspect = create_rw_signal(DEFAULT_PAGE_ASPECT);
+    let page_width = move || 1000.0 * zoom();
+    let dimensions = create_resource(
+        move || path.clone(),
+        |path| async move {
+            let Ok(args) = to_value(&DocumentPath { path }) else {
+                return Vec::new();
+            };
+            invoke::<Vec<PageDimensions>>("get_page_dimensions", &args)
+                .await
+                .unwrap_or_default()
+        },
+    );
+    let aspect_of = move |page: u32| {
+        dimensions
+            .with(|dimensions| {
+                dimensions
+                    .as_ref()?
+                    .iter()
+                    .find(|dimensions| dimensions.page == page)
+                    .filter(|dimensions| dimensions.width_pt > 0.0)
+                    .map(|dimensions| dimensions.height_pt as f64 / dimensions.width_pt as f64)
+            })
+            .unwrap_or_else(|| page_aspect())
+    };
+
+    let on_intersect = Closure::<dyn FnMut(Array)>::new(move |entries: Array| {
+        for entry in entries.iter() {
+            let entry: IntersectionObserverEntry = entry.unchecked_into();
+            if !entry.is_intersecting() {
+                continue;
+            }
+            if let Some(page) = entry
+                .target()
+                .get_attribute("data-page")
+                .and_then(|page| page.parse::<u32>().ok())
+            {
+                page_number.set(page);
+            }
+        }
+    });
+    let observer = store_value(None::<IntersectionObserver>);
+
+    let visible = create_rw_signal(BTreeSet::<u32>::new());
+    let on_visibility = Closure::<dyn FnMut(Array)>::new(move |entries: Array| {
+        visible.update(|visible| {
+            for entry in entries.iter() {
+                let entry: IntersectionObserverEntry = entry.unchecked_into();
+                let Some(page) = entry
+                    .target()
+                    .get_attribute("data-page")
+                    .and_then(|page| page.parse::<u32>().ok())
+                else {
+                    continue;
+                };
+                if entry.is_intersecting() {
+                    visible.insert(page);
+                } else {
+                    visible.remove(&page);
+                }
+            }
+        });
+    });
+    let visibility_observer = store_value(None::<IntersectionObserver>);
+    let visible_pages = Signal::derive(move || {
+        visible.with(|visible| match (visible.first(), visible.last()) {
+            (Some(first), Some(last)) => (*first, *last),
+            _ => (0, 0),
+        })
+    });
+    let aspects = Signal::derive(move || (1..=total_pages()).map(aspect_of).collect::<Vec<_>>());
+
+    create_effect(move |_| {
+        let pages = total_pages();
+        let Some(container) = container() else {
+            return;
+        };
+        if observer.with_value(Option::is_none) {
+            let options = IntersectionObserverInit::new();
+            let root: &web_sys::Element = &container;
+            options.set_root(Some(root));
+            options.set_root_margin("-50% 0px -50% 0px");
+            let created = IntersectionObserver::new_with_options(
+                on_intersect.as_ref().unchecked_ref(),
+                &options,
+            );
+            observer.set_value(created.ok());
+        }
+        if visibility_observer.with_value(Option::is_none) {
+            let options = IntersectionObserverInit::new();
+            let root: &web_sys::Element = &container;
+            options.set_root(Some(root));
+            let created = IntersectionObserver::new_with_options(
+                on_visibility.as_ref().unchecked_ref(),
+                &options,
+            );
+            visibility_observer.set_value(created.ok());
+        }
+        request_animation_frame(move || {
+            for page in 1..=pages {
+                let Some(element) = document().get_element_by_id(&page_element_id(page)) else {
+                    continue;
+                };
+                for observer in [observer, visibility_observer] {
+                    observer.with_value(|observer| {
+                        if let Some(observer) = observer {
+                            observer.observe(&element);
+                        }
+                    });
+                }
+            }
+        });
+    });
+
+    on_cleanup(move || {
+        for observer in [observer, visibility_observer] {
+            observer.with_value(|observer| {
+                if let Some(observer) = observer {
+                    observer.disconnect();
+                }
+            });
+        }
+    });
+
+    let scroll_to_fraction = move |fraction: f64| {
+        if let Some(container) = container.get_untracked() {
+            container.set_scroll_top((fraction * container.scroll_height() as f64) as i32);
+        }
+    };
+
+    view! {
+        <div class="relative">
+            <div node_ref=container class="h-[80vh] overflow-y-auto flex flex-col items-center gap-4">
+                <For
+                    each=move || 1..=total_pages()
+                    key=|page| *page
+                    children=move |page| {
+                        let url = move || {
+                            images
+                                .with(|urls| {
+                                    urls.iter()
+                                        .find(|url| url.page_number == page)
+                                        .map(|url| url.url.clone())
+                                })
+                        };
+                        view! {
+                            <div
+                                id=page_element_id(page)
+                                data-page=page
+                                style=move || format!("width: {}px;", page_width())
+                            >
+                                {move || match url() {
+                                    Some(url) => {
+                                        view! {
+                                            <img
+                                                src=url
+                                                alt=t!("hero.page", page)
+                                                class="w-full h-auto"
+                                                on:load=move |ev| {
+                                                    let image = event_target::<web_sys::HtmlImageElement>(&ev);
+                                                    if image.natural_width() > 0 {
+                                                        page_aspect
+                                                            .set(
+                                                                image.natural_height() as f64
+                                                                    / image.natural_width() as f64,
+                                                            );
+                                                    }
+                                                }
+                                            />
+                                        }
+                                            .into_view()
+                                    }
+                                    None => {
+                                        view! {
+                                            <div
+                                                class="skeleton w-full"
+                                                style=move || {
+                                                    format!("height: {}px;", page_width() * aspect_of(page))
+                                                }
+                                            ></div>
+                                        }
+                                            .into_view()
+                                    }
+                                }}
+                            </div>
+                        }
+                    }
+                />
+            </div>
+            <Show when=move || total_pages() > MINIMAP_MIN_PAGES>
+                <Minimap
+                    images=images
+                    aspects=aspects
+                    visible_pages=visible_pages
+                    on_select=scroll_to_page
+                    on_scroll=scroll_to_fraction
+                />
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,412 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::JsValue;
+
+use crate::app::i18n::t;
+use crate::app::tauri::invoke;
+
+#[derive(Serialize, Deserialize)]
+struct DocumentPath {
+    path: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct DocumentInfo {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub subject: Option<String>,
+    pub creator: Option<String>,
+    pub producer: Option<String>,
+    pub creation_date: Option<String>,
+    pub modification_date: Option<String>,
+    pub page_count: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct FontInfo {
+    name: String,
+    subtype: String,
+    encoding: Option<String>,
+    is_embedded: bool,
+    pages: Vec<u16>,
+}
+
+impl FontInfo {
+    // Subset fonts are named with a six-letter tag, e.g. "ABCDEF+Helvetica".
+    fn is_subset(&self) -> bool {
+        self.name
+            .split_once('+')
+            .is_some_and(|(tag, _)| tag.len() == 6 && tag.chars().all(|c| c.is_ascii_uppercase()))
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct DuplicatePageGroup {
+    pages: Vec<u16>,
+    perceptual_hash: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+pub struct PageStatistics {
+    pub word_count: u32,
+    pub char_count: u32,
+    pub estimated_reading_seconds: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct DocumentStatistics {
+    pub total: PageStatistics,
+    pub pages: Vec<(u32, PageStatistics)>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct CacheInfo {
+    pub data_dir: String,
+    pub document_path: Option<String>,
+    pub page_count: usize,
+    pub total_bytes: u64,
+    pub last_rendered: Option<u64>,
+    pub format: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[allow(dead_code)]
+struct CacheVerificationReport {
+    page: u32,
+    expected_hash: String,
+    actual_hash: String,
+    ok: bool,
+}
+
+const BYTE_UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+pub fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < BYTE_UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, BYTE_UNITS[unit])
+    } else {
+        format!("{:.1} {}", value, BYTE_UNITS[unit])
+    }
+}
+
+pub fn format_timestamp(seconds: u64) -> String {
+    let date = js_sys::Date::new(&JsValue::from_f64(seconds as f64 * 1000.0));
+    String::from(date.to_locale_string("pt-BR", &JsValue::UNDEFINED))
+}
+
+pub fn format_reading_time(seconds: u32) -> String {
+    match seconds {
+        0..=59 => format!("{} s", seconds),
+        _ => format!("{} min", seconds.div_ceil(60)),
+    }
+}
+
+#[component]
+pub fn DocumentInfoPanel(
+    #[prop(into)] path: Signal<Option<String>>,
+    #[prop(into)] on_retry: Callback<u32>,
+) -> impl IntoView {
+    let info = create_resource(path, |path| async move {
+        let args = to_value(&DocumentPath { path: path? }).ok()?;
+        invoke::<DocumentInfo>("get_document_info", &args)
+            .await
+            .ok()
+    });
+    let processing_log = create_resource(path, |path| async move {
+        let args = to_value(&DocumentPath { path: path? }).ok()?;
+        invoke::<String>("get_document_log", &args)
+            .await
+            .ok()
+            .filter(|log| !log.is_empty())
+    });
+    let fonts = create_resource(path, |path| async move {
+        let args = to_value(&DocumentPath { path: path? }).ok()?;
+        invoke::<Vec<FontInfo>>("get_embedded_fonts", &args)
+            .await
+            .ok()
+            .filter(|fonts| !fonts.is_empty())
+    });
+    let statistics = create_resource(path, |path| async move {
+        let args = to_value(&DocumentPath { path: path? }).ok()?;
+        invoke::<DocumentStatistics>("get_document_statistics", &args)
+            .await
+            .ok()
+    });
+    let cache_info = create_resource(path, |path| async move {
+        let args = to_value(&DocumentPath { path: path? }).ok()?;
+        invoke::<CacheInfo>("get_cache_info", &args).await.ok()
+    });
+
+    let verify_cache = create_action(move |_: &()| async move {
+        let args = to_value(&DocumentPath {
+            path: path.get_untracked()?,
+        })
+        .ok()?;
+        invoke::<Vec<CacheVerificationReport>>("verify_cache", &args)
+            .await
+            .ok()
+    });
+    let find_duplicates = create_action(move |_: &()| async move {
+        let args = to_value(&DocumentPath {
+            path: path.get_untracked()?,
+        })
+        .ok()?;
+        invoke::<Vec<DuplicatePageGroup>>("find_duplicate_pages", &args)
+            .await
+            .ok()
+    });
+    let corrupted_pages = move || {
+        verify_cache.value().get().flatten().map(|reports| {
+            reports
+                .into_iter()
+                .filter(|report| !report.ok)
+                .map(|report| report.page)
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let row = |label: String, value: Option<String>| {
+        view! {
+            <tr>
+                <th>{label}</th>
+                <td>{value.unwrap_or_else(|| "—".to_string())}</td>
+            </tr>
+        }
+    };
+
+    view! {
+        <aside class="absolute top-20 right-4 w-80 card bg-base-100 shadow-xl">
+            <div class="card-body">
+                <h2 class="card-title">{move || t!("info.title")}</h2>
+                <Suspense fallback=|| view! { <span class="loading loading-spinner"></span> }>
+                    {move || {
+                        info.get()
+                            .map(|info| match info {
+                                Some(info) => {
+                                    view! {
+                                        <table class="table table-sm">
+                                            <tbody>
+                                                {row(t!("info.document_title"), info.title)}
+                                                {row(t!("info.author"), info.author)}
+                                                {row(t!("info.subject"), info.subject)}
+                                                {row(t!("info.creator"), info.creator)}
+                                                {row(t!("info.producer"), info.producer)}
+                                                {row(t!("info.created"), info.creation_date)}
+                                                {row(t!("info.modified"), info.modification_date)}
+                                                {row(t!("info.pages"), Some(info.page_count.to_string()))}
+                                            </tbody>
+                                        </table>
+                                    }
+                                        .into_view()
+                                }
+                                None => {
+                                    view! { <p>{t!("info.unavailable")}</p> }
+                                        .into_view()
+                                }
+                            })
+                    }}
+
+                </Suspense>
+                <Suspense fallback=|| ()>
+                    {move || {
+                        statistics
+                            .get()
+                            .flatten()
+                            .map(|statistics| {
+                                let total = statistics.total;
+                                view! {
+                                    <table class="table table-sm">
+                                        <tbody>
+                                            {row(t!("info.words"), Some(total.word_count.to_string()))}
+                                            {row(t!("info.characters"), Some(total.char_count.to_string()))}
+                                            {row(
+                                                t!("info.reading_time"),
+                                                Some(format_reading_time(total.estimated_reading_seconds)),
+                                            )}
+                                        </tbody>
+                                    </table>
+                                }
+                            })
+                    }}
+
+                </Suspense>
+                <Suspense fallback=|| ()>
+                    {move || {
+                        fonts
+                            .get()
+                            .flatten()
+                            .map(|fonts| {
+                                view! {
+                                    <details class="collapse collapse-arrow bg-base-200">
+                                        <summary class="collapse-title text-sm font-medium">
+                                            {t!("info.fonts", fonts.len())}
+                                        </summary>
+                                        <ul class="collapse-content flex flex-col gap-2 max-h-64 overflow-auto text-xs">
+                                            {fonts
+                                                .into_iter()
+                                                .map(|font| {
+                                                    let status = if !font.is_embedded {
+                                                        ("badge badge-warning badge-xs", t!("info.font_not_embedded"))
+                                                    } else if font.is_subset() {
+                                                        ("badge badge-ghost badge-xs", t!("info.font_subset"))
+                                                    } else {
+                                                        ("badge badge-success badge-xs", t!("info.font_embedded"))
+                                                    };
+                                                    let pages = font
+                                                        .pages
+                                                        .iter()
+                                                        .map(u16::to_string)
+                                                        .collect::<Vec<_>>()
+                                                        .join(", ");
+                                                    view! {
+                                                        <li>
+                                                            <div class="flex items-center gap-2">
+                                                                <span class="font-semibold break-all flex-1">{font.name}</span>
+                                                                <span class=status.0>{status.1}</span>
+                                                            </div>
+                                                            <div class="opacity-70">
+                                                                {font.subtype} " · "
+                                                                {font.encoding.unwrap_or_else(|| "—".to_string())}
+                                                            </div>
+                                                            <div class="opacity-70">{t!("info.font_pages", pages)}</div>
+                                                        </li>
+                                                    }
+                                                })
+                                                .collect_view()}
+                                        </ul>
+                                    </details>
+                                }
+                            })
+                    }}
+                </Suspense>
+                <Suspense fallback=|| ()>
+                    {move || {
+                        cache_info
+                            .get()
+                            .flatten()
+                            .map(|cache| {
+                                view! {
+                                    <h3 class="font-semibold">{t!("info.cache")}</h3>
+                                    <table class="table table-sm">
+                                        <tbody>
+                                            {row(t!("info.pages"), Some(cache.page_count.to_string()))}
+                                            {row(t!("info.format"), Some(cache.format.to_uppercase()))}
+                                            {row(t!("info.size"), Some(format_bytes(cache.total_bytes)))}
+                                            {row(t!("info.last_rendered"), cache.last_rendered.map(format_timestamp))}
+                                        </tbody>
+                                    </table>
+                                    <button
+                                        class="btn btn-sm"
+                                        disabled=move || verify_cache.pending()()
+                                        on:click=move |_| verify_cache.dispatch(())
+                                    >
+                                        {move || t!("info.verify_cache")}
+                                    </button>
+                                    <button
+                                        class="btn btn-sm"
+                                        disabled=move || find_duplicates.pending()()
+                                        on:click=move |_| find_duplicates.dispatch(())
+                                    >
+                                        {move || t!("info.find_duplicates")}
+                                    </button>
+                                }
+                            })
+                    }}
+                </Suspense>
+                {move || {
+                    corrupted_pages()
+                        .map(|pages| {
+                            if pages.is_empty() {
+                                return view! { <p class="text-success text-sm">{t!("info.no_corrupted_pages")}</p> }
+                                    .into_view();
+                            }
+                            view! {
+                                <ul class="flex flex-col gap-1">
+                                    {pages
+                                        .into_iter()
+                                        .map(|page| {
+                                            view! {
+                                                <li class="flex items-center gap-2 text-error text-sm">
+                                                    <span class="flex-1">{t!("info.corrupted_page", page)}</span>
+                                                    <button
+                                                        class="btn btn-xs"
+                                                        on:click=move |_| {
+                                                            on_retry.call(page);
+                                                            verify_cache.value().set(None);
+                                                        }
+                                                    >
+                                                        {move || t!("info.render_again")}
+                                                    </button>
+                                                </li>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            }
+                                .into_view()
+                        })
+                }}
+                {move || {
+                    find_duplicates
+                        .value()
+                        .get()
+                        .flatten()
+                        .map(|groups| {
+                            if groups.is_empty() {
+                                return view! { <p class="text-success text-sm">{t!("info.no_duplicate_pages")}</p> }
+                                    .into_view();
+                            }
+                            view! {
+                                <ul class="flex flex-col gap-1">
+                                    {groups
+                                        .into_iter()
+                                        .map(|group| {
+                                            let pages = group
+                                                .pages
+                                                .iter()
+                                                .map(u16::to_string)
+                                                .collect::<Vec<_>>()
+                                                .join(", ");
+                                            view! {
+                                                <li class="text-warning text-sm" title=group.perceptual_hash>
+                                                    {t!("info.similar_pages", pages)}
+                                                </li>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            }
+                                .into_view()
+                        })
+                }}
+                <Suspense fallback=|| ()>
+                    {move || {
+                        processing_log
+                            .get()
+                            .flatten()
+                            .map(|log| {
+                                view! {
+                                    <details class="collapse collapse-arrow bg-base-200">
+                                        <summary class="collapse-title text-sm font-medium">
+                                            {move || t!("info.processing_log")}
+                                        </summary>
+                                        <div class="collapse-content">
+                                            <pre class="text-xs max-h-64 overflow-auto whitespace-pre-wrap">
+                                                {log}
+                                            </pre>
+                                        </div>
+                                    </details>
+                                }
+                            })
+                    }}
+                </Suspense>
+            </div>
+        </aside>
+    }
+}
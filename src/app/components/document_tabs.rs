@@ -0,0 +1,369 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::JsValue;
+
+use super::batch_dialog::BatchDialog;
+use super::cache_manager::CacheManager;
+use super::command_palette::{provide_viewer_commands, Command, CommandPalette};
+use super::folder_panel::FolderPanel;
+use super::health_banner::HealthReport;
+use super::hero::Hero;
+use super::merge_dialog::MergeDialog;
+use super::password_dialog::PasswordDialog;
+use super::recent_documents::RecentDocuments;
+use super::settings_dialog::SettingsDialog;
+use super::theme_toggle::{Theme, ThemeToggle};
+use super::toast::{push_toast, use_toasts, ToastKind};
+use super::url_dialog::UrlDialog;
+use crate::app::i18n::{t, use_i18n};
+use crate::app::session::DocumentSession;
+use crate::app::tauri::{error_kind, invoke, listen, log_error, EventListener};
+
+const NOTHING_SELECTED_ERROR: &str = "nothing_selected";
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct OpenDocument {
+    path: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SetTrayDocumentArgs {
+    name: Option<String>,
+}
+
+#[component]
+pub fn DocumentTabs() -> impl IntoView {
+    let owner = Owner::current().expect("DocumentTabs must be rendered inside a reactive owner");
+    let sessions = create_rw_signal(Vec::<DocumentSession>::new());
+    let active_session = create_rw_signal(None::<String>);
+    let (show_batch, set_show_batch) = create_signal(false);
+    let health = create_resource(
+        || (),
+        |_| async move {
+            invoke::<HealthReport>("health_check", &JsValue::default())
+                .await
+                .ok()
+        },
+    );
+    let health_report = Signal::derive(move || health.get().flatten());
+    let (show_merge, set_show_merge) = create_signal(false);
+    let (show_url, set_show_url) = create_signal(false);
+    let (show_folder, set_show_folder) = create_signal(false);
+    let (show_cache, set_show_cache) = create_signal(false);
+    let (show_settings, set_show_settings) = create_signal(false);
+    let theme = create_rw_signal(Theme::system());
+
+    let open_session = move |path: String| {
+        let session = with_owner(owner, || DocumentSession::open(path));
+        active_session.set(Some(session.id.clone()));
+        sessions.update(|sessions| sessions.push(session));
+    };
+
+    let close_session = move |id: String| {
+        sessions.update(|sessions| {
+            if let Some(index) = sessions.iter().position(|session| session.id == id) {
+                sessions.remove(index).close();
+            }
+        });
+        if active_session.get_untracked().as_deref() == Some(id.as_str()) {
+            active_session.set(
+                sessions
+                    .with_untracked(|sessions| sessions.last().map(|session| session.id.clone())),
+            );
+        }
+    };
+
+    let open_listener = store_value(None::<EventListener>);
+    let settings_listener = store_value(None::<EventListener>);
+    let ready_listener = store_value(None::<EventListener>);
+    create_effect(move |_| {
+        on_cleanup(move || {
+            open_listener.set_value(None);
+            settings_listener.set_value(None);
+            ready_listener.set_value(None);
+        });
+        spawn_local(async move {
+            let callback = listen("open_document", move |document: OpenDocument| {
+                open_session(document.path)
+            })
+            .await
+            .unwrap();
+            open_listener.set_value(Some(callback));
+            let callback = listen("open_settings", move |_: ()| set_show_settings(true))
+                .await
+                .unwrap();
+            settings_listener.set_value(Some(callback));
+            let callback = listen("document_ready_opened", move |document: OpenDocument| {
+                sessions.with_untracked(|sessions| {
+                    if let Some(session) = sessions
+                        .iter()
+                        .find(|session| session.path == document.path)
+                    {
+                        active_session.set(Some(session.id.clone()));
+                        session.page_number.set(1);
+                    }
+                });
+            })
+            .await
+            .unwrap();
+            ready_listener.set_value(Some(callback));
+            match invoke::<Vec<String>>("take_pending_documents", &JsValue::default()).await {
+                Ok(paths) => paths.into_iter().for_each(open_session),
+                Err(err) => log_error(err.to_string()),
+            }
+        });
+    });
+
+    let i18n = use_i18n();
+    let toasts = use_toasts();
+    let reload_cleared = move |path: Option<String>| {
+        push_toast(toasts, i18n.t("tabs.cache_cleared"), ToastKind::Info);
+        sessions.with_untracked(|sessions| {
+            sessions
+                .iter()
+                .filter(|session| path.as_ref().map_or(true, |path| &session.path == path))
+                .for_each(DocumentSession::reload);
+        });
+    };
+
+    let unlock_session = move |id: String, decrypted_path: String| {
+        close_session(id);
+        open_session(decrypted_path);
+    };
+
+    let select_document = create_action(move |_: &()| async move {
+        let command = invoke::<String>("select_document", &JsValue::default()).await;
+        match command {
+            Ok(path) => open_session(path),
+            Err(err) if error_kind(&err) == Some(NOTHING_SELECTED_ERROR) => {}
+            Err(err) => {
+                log_error(err.to_string());
+                push_toast(
+                    toasts,
+                    i18n.format("tabs.select_failed", &[err.to_string()]),
+                    ToastKind::Error,
+                );
+            }
+        }
+    });
+
+    let current_session = create_memo(move |_| {
+        let id = active_session()?;
+        sessions.with(|sessions| sessions.iter().find(|session| session.id == id).cloned())
+    });
+
+    create_effect(move |_| {
+        let name = current_session().map(|session| session.name);
+        spawn_local(async move {
+            let Ok(args) = to_value(&SetTrayDocumentArgs { name }) else {
+                return;
+            };
+            if let Err(err) = invoke::<()>("set_tray_document", &args).await {
+                log_error(err.to_string());
+            }
+        });
+    });
+
+    let viewer_commands = provide_viewer_commands();
+    let palette_commands = Signal::derive(move || {
+        vec![
+            Command::new(
+                i18n.t("common.select_document"),
+                &["open", "abrir", "pdf", "documento"],
+                move |_| select_document.dispatch(()),
+            ),
+            Command::new(
+                i18n.t("home.merge_pdfs"),
+                &["merge", "mesclar", "juntar", "combine"],
+                move |_| set_show_merge(true),
+            ),
+            Command::new(
+                i18n.t("home.open_url"),
+                &["url", "download", "link", "baixar"],
+                move |_| set_show_url(true),
+            ),
+            Command::new(
+                i18n.t("home.open_folder"),
+                &["folder", "pasta", "directory", "diretório"],
+                move |_| set_show_folder(true),
+            ),
+            Command::new(
+                i18n.t("home.process_folder"),
+                &["batch", "lote", "process", "processar"],
+                move |_| set_show_batch(true),
+            ),
+            Command::new(
+                i18n.t("palette.cache"),
+                &["cache", "clear", "limpar"],
+                move |_| set_show_cache(true),
+            ),
+            Command::new(
+                i18n.t("palette.settings"),
+                &["settings", "configurações", "preferences", "preferências"],
+                move |_| set_show_settings(true),
+            ),
+        ]
+    });
+
+    view! {
+        <div class="flex flex-col min-h-screen">
+            <div class="flex items-end bg-base-300 px-2 pt-2">
+                <Show when=move || sessions.with(|sessions| !sessions.is_empty())>
+                    <div role="tablist" class="tabs tabs-lifted flex-1">
+                        <For
+                            each=move || sessions()
+                            key=|session| session.id.clone()
+                            children=move |session| {
+                                let id = session.id.clone();
+                                let select_id = session.id.clone();
+                                let close_id = session.id.clone();
+                                view! {
+                                    <a
+                                        role="tab"
+                                        class="tab gap-2"
+                                        class=(
+                                            "tab-active",
+                                            move || active_session().as_deref() == Some(id.as_str()),
+                                        )
+                                        title=session.path
+                                        on:click=move |_| active_session.set(Some(select_id.clone()))
+                                    >
+                                        {session.name}
+                                        <button
+                                            class="btn btn-ghost btn-xs"
+                                            on:click=move |ev| {
+                                                ev.stop_propagation();
+                                                close_session(close_id.clone());
+                                            }
+                                        >
+                                            "✕"
+                                        </button>
+                                    </a>
+                                }
+                            }
+                        />
+
+                        <button
+                            role="tab"
+                            class="tab"
+                            title=move || t!("tabs.open_document")
+                            on:click=move |_| select_document.dispatch(())
+                        >
+                            "+"
+                        </button>
+                    </div>
+                </Show>
+                <div class="ml-auto pb-2 flex items-center gap-2">
+                    <button class="btn btn-ghost btn-sm" on:click=move |_| set_show_cache(true)>
+                        {move || t!("tabs.cache")}
+                    </button>
+                    <button class="btn btn-ghost btn-sm" on:click=move |_| set_show_settings(true)>
+                        {move || t!("palette.settings")}
+                    </button>
+                    <ThemeToggle theme/>
+                </div>
+            </div>
+            {move || match current_session() {
+                Some(session) => {
+                    let encrypted = session.encrypted;
+                    let locked_id = session.id.clone();
+                    let locked_path = session.path.clone();
+                    view! {
+                        <Show
+                            when=encrypted
+                            fallback=move || {
+                                view! {
+                                    <Hero
+                                        session=session.clone()
+                                        health=health_report
+                                        on_select_document=move |_| select_document.dispatch(())
+                                        on_open_document=open_session
+                                    />
+                                }
+                            }
+                        >
+                            <PasswordDialog
+                                path=locked_path.clone()
+                                on_unlock={
+                                    let locked_id = locked_id.clone();
+                                    move |decrypted_path| {
+                                        unlock_session(locked_id.clone(), decrypted_path)
+                                    }
+                                }
+                            />
+                        </Show>
+                    }
+                        .into_view()
+                }
+                None => {
+                    view! {
+                        <div class="hero bg-base-200 flex-1">
+                            <div class="hero-content text-center">
+                                <div class="max-w-md">
+                                    <h1 class="text-4xl font-bold">{move || t!("home.title")}</h1>
+                                    <p class="py-6">{move || t!("home.subtitle")}</p>
+                                    <button
+                                        class="btn btn-primary"
+                                        on:click=move |ev| {
+                                            ev.prevent_default();
+                                            select_document.dispatch(());
+                                        }
+                                    >
+
+                                        {move || t!("common.select_document")}
+                                    </button>
+                                    <button
+                                        class="btn btn-ghost ml-2"
+                                        on:click=move |_| set_show_batch(true)
+                                    >
+                                        {move || t!("home.process_folder")}
+                                    </button>
+                                    <button
+                                        class="btn btn-ghost ml-2"
+                                        on:click=move |_| set_show_merge(true)
+                                    >
+                                        {move || t!("home.merge_pdfs")}
+                                    </button>
+                                    <button
+                                        class="btn btn-ghost ml-2"
+                                        on:click=move |_| set_show_url(true)
+                                    >
+                                        {move || t!("home.open_url")}
+                                    </button>
+                                    <button
+                                        class="btn btn-ghost ml-2"
+                                        on:click=move |_| set_show_folder(true)
+                                    >
+                                        {move || t!("home.open_folder")}
+                                    </button>
+                                    <RecentDocuments on_open=open_session/>
+                                </div>
+                            </div>
+                        </div>
+                    }
+                        .into_view()
+                }
+            }}
+            <Show when=show_batch>
+                <BatchDialog on_close=move |_| set_show_batch(false)/>
+            </Show>
+            <Show when=show_merge>
+                <MergeDialog on_close=move |_| set_show_merge(false)/>
+            </Show>
+            <Show when=show_folder>
+                <FolderPanel on_open=open_session on_close=move |_| set_show_folder(false)/>
+            </Show>
+            <Show when=show_cache>
+                <CacheManager on_cleared=reload_cleared on_close=move |_| set_show_cache(false)/>
+            </Show>
+            <Show when=show_settings>
+                <SettingsDialog theme on_close=move |_| set_show_settings(false)/>
+            </Show>
+            <Show when=show_url>
+                <UrlDialog on_open=open_session on_close=move |_| set_show_url(false)/>
+            </Show>
+            <CommandPalette commands=palette_commands viewer_commands/>
+        </div>
+    }
+}
@@ -0,0 +1,225 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use crate::app::i18n::t;
+use crate::app::tauri::{invoke, listen, EventListener};
+
+const DEFAULT_WATERMARK_OPACITY: f32 = 0.3;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveFileArgs {
+    filter_name: String,
+    extensions: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportPageRangeArgs {
+    path: String,
+    start_page: u32,
+    end_page: u32,
+    output_zip: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WatermarkPagesArgs {
+    path: String,
+    text: String,
+    opacity: f32,
+    output: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct ExportProgress {
+    current: u32,
+    total: u32,
+}
+
+#[component]
+pub fn ExportDialog(
+    path: String,
+    #[prop(into)] total_pages: Signal<u32>,
+    #[prop(into)] on_close: Callback<()>,
+) -> impl IntoView {
+    let start_page = create_rw_signal(1u32);
+    let end_page = create_rw_signal(total_pages.get_untracked().max(1));
+    let progress = create_rw_signal(None::<ExportProgress>);
+    let watermark_text = create_rw_signal(String::new());
+    let watermark_opacity = create_rw_signal(DEFAULT_WATERMARK_OPACITY);
+    let error = create_rw_signal(None::<String>);
+
+    let listener = store_value(None::<EventListener>);
+    create_effect(move |_| {
+        on_cleanup(move || listener.set_value(None));
+        spawn_local(async move {
+            let callback = listen("export_progress", move |update: ExportProgress| {
+                progress.set(Some(update));
+            })
+            .await
+            .unwrap();
+            listener.set_value(Some(callback));
+        });
+    });
+
+    let valid_range = move || {
+        let (start, end) = (start_page(), end_page());
+        start >= 1 && start <= end && end <= total_pages()
+    };
+
+    let watermark_path = path.clone();
+    let export_watermarked = create_action(move |_: &()| {
+        let path = watermark_path.clone();
+        async move {
+            error.set(None);
+            let save_args = SaveFileArgs {
+                filter_name: "PDF".to_string(),
+                extensions: vec!["pdf".to_string()],
+            };
+            let Ok(save_args) = to_value(&save_args) else {
+                return;
+            };
+            let Ok(output) = invoke::<String>("select_save_file", &save_args).await else {
+                return;
+            };
+
+            let args = WatermarkPagesArgs {
+                path,
+                text: watermark_text.get_untracked(),
+                opacity: watermark_opacity.get_untracked(),
+                output,
+            };
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            match invoke::<()>("watermark_pages", &args).await {
+                Ok(()) => on_close.call(()),
+                Err(err) => error.set(Some(err.to_string())),
+            }
+        }
+    });
+
+    let export = create_action(move |_: &()| {
+        let path = path.clone();
+        async move {
+            error.set(None);
+            let save_args = SaveFileArgs {
+                filter_name: "ZIP".to_string(),
+                extensions: vec!["zip".to_string()],
+            };
+            let Ok(save_args) = to_value(&save_args) else {
+                return;
+            };
+            let Ok(output_zip) = invoke::<String>("select_save_file", &save_args).await else {
+                return;
+            };
+
+            let args = ExportPageRangeArgs {
+                path,
+                start_page: start_page.get_untracked(),
+                end_page: end_page.get_untracked(),
+                output_zip,
+            };
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            match invoke::<()>("export_page_range", &args).await {
+                Ok(()) => on_close.call(()),
+                Err(err) => error.set(Some(err.to_string())),
+            }
+        }
+    });
+
+    let parse_page = |ev: ev::Event| event_target_value(&ev).parse::<u32>().unwrap_or(1);
+
+    view! {
+        <dialog class="modal modal-open">
+            <div class="modal-box text-left">
+                <h3 class="font-bold text-lg">{move || t!("export.title")}</h3>
+                <div class="flex gap-4 py-4">
+                    <label class="form-control">
+                        <span class="label-text">{move || t!("export.start_page")}</span>
+                        <input
+                            type="number"
+                            class="input input-bordered input-sm w-24"
+                            min="1"
+                            prop:value=move || start_page().to_string()
+                            on:input=move |ev| start_page.set(parse_page(ev))
+                        />
+                    </label>
+                    <label class="form-control">
+                        <span class="label-text">{move || t!("export.end_page")}</span>
+                        <input
+                            type="number"
+                            class="input input-bordered input-sm w-24"
+                            min="1"
+                            prop:value=move || end_page().to_string()
+                            on:input=move |ev| end_page.set(parse_page(ev))
+                        />
+                    </label>
+                </div>
+                <div class="divider">{move || t!("export.watermark")}</div>
+                <div class="flex flex-col gap-2">
+                    <input
+                        type="text"
+                        class="input input-bordered input-sm"
+                        placeholder=move || t!("export.watermark_text")
+                        prop:value=watermark_text
+                        on:input=move |ev| watermark_text.set(event_target_value(&ev))
+                    />
+                    <label class="form-control">
+                        <span class="label-text">
+                            {move || t!("export.opacity", format!("{:.0}", watermark_opacity() * 100.0))}
+                        </span>
+                        <input
+                            type="range"
+                            class="range range-sm"
+                            min="0.05"
+                            max="1"
+                            step="0.05"
+                            prop:value=move || watermark_opacity().to_string()
+                            on:input=move |ev| {
+                                if let Ok(opacity) = event_target_value(&ev).parse() {
+                                    watermark_opacity.set(opacity);
+                                }
+                            }
+                        />
+                    </label>
+                    <button
+                        class="btn btn-sm"
+                        disabled=move || {
+                            watermark_text.with(|text| text.trim().is_empty())
+                                || export_watermarked.pending()()
+                        }
+                        on:click=move |_| export_watermarked.dispatch(())
+                    >
+                        {move || t!("export.export_watermarked")}
+                    </button>
+                </div>
+                <Show when=move || export.pending()()>
+                    <progress
+                        class="progress progress-primary w-full"
+                        value=move || progress().map(|progress| progress.current).unwrap_or(0)
+                        max=move || progress().map(|progress| progress.total).unwrap_or(1)
+                    ></progress>
+                </Show>
+                <Show when=move || error().is_some()>
+                    <p class="text-error">{move || error().unwrap_or_default()}</p>
+                </Show>
+                <div class="modal-action">
+                    <button class="btn" on:click=move |_| on_close.call(())>
+                        {move || t!("common.cancel")}
+                    </button>
+                    <button
+                        class="btn btn-primary"
+                        disabled=move || !valid_range() || export.pending()()
+                        on:click=move |_| export.dispatch(())
+                    >
+                        {move || t!("common.save_as")}
+                    </button>
+                </div>
+            </div>
+        </dialog>
+    }
+}
@@ -0,0 +1,92 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::JsValue;
+
+use crate::app::i18n::t;
+use crate::app::tauri::invoke;
+
+#[derive(Serialize, Deserialize)]
+struct DirectoryArgs {
+    dir: String,
+}
+
+#[component]
+pub fn FolderPanel(
+    #[prop(into)] on_open: Callback<String>,
+    #[prop(into)] on_close: Callback<()>,
+) -> impl IntoView {
+    let dir = create_rw_signal(None::<String>);
+    let files = create_rw_signal(Vec::<String>::new());
+    let error = create_rw_signal(None::<String>);
+
+    let pick_dir = create_action(move |_: &()| async move {
+        let Ok(selected) = invoke::<String>("select_directory", &JsValue::default()).await else {
+            return;
+        };
+        let Ok(args) = to_value(&DirectoryArgs {
+            dir: selected.clone(),
+        }) else {
+            return;
+        };
+        match invoke::<Vec<String>>("list_pdfs_in_dir", &args).await {
+            Ok(paths) => {
+                error.set(None);
+                files.set(paths);
+            }
+            Err(err) => error.set(Some(err.to_string())),
+        }
+        dir.set(Some(selected));
+    });
+
+    let file_name = |path: &str| path.rsplit(['/', '\\']).next().unwrap_or(path).to_string();
+
+    view! {
+        <dialog class="modal modal-open">
+            <div class="modal-box text-left">
+                <h3 class="font-bold text-lg">{move || t!("home.open_folder")}</h3>
+                <div class="flex flex-col gap-4 py-4">
+                    <div class="flex items-center gap-2">
+                        <button class="btn btn-sm" on:click=move |_| pick_dir.dispatch(())>
+                            {move || t!("common.select_folder")}
+                        </button>
+                        <span class="text-sm truncate">
+                            {move || dir().unwrap_or_else(|| t!("common.no_folder_selected"))}
+                        </span>
+                    </div>
+                    {move || error().map(|error| view! { <p class="text-error">{error}</p> })}
+                    <Show when=move || dir.with(Option::is_some) && files.with(Vec::is_empty)>
+                        <p class="text-sm">{move || t!("folder.empty")}</p>
+                    </Show>
+                    <ul class="menu bg-base-200 rounded-box max-h-80 overflow-y-auto flex-nowrap">
+                        <For
+                            each=move || files()
+                            key=|path| path.clone()
+                            children=move |path| {
+                                let open_path = path.clone();
+                                view! {
+                                    <li>
+                                        <a
+                                            title=path.clone()
+                                            on:click=move |_| {
+                                                on_open.call(open_path.clone());
+                                                on_close.call(());
+                                            }
+                                        >
+                                            {file_name(&path)}
+                                        </a>
+                                    </li>
+                                }
+                            }
+                        />
+                    </ul>
+                </div>
+                <div class="modal-action">
+                    <button class="btn" on:click=move |_| on_close.call(())>
+                        {move || t!("common.close")}
+                    </button>
+                </div>
+            </div>
+        </dialog>
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use super::toast::{push_toast, use_toasts, ToastKind};
+use crate::app::i18n::{t, use_i18n};
+use crate::app::tauri::{invoke, log_error};
+
+#[derive(Serialize, Deserialize)]
+struct DocumentPath {
+    path: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FillFormFieldArgs {
+    path: String,
+    field_name: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+enum FieldKind {
+    Text,
+    CheckBox,
+    RadioButton,
+    Dropdown,
+    Signature,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct FormField {
+    name: String,
+    kind: FieldKind,
+    page: u16,
+    bbox: [f32; 4],
+    value: Option<String>,
+    options: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct PageDimensions {
+    page: u32,
+    width_pt: f32,
+    height_pt: f32,
+}
+
+const OFF_STATE: &str = "Off";
+const FIELD_CLASS: &str =
+    "absolute pointer-events-auto bg-info/10 border border-info/40 rounded-sm text-xs";
+
+#[component]
+pub fn FormLayer(path: String, #[prop(into)] page: Signal<u32>) -> impl IntoView {
+    let i18n = use_i18n();
+    let toasts = use_toasts();
+    // Values are shared by name so the widgets of one radio group stay in sync.
+    let values = create_rw_signal(HashMap::<String, String>::new());
+
+    let document_path = path.clone();
+    let document = create_resource(
+        move || document_path.clone(),
+        move |path| async move {
+            let Ok(args) = to_value(&DocumentPath { path }) else {
+                return (Vec::new(), Vec::new());
+            };
+            let fields = invoke::<Vec<FormField>>("detect_forms", &args)
+                .await
+                .unwrap_or_default();
+            if fields.is_empty() {
+                return (fields, Vec::new());
+            }
+            let dimensions = invoke::<Vec<PageDimensions>>("get_page_dimensions", &args)
+                .await
+                .unwrap_or_default();
+            values.set(
+                fields
+                    .iter()
+                    .filter_map(|field| Some((field.name.clone(), field.value.clone()?)))
+                    .collect(),
+            );
+            (fields, dimensions)
+        },
+    );
+
+    let fill = create_action(move |(field_name, value): &(String, String)| {
+        let path = path.clone();
+        let (field_name, value) = (field_name.clone(), value.clone());
+        async move {
+            values.update(|values| {
+                values.insert(field_name.clone(), value.clone());
+            });
+            let Ok(args) = to_value(&FillFormFieldArgs {
+                path,
+                field_name,
+                value,
+            }) else {
+                return;
+            };
+            if let Err(err) = invoke::<()>("fill_form_field", &args).await {
+                log_error(err.to_string());
+                push_toast(
+                    toasts,
+                    i18n.format("forms.fill_failed", &[err.to_string()]),
+                    ToastKind::Error,
+                );
+            }
+        }
+    });
+
+    // Field rectangles are in PDF points with the origin at the bottom left.
+    let page_fields = move || {
+        let page = page();
+        document.with(|document| {
+            let Some((fields, dimensions)) = document else {
+                return Vec::new();
+            };
+            let Some((width, height)) = dimensions
+                .iter()
+                .find(|dimensions| dimensions.page == page)
+                .filter(|dimensions| dimensions.width_pt > 0.0 && dimensions.height_pt > 0.0)
+                .map(|dimensions| (dimensions.width_pt, dimensions.height_pt))
+            else {
+                return Vec::new();
+            };
+            fields
+                .iter()
+                .filter(|field| u32::from(field.page) == page)
+                .map(|field| {
+                    let [x0, y0, x1, y1] = field.bbox;
+                    let style = format!(
+                        "left: {}%; top: {}%; width: {}%; height: {}%;",
+                        x0 / width * 100.0,
+                        (1.0 - y1 / height) * 100.0,
+                        (x1 - x0) / width * 100.0,
+                        (y1 - y0) / height * 100.0,
+                    );
+                    (field.clone(), style)
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let field_view = move |field: FormField, style: String| {
+        let name = field.name.clone();
+        let current = {
+            let name = name.clone();
+            move || values.with(|values| values.get(&name).cloned().unwrap_or_default())
+        };
+        match field.kind {
+            FieldKind::Text => view! {
+                <input
+                    type="text"
+                    title=name.clone()
+                    class=format!("{} px-1", FIELD_CLASS)
+                    style=style
+                    prop:value=current
+                    on:change=move |ev| fill.dispatch((name.clone(), event_target_value(&ev)))
+                />
+            }
+            .into_view(),
+            FieldKind::Dropdown => {
+                let options = field.options.clone();
+                view! {
+                    <select
+                        title=name.clone()
+                        class=FIELD_CLASS
+                        style=style
+                        prop:value=current.clone()
+                        on:change=move |ev| fill.dispatch((name.clone(), event_target_value(&ev)))
+                    >
+                        {options
+                            .into_iter()
+                            .map(|option| {
+                                let selected = {
+                                    let option = option.clone();
+                                    let current = current.clone();
+                                    move || current() == option
+                                };
+                                view! {
+                                    <option value=option.clone() selected=selected>
+                                        {option.clone()}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                }
+                .into_view()
+            }
+            FieldKind::CheckBox | FieldKind::RadioButton => {
+                let on_state = field.options.first().cloned().unwrap_or_default();
+                let is_radio = field.kind == FieldKind::RadioButton;
+                let checked = {
+                    let on_state = on_state.clone();
+                    move || current() == on_state
+                };
+                view! {
+                    <input
+                        type=if is_radio { "radio" } else { "checkbox" }
+                        title=name.clone()
+                        class=format!("{} cursor-pointer", FIELD_CLASS)
+                        style=style
+                        prop:checked=checked
+                        on:change=move |ev| {
+                            let value = if event_target_checked(&ev) {
+                                on_state.clone()
+                            } else {
+                                OFF_STATE.to_string()
+                            };
+                            fill.dispatch((name.clone(), value));
+                        }
+                    />
+                }
+                .into_view()
+            }
+            FieldKind::Signature => view! {
+                <div
+                    title=t!("forms.signature_field", name)
+                    class=format!("{} flex items-center justify-center opacity-70", FIELD_CLASS)
+                    style=style
+                >
+                    {move || t!("forms.signature")}
+                </div>
+            }
+            .into_view(),
+        }
+    };
+
+    view! {
+        <div class="absolute inset-0 pointer-events-none">
+            {move || {
+                page_fields()
+                    .into_iter()
+                    .map(|(field, style)| field_view(field, style))
+                    .collect_view()
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,61 @@
+use leptos::*;
+use serde::Deserialize;
+
+use crate::app::i18n::t;
+
+const LOW_DISK_SPACE_BYTES: u64 = 500 * 1024 * 1024;
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct HealthReport {
+    magick_available: bool,
+    magick_version: Option<String>,
+    libreoffice_available: bool,
+    pub tesseract_available: bool,
+    pub ocr_languages: Vec<String>,
+    app_data_dir_writable: bool,
+    available_disk_bytes: u64,
+}
+
+impl HealthReport {
+    // Returns the catalogue keys of the problems found.
+    fn problems(&self) -> Vec<&'static str> {
+        let mut problems = Vec::new();
+        if !self.magick_available || self.magick_version.is_none() {
+            problems.push("health.magick_missing");
+        }
+        if !self.libreoffice_available {
+            problems.push("health.libreoffice_missing");
+        }
+        if !self.app_data_dir_writable {
+            problems.push("health.data_dir_read_only");
+        }
+        if self.available_disk_bytes < LOW_DISK_SPACE_BYTES {
+            problems.push("health.low_disk_space");
+        }
+        problems
+    }
+}
+
+#[component]
+pub fn HealthBanner(#[prop(into)] report: Signal<Option<HealthReport>>) -> impl IntoView {
+    let problems = move || {
+        report
+            .with(|report| report.as_ref().map(HealthReport::problems))
+            .unwrap_or_default()
+    };
+
+    view! {
+        <Show when=move || !problems().is_empty()>
+            <div role="alert" class="alert alert-warning absolute top-16 inset-x-4 z-10">
+                <ul class="text-left">
+                    {move || {
+                        problems()
+                            .into_iter()
+                            .map(|problem| view! { <li>{t!(problem)}</li> })
+                            .collect_view()
+                    }}
+                </ul>
+            </div>
+        </Show>
+    }
+}
@@ -1,12 +1,40 @@
-use anyhow::{anyhow, Result};
 use ev::MouseEvent;
-use js_sys::Array;
-use leptos::logging::log;
 use leptos::*;
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
 use serde_wasm_bindgen::to_value;
-use wasm_bindgen::prelude::*;
-use web_sys::{Blob, Url};
+use std::collections::HashMap;
+use std::time::Duration;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{ResizeObserver, Url};
+
+use super::adjustment_toolbar::AdjustmentToolbar;
+use super::annotation_layer::AnnotationLayer;
+use super::command_palette::{use_viewer_commands, Command};
+use super::context_menu::{ContextMenu, ContextMenuItem};
+use super::continuous_view::{scroll_to_page, ContinuousView};
+use super::document_info::{DocumentInfo, DocumentInfoPanel};
+use super::export_dialog::ExportDialog;
+use super::form_layer::FormLayer;
+use super::health_banner::{HealthBanner, HealthReport};
+use super::image_export_dialog::ImageExportDialog;
+use super::link_layer::LinkLayer;
+use super::ocr_panel::OcrPanel;
+use super::outline_panel::OutlinePanel;
+use super::redaction_layer::RedactionLayer;
+use super::render_settings::RenderSettingsPanel;
+use super::search_panel::{SearchHighlightLayer, SearchMatch, SearchPanel};
+use super::split_dialog::SplitDialog;
+use super::thumbnail_strip::ThumbnailStrip;
+use super::toast::{push_toast, use_toasts, ToastKind};
+use crate::app::i18n::{t, use_i18n};
+use crate::app::session::{create_object_url, DocumentSession};
+use crate::app::shortcuts::{has_text_selection, is_typing, shortcut_action, ShortcutAction};
+use crate::app::tauri::{invoke, listen, log_error, log_trace, EventListener};
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct FileDrop {
+    paths: Vec<String>,
+}
 
 #[derive(Serialize, Deserialize)]
 struct GreetArgs<'a> {
@@ -18,141 +46,191 @@ struct DocumentPath {
     path: String,
 }
 
-#[wasm_bindgen(js_namespace = ["window"])]
-extern "C" {
-    #[derive(Debug, Clone)]
-    type TauriInstance;
-    #[wasm_bindgen(js_name = "__TAURI__")]
-    static TAURI_INSTANCE: TauriInstance;
-
-    #[wasm_bindgen(getter, method)]
-    fn core(this: &TauriInstance) -> TauriCoreApi;
+#[derive(Serialize, Deserialize)]
+struct PageArgs {
+    path: String,
+    page: u32,
+}
 
-    #[wasm_bindgen(getter, method)]
-    fn event(this: &TauriInstance) -> TauriEventApi;
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveFileArgs {
+    filter_name: String,
+    extensions: Vec<String>,
 }
 
-#[wasm_bindgen]
-extern "C" {
-    #[derive(Debug, Clone)]
-    type TauriCoreApi;
+#[derive(Serialize, Deserialize)]
+struct AnonymizeDocumentArgs {
+    path: String,
+    output: String,
+}
 
-    #[wasm_bindgen(catch, method)]
-    async fn invoke(this: &TauriCoreApi, fn_name: &str, args: &JsValue)
-        -> Result<JsValue, JsValue>;
+#[derive(Serialize, Deserialize)]
+struct ExtractPageArgs {
+    path: String,
+    page: u32,
+    output: String,
 }
 
-#[wasm_bindgen]
-extern "C" {
-    #[derive(Debug, Clone)]
-    type TauriEventApi;
-
-    #[wasm_bindgen(catch, method)]
-    async fn listen(
-        this: &TauriEventApi,
-        event_name: &str,
-        callback: &Closure<dyn FnMut(JsValue)>,
-    ) -> Result<JsValue, JsValue>;
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InvertedPageArgs {
+    path: String,
+    page_number: u32,
 }
 
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-struct EventData<T> {
-    event: String,
-    payload: T,
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct PageImage {
+    mime_type: String,
+    data: Vec<u8>,
 }
 
 #[derive(Serialize, Deserialize)]
-struct Log {
-    message: String,
+#[serde(rename_all = "camelCase")]
+struct SetDisplayModeArgs {
+    display_mode: String,
 }
 
-fn log_trace(message: &String) {
-    let args = to_value(&Log {
-        message: message.to_string(),
-    })
-    .unwrap();
-    spawn_local(async move {
-        if let Err(err) = invoke::<String>("log_trace", &args).await {
-            log!("{}", err.to_string());
-        }
-    });
+#[derive(Serialize, Deserialize)]
+struct SaveViewportArgs {
+    path: String,
+    page: u32,
+    zoom: f64,
 }
 
-fn log_error(message: String) {
-    let args = to_value(&Log { message }).unwrap();
-    spawn_local(async move {
-        if let Err(err) = invoke::<String>("log_error", &args).await {
-            log!("{}", err.to_string());
-        }
-    });
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RotatePageArgs {
+    document_id: String,
+    page: u32,
+    degrees: i16,
 }
 
-async fn listen<F, T>(
-    event_name: &str,
-    mut callback: F,
-) -> Result<Closure<dyn FnMut(wasm_bindgen::JsValue)>>
-where
-    F: FnMut(T) + 'static,
-    T: DeserializeOwned,
-{
-    let callback = Closure::new(move |data: JsValue| {
-        let data: Result<EventData<T>> =
-            serde_wasm_bindgen::from_value(data).map_err(|err| anyhow!("{:?}", err));
-        match data {
-            Ok(data) => callback(data.payload),
-            Err(err) => log_error(err.to_string()),
-        }
-    });
-
-    TAURI_INSTANCE
-        .event()
-        .listen(event_name, &callback)
-        .await
-        .map_err(|err| anyhow!("{:?}", err))?;
+const PAGE_WIDTH: f64 = 1000.0;
+const VIEWPORT_PADDING: f64 = 64.0;
+const CUSTOM_ZOOM_PREFIX: &str = "custom_zoom:";
+const DEFAULT_ZOOM: f64 = 1.0;
+const MIN_ZOOM: f64 = 0.25;
+const MAX_ZOOM: f64 = 4.0;
+const ZOOM_STEP: f64 = 0.25;
+const DOUBLE_CLICK_ZOOM: f64 = 2.0;
+const WHEEL_ZOOM_FACTOR: f64 = 0.001;
+const FULLSCREEN_CONTROLS_TIMEOUT: Duration = Duration::from_secs(2);
+const COPY_TOAST_TIMEOUT: Duration = Duration::from_secs(2);
+const PREFETCH_DELAY: Duration = Duration::from_millis(300);
+const PREFETCH_AHEAD: u32 = 2;
+const VIEWPORT_SAVE_DELAY: Duration = Duration::from_secs(1);
+const SUPPORTED_EXTENSIONS: [&str; 11] = [
+    "pdf", "png", "jpg", "jpeg", "bmp", "tif", "tiff", "docx", "odt", "pptx", "odp",
+];
 
-    Ok(callback)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DisplayMode {
+    FitWidth,
+    FitHeight,
+    ActualSize,
+    CustomZoom(f64),
 }
 
-async fn invoke<T>(fn_name: &str, args: &JsValue) -> Result<T>
-where
-    T: DeserializeOwned,
-{
-    let result = TAURI_INSTANCE
-        .core()
-        .invoke(fn_name, args)
-        .await
-        .map_err(|err| anyhow!("{:?}", err))?;
-
-    let output: Result<T> =
-        serde_wasm_bindgen::from_value(result).map_err(|err| anyhow!("{:?}", err));
-
-    match output {
-        Ok(data) => Ok(data),
-        Err(err) => {
-            log_error(err.to_string());
-            Err(err)
+impl DisplayMode {
+    fn value(&self) -> String {
+        match self {
+            DisplayMode::FitWidth => "fit_width".to_string(),
+            DisplayMode::FitHeight => "fit_height".to_string(),
+            DisplayMode::ActualSize => "actual_size".to_string(),
+            DisplayMode::CustomZoom(zoom) => format!("{}{}", CUSTOM_ZOOM_PREFIX, zoom),
         }
     }
-}
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
-struct ImageLoaded {
-    page_number: u16,
-    path: String,
-    data: Vec<u8>,
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "fit_width" => Some(DisplayMode::FitWidth),
+            "fit_height" => Some(DisplayMode::FitHeight),
+            "actual_size" => Some(DisplayMode::ActualSize),
+            value => value
+                .strip_prefix(CUSTOM_ZOOM_PREFIX)?
+                .parse()
+                .ok()
+                .map(DisplayMode::CustomZoom),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
-struct ImageUrl {
-    page_number: u16,
-    url: String,
+fn is_supported_document(path: &str) -> bool {
+    path.rsplit_once('.').is_some_and(|(_, extension)| {
+        SUPPORTED_EXTENSIONS.contains(&extension.to_lowercase().as_str())
+    })
 }
 
 #[component]
-pub fn Hero() -> impl IntoView {
-    let (page_number, set_page_number) = create_signal(1u16);
-    let (images, set_images) = create_signal(Vec::<ImageUrl>::new());
+pub fn Hero(
+    session: DocumentSession,
+    #[prop(into)] health: Signal<Option<HealthReport>>,
+    #[prop(into)] on_select_document: Callback<()>,
+    #[prop(into)] on_open_document: Callback<String>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let retry_session = session.clone();
+    let verify_session = session.clone();
+    let delete_session = session.clone();
+    let options_session = session.clone();
+    let render_options = session.options;
+    let settings_session = session.clone();
+    let reprocess = Callback::new(move |options| settings_session.reprocess(options));
+    let prefetch_session = session.clone();
+    let reload_session = session.clone();
+    let DocumentSession {
+        id: session_id,
+        path,
+        images,
+        page_number,
+        progress,
+        converting,
+        page_errors,
+        blank_pages,
+        changed_on_disk,
+        document_id,
+        viewport_restored,
+        restored_zoom,
+        ..
+    } = session;
+    let (show_info, set_show_info) = create_signal(false);
+    let (show_render_settings, set_show_render_settings) = create_signal(false);
+    let (show_outline, set_show_outline) = create_signal(false);
+    let (show_export, set_show_export) = create_signal(false);
+    let (show_image_export, set_show_image_export) = create_signal(false);
+    let (confirm_delete, set_confirm_delete) = create_signal(false);
+    let (confirm_anonymize, set_confirm_anonymize) = create_signal(false);
+    let (redacting, set_redacting) = create_signal(false);
+    let (show_search, set_show_search) = create_signal(false);
+    let (show_ocr, set_show_ocr) = create_signal(false);
+    let ocr_available = move || {
+        health.with(|health| {
+            health
+                .as_ref()
+                .is_some_and(|health| health.tesseract_available)
+        })
+    };
+    let ocr_languages = Signal::derive(move || {
+        health.with(|health| {
+            health
+                .as_ref()
+                .map(|health| health.ocr_languages.clone())
+                .unwrap_or_default()
+        })
+    });
+    let search_matches = create_rw_signal(Vec::<SearchMatch>::new());
+    let (show_split, set_show_split) = create_signal(false);
+    let display_mode = create_rw_signal(DisplayMode::CustomZoom(DEFAULT_ZOOM));
+    let viewport = create_node_ref::<html::Div>();
+    let viewport_size = create_rw_signal((0.0, 0.0));
+    let page_size = create_rw_signal(None::<(f64, f64)>);
+    let panning = create_rw_signal(None::<(i32, i32)>);
+    let pan_offset = create_rw_signal((0, 0));
+    let page_image = create_node_ref::<html::Img>();
+    let fullscreen = create_rw_signal(false);
+    let controls_visible = create_rw_signal(true);
+    let controls_timeout = store_value(None::<TimeoutHandle>);
     let selected_page = create_memo(move |_| {
         images.with(|urls| {
             urls.iter()
@@ -161,139 +239,1489 @@ pub fn Hero() -> impl IntoView {
         })
     });
 
+    let last_page = move || {
+        images
+            .with(|images| images.last().map(|image| image.page_number))
+            .unwrap_or_default()
+    };
+
+    let total_pages = move || {
+        progress
+            .with(|progress| progress.as_ref().map(|progress| progress.total))
+            .unwrap_or_default()
+            .max(last_page())
+    };
+
+    let scroll_mode = create_rw_signal(false);
+    let spread_mode = create_rw_signal(false);
+    let spread_pages = create_memo(move |_| {
+        let left = page_number();
+        images.with(|urls| {
+            let find = |page: u32| urls.iter().find(|url| url.page_number == page).cloned();
+            (find(left), find(left + 1))
+        })
+    });
+    create_effect(move |_| {
+        if spread_mode() && page_number() % 2 == 0 {
+            page_number.update(|page_number| *page_number -= 1);
+        }
+    });
+    let page_step = move || if spread_mode() { 2 } else { 1 };
+
+    let is_blank = move |page: u32| blank_pages.with(|pages| pages.contains(&page));
+
+    let next_page = move || {
+        let mut target = page_number() + page_step();
+        while target < total_pages() && is_blank(target) {
+            target += 1;
+        }
+        if target <= total_pages() {
+            page_number.set(target);
+            let message = format!("Page_number: {}", page_number());
+            log_trace(&message);
+        }
+    };
+
+    let previous_page = move || {
+        if page_number() > 1 {
+            let mut target = page_number().saturating_sub(page_step()).max(1);
+            while target > 1 && is_blank(target) {
+                target -= 1;
+            }
+            page_number.set(target);
+            let message = format!("Page_number: {}", page_number());
+            log_trace(&message);
+        }
+    };
+
+    let prefetch_timeout = store_value(None::<TimeoutHandle>);
     create_effect(move |_| {
+        let current = page_number();
+        if let Some(handle) = prefetch_timeout.get_value() {
+            handle.clear();
+        }
+        prefetch_session.evict_distant_pages(current);
+        if images.with_untracked(|urls| urls.iter().all(|url| url.page_number != current)) {
+            prefetch_session.request_page(current);
+        }
+        let session = prefetch_session.clone();
+        let handle = set_timeout_with_handle(
+            move || {
+                let total = total_pages();
+                for page in current + 1..=(current + PREFETCH_AHEAD).min(total) {
+                    let loaded = images
+                        .with_untracked(|urls| urls.iter().any(|url| url.page_number == page));
+                    if !loaded {
+                        session.request_page(page);
+                    }
+                }
+            },
+            PREFETCH_DELAY,
+        );
+        prefetch_timeout.set_value(handle.ok());
+    });
+    on_cleanup(move || {
+        if let Some(handle) = prefetch_timeout.get_value() {
+            handle.clear();
+        }
+    });
+
+    let toggle_grayscale = move |_: MouseEvent| {
+        let mut options = render_options.get_untracked();
+        options.grayscale = !options.grayscale;
+        options_session.reprocess(options);
+    };
+
+    // Negated renders keyed by the regular page URL, so a re-rendered page is fetched again.
+    let inverted = create_rw_signal(false);
+    let inverted_pages = create_rw_signal(HashMap::<String, String>::new());
+    let inverted_path = path.clone();
+    create_effect(move |_| {
+        if !inverted() {
+            return;
+        }
+        let Some(page) = selected_page() else {
+            return;
+        };
+        if inverted_pages.with_untracked(|pages| pages.contains_key(&page.url)) {
+            return;
+        }
+        let args = InvertedPageArgs {
+            path: inverted_path.clone(),
+            page_number: page.page_number,
+        };
         spawn_local(async move {
-            let callback = listen("image", move |image: ImageLoaded| {
-                let url = create_object_url(image.data);
-                let page_number = image.page_number;
-                set_images.update(|urls| urls.push(ImageUrl { page_number, url }));
-            })
-            .await
-            .unwrap();
-            callback.forget();
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            match invoke::<PageImage>("get_inverted_page", &args).await {
+                Ok(image) => {
+                    let url = create_object_url(image.data, &image.mime_type);
+                    inverted_pages.update(|pages| {
+                        if let Some(previous) = pages.insert(page.url, url) {
+                            let _ = Url::revoke_object_url(&previous);
+                        }
+                    });
+                }
+                Err(err) => log_error(err.to_string()),
+            }
         });
     });
+    on_cleanup(move || {
+        inverted_pages.with_untracked(|pages| {
+            for url in pages.values() {
+                let _ = Url::revoke_object_url(url);
+            }
+        })
+    });
+    let inverted_url = move || {
+        let url = selected_page()?.url;
+        inverted_pages.with(|pages| pages.get(&url).cloned())
+    };
 
-    let select_document =
-        create_action(|input: &(WriteSignal<Vec<ImageUrl>>, WriteSignal<u16>)| {
-            let set_images = input.0.clone();
-            let set_page_number = input.1.clone();
-            async move {
-                let command = invoke::<String>("select_document", &JsValue::default()).await;
-                match command {
-                    Ok(path) => {
-                        set_images.update(|images| {
-                            images.clear();
-                        });
-                        set_page_number(1);
-                        path
-                    }
-                    Err(_) => todo!(),
+    let page_input = create_rw_signal(String::new());
+    create_effect(move |_| page_input.set(page_number().to_string()));
+
+    let go_to_page = move || {
+        let total = total_pages().max(1);
+        let page = page_input
+            .get_untracked()
+            .trim()
+            .parse::<i64>()
+            .map(|page| page.clamp(1, total as i64) as u32)
+            .unwrap_or_else(|_| page_number.get_untracked());
+        page_number.set(page);
+        page_input.set(page.to_string());
+    };
+
+    let _next_page = move |_: MouseEvent| next_page();
+    let _previous_page = move |_: MouseEvent| previous_page();
+
+    // Fit modes are resolved against the viewport size and the rendered page's pixel size.
+    let zoom = create_memo(move |_| {
+        let (width, height) = viewport_size();
+        let zoom = match display_mode() {
+            DisplayMode::FitWidth if width > VIEWPORT_PADDING => {
+                (width - VIEWPORT_PADDING) / PAGE_WIDTH
+            }
+            DisplayMode::FitHeight if height > VIEWPORT_PADDING => page_size()
+                .filter(|(page_width, page_height)| *page_width > 0.0 && *page_height > 0.0)
+                .map(|(page_width, page_height)| {
+                    (height - VIEWPORT_PADDING) * page_width / (page_height * PAGE_WIDTH)
+                })
+                .unwrap_or(DEFAULT_ZOOM),
+            DisplayMode::ActualSize => page_size()
+                .map(|(page_width, _)| page_width / PAGE_WIDTH)
+                .unwrap_or(DEFAULT_ZOOM),
+            DisplayMode::CustomZoom(zoom) => zoom,
+            _ => DEFAULT_ZOOM,
+        };
+        zoom.clamp(MIN_ZOOM, MAX_ZOOM)
+    });
+    let on_resize = Closure::<dyn FnMut()>::new(move || {
+        if let Some(viewport) = viewport.get_untracked() {
+            viewport_size.set((
+                viewport.client_width() as f64,
+                viewport.client_height() as f64,
+            ));
+        }
+    });
+    let resize_observer = store_value(None::<ResizeObserver>);
+    create_effect(move |_| {
+        let Some(viewport) = viewport() else {
+            return;
+        };
+        if resize_observer.with_value(Option::is_some) {
+            return;
+        }
+        if let Ok(observer) = ResizeObserver::new(on_resize.as_ref().unchecked_ref()) {
+            observer.observe(&viewport);
+            resize_observer.set_value(Some(observer));
+        }
+    });
+    on_cleanup(move || {
+        resize_observer.with_value(|observer| {
+            if let Some(observer) = observer {
+                observer.disconnect();
+            }
+        });
+    });
+
+    spawn_local(async move {
+        if let Ok(Some(mode)) =
+            invoke::<Option<String>>("get_display_mode", &JsValue::default()).await
+        {
+            if let Some(mode) = DisplayMode::parse(&mode) {
+                // A zoom restored for this document takes precedence over the global mode.
+                if restored_zoom.get_untracked().is_none() {
+                    display_mode.set(mode);
                 }
             }
+        }
+    });
+    create_effect(move |_| {
+        if let Some(zoom) = restored_zoom() {
+            display_mode.set(DisplayMode::CustomZoom(zoom));
+        }
+    });
+
+    let viewport_path = path.clone();
+    let viewport_timeout = store_value(None::<TimeoutHandle>);
+    create_effect(move |_| {
+        let page = page_number();
+        let zoom = zoom();
+        // Nothing is saved until the stored viewport was applied, so it isn't overwritten.
+        if !viewport_restored() {
+            return;
+        }
+        if let Some(handle) = viewport_timeout.get_value() {
+            handle.clear();
+        }
+        let path = viewport_path.clone();
+        let handle = set_timeout_with_handle(
+            move || {
+                spawn_local(async move {
+                    let Ok(args) = to_value(&SaveViewportArgs { path, page, zoom }) else {
+                        return;
+                    };
+                    if let Err(err) = invoke::<()>("save_viewport", &args).await {
+                        log_error(err.to_string());
+                    }
+                });
+            },
+            VIEWPORT_SAVE_DELAY,
+        );
+        viewport_timeout.set_value(handle.ok());
+    });
+    on_cleanup(move || {
+        if let Some(handle) = viewport_timeout.get_value() {
+            handle.clear();
+        }
+    });
+    let set_display_mode = move |mode: DisplayMode| {
+        display_mode.set(mode);
+        spawn_local(async move {
+            let Ok(args) = to_value(&SetDisplayModeArgs {
+                display_mode: mode.value(),
+            }) else {
+                return;
+            };
+            if let Err(err) = invoke::<()>("set_display_mode", &args).await {
+                log_error(err.to_string());
+            }
         });
+    };
 
-    fn create_object_url(data: Vec<u8>) -> String {
-        let array = Array::new();
-        array.push(&js_sys::Uint8Array::from(&data[..]));
+    let zoom_in = move || {
+        set_display_mode(DisplayMode::CustomZoom(
+            (zoom.get_untracked() + ZOOM_STEP).min(MAX_ZOOM),
+        ))
+    };
+    let zoom_out = move || {
+        set_display_mode(DisplayMode::CustomZoom(
+            (zoom.get_untracked() - ZOOM_STEP).max(MIN_ZOOM),
+        ))
+    };
+    let reset_zoom = move || {
+        set_display_mode(DisplayMode::CustomZoom(DEFAULT_ZOOM));
+        pan_offset.set((0, 0));
+    };
 
-        let blob = Blob::new_with_u8_array_sequence(&array).unwrap();
-        Url::create_object_url_with_blob(&blob).unwrap()
-    }
+    // Wheel zoom fires many events per gesture, so it isn't persisted as the default display mode.
+    let wheel_zoom = move |ev: ev::WheelEvent| {
+        if !ev.ctrl_key() {
+            return;
+        }
+        ev.prevent_default();
+        let target = zoom.get_untracked() - WHEEL_ZOOM_FACTOR * ev.delta_y();
+        display_mode.set(DisplayMode::CustomZoom(target.clamp(MIN_ZOOM, MAX_ZOOM)));
+    };
 
-    let _next_page = move |_: MouseEvent| {
-        if page_number() < (images.with(|images| images.len()) - 1) as u16 {
-            set_page_number.update(|page_number| *page_number += 1);
-            let message = format!("Page_number: {}", page_number());
-            log_trace(&message);
+    // Scaling happens around the page centre, so the pan shifts the clicked point back under the cursor.
+    let toggle_magnification = move |ev: MouseEvent| {
+        let current = zoom.get_untracked();
+        if current > DEFAULT_ZOOM && display_mode.get_untracked() != DisplayMode::FitWidth {
+            set_display_mode(DisplayMode::FitWidth);
+            pan_offset.set((0, 0));
+            return;
         }
+        let Some(image) = page_image.get_untracked() else {
+            return;
+        };
+        let scale = DOUBLE_CLICK_ZOOM / current;
+        let (width, height) = (image.offset_width(), image.offset_height());
+        let offset_x = (ev.offset_x() - width / 2) as f64;
+        let offset_y = (ev.offset_y() - height / 2) as f64;
+        let max_x = (width as f64 * scale / 2.0) as i32;
+        let max_y = (height as f64 * scale / 2.0) as i32;
+        let (x, y) = pan_offset.get_untracked();
+        set_display_mode(DisplayMode::CustomZoom(DOUBLE_CLICK_ZOOM));
+        pan_offset.set((
+            (x - (offset_x * (scale - 1.0)) as i32).clamp(-max_x, max_x),
+            (y - (offset_y * (scale - 1.0)) as i32).clamp(-max_y, max_y),
+        ));
     };
 
-    let _previous_page = move |_: MouseEvent| {
-        if page_number() > 1 {
-            set_page_number.update(|page_number| *page_number -= 1);
-            let message = format!("Page_number: {}", page_number());
-            log_trace(&message);
+    let start_pan = move |ev: ev::PointerEvent| {
+        if zoom.get_untracked() <= DEFAULT_ZOOM {
+            return;
+        }
+        ev.prevent_default();
+        if let Some(image) = page_image.get_untracked() {
+            let _ = image.set_pointer_capture(ev.pointer_id());
         }
+        let (x, y) = pan_offset.get_untracked();
+        panning.set(Some((ev.client_x() - x, ev.client_y() - y)));
     };
 
-    let path = select_document.value();
-    let _preparing_document = select_document.pending();
+    let pan = move |ev: ev::PointerEvent| {
+        let Some((start_x, start_y)) = panning.get_untracked() else {
+            return;
+        };
+        let Some(image) = page_image.get_untracked() else {
+            return;
+        };
+        let max_x = image.offset_width() / 2;
+        let max_y = image.offset_height() / 2;
+        pan_offset.set((
+            (ev.client_x() - start_x).clamp(-max_x, max_x),
+            (ev.client_y() - start_y).clamp(-max_y, max_y),
+        ));
+    };
 
-    let _prepare_document = create_resource(path, |path| async move {
-        match path {
-            Some(path) => {
-                let args = to_value(&DocumentPath { path }).ok()?;
-                invoke::<String>("prepare_document", &args).await.ok()
+    let stop_pan = move |ev: ev::PointerEvent| {
+        if panning.get_untracked().is_none() {
+            return;
+        }
+        if let Some(image) = page_image.get_untracked() {
+            let _ = image.release_pointer_capture(ev.pointer_id());
+        }
+        panning.set(None);
+    };
+
+    let outline_path = path.clone();
+    let ocr_path = path.clone();
+    let print_path = path.clone();
+    let export_path = path.clone();
+    let image_export_path = path.clone();
+    let thumbnail_path = path.clone();
+    let extract_path = path.clone();
+    let anonymize_path = path.clone();
+    let redaction_path = path.clone();
+    let link_path = path.clone();
+    let form_path = path.clone();
+    let highlight_path = path.clone();
+    let split_path = path.clone();
+    let annotation_path = path.clone();
+    let copy_path = path.clone();
+    let scroll_path = path.clone();
+    let adjustment_path = path.clone();
+    let document_info_path = path.clone();
+    let adjustment_session_id = session_id;
+    let document_info = create_resource(
+        move || document_info_path.clone(),
+        |path| async move {
+            let args = to_value(&DocumentPath { path }).ok()?;
+            invoke::<DocumentInfo>("get_document_info", &args)
+                .await
+                .ok()
+        },
+    );
+    let title_path = path.clone();
+    let document_title = Signal::derive(move || {
+        document_info
+            .with(|info| info.clone().flatten().and_then(|info| info.title))
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or_else(|| {
+                title_path
+                    .rsplit(['/', '\\'])
+                    .next()
+                    .unwrap_or(&title_path)
+                    .to_string()
+            })
+    });
+    let page_alt =
+        move |page: u32| i18n.format("hero.page_alt", &[page.to_string(), document_title()]);
+    let copied = create_rw_signal(false);
+    let copy_page = create_action(move |_: &()| {
+        let args = PageArgs {
+            path: copy_path.clone(),
+            page: page_number.get_untracked(),
+        };
+        async move {
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            if invoke::<()>("copy_page_to_clipboard", &args).await.is_ok() {
+                copied.set(true);
+                set_timeout(move || copied.set(false), COPY_TOAST_TIMEOUT);
+            }
+        }
+    });
+    let context_menu = create_rw_signal(None::<(i32, i32)>);
+    let extracted = create_rw_signal(None::<String>);
+    let extract_page = create_action(move |_: &()| {
+        let path = extract_path.clone();
+        let page = page_number.get_untracked();
+        async move {
+            let save_args = SaveFileArgs {
+                filter_name: "PDF".to_string(),
+                extensions: vec!["pdf".to_string()],
+            };
+            let Ok(save_args) = to_value(&save_args) else {
+                return;
+            };
+            let Ok(output) = invoke::<String>("select_save_file", &save_args).await else {
+                return;
+            };
+            let Ok(args) = to_value(&ExtractPageArgs {
+                path,
+                page,
+                output: output.clone(),
+            }) else {
+                return;
+            };
+            match invoke::<()>("extract_page", &args).await {
+                Ok(()) => {
+                    extracted.set(Some(output));
+                    set_timeout(move || extracted.set(None), COPY_TOAST_TIMEOUT);
+                }
+                Err(err) => log_error(err.to_string()),
+            }
+        }
+    });
+    let image_path = path.clone();
+    let export_page_image = create_action(move |_: &()| {
+        let path = image_path.clone();
+        let page = page_number.get_untracked();
+        async move {
+            let save_args = SaveFileArgs {
+                filter_name: "PNG".to_string(),
+                extensions: vec!["png".to_string()],
+            };
+            let Ok(save_args) = to_value(&save_args) else {
+                return;
+            };
+            let Ok(output) = invoke::<String>("select_save_file", &save_args).await else {
+                return;
+            };
+            let Ok(args) = to_value(&ExtractPageArgs {
+                path,
+                page,
+                output: output.clone(),
+            }) else {
+                return;
+            };
+            match invoke::<()>("export_page_image", &args).await {
+                Ok(()) => {
+                    extracted.set(Some(output));
+                    set_timeout(move || extracted.set(None), COPY_TOAST_TIMEOUT);
+                }
+                Err(err) => log_error(err.to_string()),
             }
-            None => None,
         }
     });
+    let toasts = use_toasts();
+    let bookmark_path = path.clone();
+    let add_bookmark = create_action(move |_: &()| {
+        let args = PageArgs {
+            path: bookmark_path.clone(),
+            page: page_number.get_untracked(),
+        };
+        async move {
+            let page = args.page;
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            match invoke::<()>("add_bookmark", &args).await {
+                Ok(()) => push_toast(
+                    toasts,
+                    i18n.format("hero.bookmark_added", &[page.to_string()]),
+                    ToastKind::Success,
+                ),
+                Err(err) => {
+                    log_error(err.to_string());
+                    push_toast(
+                        toasts,
+                        i18n.format("hero.bookmark_failed", &[err.to_string()]),
+                        ToastKind::Error,
+                    );
+                }
+            }
+        }
+    });
+    let anonymize_document = create_action(move |_: &()| {
+        let path = anonymize_path.clone();
+        async move {
+            let save_args = SaveFileArgs {
+                filter_name: "PDF".to_string(),
+                extensions: vec!["pdf".to_string()],
+            };
+            let Ok(save_args) = to_value(&save_args) else {
+                return;
+            };
+            let Ok(output) = invoke::<String>("select_save_file", &save_args).await else {
+                return;
+            };
+            let Ok(args) = to_value(&AnonymizeDocumentArgs { path, output }) else {
+                return;
+            };
+            match invoke::<()>("anonymize_document", &args).await {
+                Ok(()) => push_toast(toasts, i18n.t("hero.anonymized"), ToastKind::Success),
+                Err(err) => {
+                    log_error(err.to_string());
+                    push_toast(
+                        toasts,
+                        i18n.format("hero.anonymize_failed", &[err.to_string()]),
+                        ToastKind::Error,
+                    );
+                }
+            }
+        }
+    });
+    let print_page = create_action(move |_: &()| {
+        let args = PageArgs {
+            path: print_path.clone(),
+            page: page_number.get_untracked(),
+        };
+        async move {
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            if let Err(err) = invoke::<()>("print_page", &args).await {
+                log_error(err.to_string());
+                push_toast(
+                    toasts,
+                    i18n.format("hero.print_failed", &[err.to_string()]),
+                    ToastKind::Error,
+                );
+            }
+        }
+    });
+    let rotate_page = create_action(move |degrees: &i16| {
+        let args = document_id
+            .get_untracked()
+            .map(|document_id| RotatePageArgs {
+                document_id,
+                page: page_number.get_untracked(),
+                degrees: *degrees,
+            });
+        async move {
+            let Some(Ok(args)) = args.map(|args| to_value(&args)) else {
+                return;
+            };
+            if let Err(err) = invoke::<()>("rotate_page", &args).await {
+                log_error(err.to_string());
+                push_toast(
+                    toasts,
+                    i18n.format("hero.rotate_failed", &[err.to_string()]),
+                    ToastKind::Error,
+                );
+            }
+        }
+    });
+
+    let context_menu_items = vec![
+        ContextMenuItem::new(
+            Signal::derive(move || i18n.t("hero.copy_title")),
+            move |_| copy_page.dispatch(()),
+        ),
+        ContextMenuItem::new(
+            Signal::derive(move || i18n.t("hero.export_page_image")),
+            move |_| export_page_image.dispatch(()),
+        ),
+        ContextMenuItem::new(
+            Signal::derive(move || i18n.t("hero.extract_page")),
+            move |_| extract_page.dispatch(()),
+        ),
+        ContextMenuItem::new(
+            Signal::derive(move || i18n.t("hero.rotate_right")),
+            move |_| rotate_page.dispatch(90),
+        ),
+        ContextMenuItem::new(
+            Signal::derive(move || i18n.t("hero.rotate_left")),
+            move |_| rotate_page.dispatch(-90),
+        ),
+        ContextMenuItem::new(
+            Signal::derive(move || i18n.t("hero.add_bookmark")),
+            move |_| add_bookmark.dispatch(()),
+        ),
+        ContextMenuItem::new(
+            Signal::derive(move || i18n.t("hero.page_info")),
+            move |_| set_show_info(true),
+        ),
+    ];
+
+    let info_path = Signal::derive(move || Some(path.clone()));
+
+    let toggle_fullscreen = create_action(move |_: &()| async move {
+        if let Ok(state) = invoke::<bool>("toggle_fullscreen", &JsValue::default()).await {
+            fullscreen.set(state);
+        }
+    });
+
+    let open_context_menu_at_page = move || {
+        if let Some(image) = page_image.get_untracked() {
+            let rect = image.get_bounding_client_rect();
+            context_menu.set(Some((
+                (rect.left() + rect.width() / 2.0) as i32,
+                (rect.top() + rect.height() / 2.0) as i32,
+            )));
+        }
+    };
+    let shortcuts = window_event_listener(ev::keydown, move |ev| {
+        if selected_page.with_untracked(Option::is_none) || is_typing(&ev) {
+            return;
+        }
+        let Some(action) = shortcut_action(&ev) else {
+            return;
+        };
+        match action {
+            ShortcutAction::ZoomIn => zoom_in(),
+            ShortcutAction::ZoomOut => zoom_out(),
+            ShortcutAction::ResetZoom => reset_zoom(),
+            ShortcutAction::NextPage => next_page(),
+            ShortcutAction::PreviousPage => previous_page(),
+            ShortcutAction::FirstPage => page_number.set(1),
+            ShortcutAction::LastPage => page_number.set(total_pages().max(1)),
+            ShortcutAction::ToggleFullscreen => toggle_fullscreen.dispatch(()),
+            ShortcutAction::Print => print_page.dispatch(()),
+            ShortcutAction::Search => set_show_search(true),
+            ShortcutAction::CopyPage if has_text_selection() => return,
+            ShortcutAction::CopyPage => copy_page.dispatch(()),
+            ShortcutAction::FitWidth => set_display_mode(DisplayMode::FitWidth),
+            ShortcutAction::FitHeight => set_display_mode(DisplayMode::FitHeight),
+            ShortcutAction::ActualSize => set_display_mode(DisplayMode::ActualSize),
+            ShortcutAction::ContextMenu => open_context_menu_at_page(),
+            ShortcutAction::ToggleTheme
+            | ShortcutAction::ShortcutReference
+            | ShortcutAction::CommandPalette => return,
+        }
+        ev.prevent_default();
+    });
+    on_cleanup(move || shortcuts.remove());
+
+    if let Some(viewer_commands) = use_viewer_commands() {
+        viewer_commands.register(Signal::derive(move || {
+            vec![
+                Command::new(
+                    i18n.t("hero.search_label"),
+                    &["search", "find", "pesquisar", "buscar"],
+                    move |_| set_show_search(true),
+                ),
+                Command::new(
+                    i18n.t("hero.split_label"),
+                    &["split", "dividir", "pdf"],
+                    move |_| set_show_split(true),
+                ),
+                Command::new(
+                    i18n.t("hero.export_label"),
+                    &["export", "exportar", "zip"],
+                    move |_| set_show_export(true),
+                ),
+                Command::new(
+                    i18n.t("hero.export_images_label"),
+                    &["export", "exportar", "images", "imagens", "png"],
+                    move |_| set_show_image_export(true),
+                ),
+                Command::new(
+                    i18n.t("hero.print_label"),
+                    &["print", "imprimir"],
+                    move |_| print_page.dispatch(()),
+                ),
+                Command::new(
+                    i18n.t("hero.outline_label"),
+                    &["outline", "sumário", "bookmarks", "índice"],
+                    move |_| set_show_outline(true),
+                ),
+                Command::new(
+                    i18n.t("hero.info_label"),
+                    &["info", "information", "informações", "metadata"],
+                    move |_| set_show_info(true),
+                ),
+                Command::new(
+                    i18n.t("hero.fullscreen"),
+                    &["fullscreen", "tela cheia"],
+                    move |_| toggle_fullscreen.dispatch(()),
+                ),
+            ]
+        }));
+    }
+
+    let listener = store_value(None::<EventListener>);
+    create_effect(move |_| {
+        on_cleanup(move || listener.set_value(None));
+        spawn_local(async move {
+            let callback = listen("tauri://resize", move |_: IgnoredAny| {
+                spawn_local(async move {
+                    if let Ok(state) = invoke::<bool>("is_fullscreen", &JsValue::default()).await {
+                        fullscreen.set(state);
+                    }
+                });
+            })
+            .await
+            .unwrap();
+            listener.set_value(Some(callback));
+        });
+    });
+
+    let dragging = create_rw_signal(false);
+    let drop_listeners = store_value(Vec::<EventListener>::new());
+    create_effect(move |_| {
+        on_cleanup(move || drop_listeners.set_value(Vec::new()));
+        spawn_local(async move {
+            let hover = listen("tauri://file-drop-hover", move |_: IgnoredAny| {
+                dragging.set(true)
+            })
+            .await
+            .unwrap();
+            let cancelled = listen("tauri://file-drop-cancelled", move |_: IgnoredAny| {
+                dragging.set(false)
+            })
+            .await
+            .unwrap();
+            let dropped = listen("tauri://file-drop", move |drop: FileDrop| {
+                dragging.set(false);
+                match drop
+                    .paths
+                    .into_iter()
+                    .find(|path| is_supported_document(path))
+                {
+                    Some(path) => on_open_document.call(path),
+                    None => push_toast(toasts, i18n.t("hero.unsupported_file"), ToastKind::Warning),
+                }
+            })
+            .await
+            .unwrap();
+            drop_listeners.set_value(vec![hover, cancelled, dropped]);
+        });
+    });
+
+    let show_controls = move |_: ev::MouseEvent| {
+        controls_visible.set(true);
+        if !fullscreen.get_untracked() {
+            return;
+        }
+        if let Some(handle) = controls_timeout.get_value() {
+            handle.clear();
+        }
+        let handle = set_timeout_with_handle(
+            move || controls_visible.set(false),
+            FULLSCREEN_CONTROLS_TIMEOUT,
+        );
+        controls_timeout.set_value(handle.ok());
+    };
+
+    let fullscreen_label = move || {
+        if fullscreen() {
+            t!("hero.exit_fullscreen")
+        } else {
+            t!("hero.fullscreen")
+        }
+    };
+
+    let controls_hidden =
+        move || selected_page().is_none() || (fullscreen() && !controls_visible());
+
+    let preparing = move || {
+        progress.with(|progress| {
+            progress
+                .as_ref()
+                .is_some_and(|progress| progress.current < progress.total)
+        })
+    };
+
+    let progress_percentage = move || {
+        progress.with(|progress| match progress {
+            Some(progress) if progress.total > 0 => {
+                (progress.current as u64 * 100 / progress.total as u64) as u32
+            }
+            _ => 0,
+        })
+    };
 
     view! {
-        <div class="hero bg-base-200 min-h-screen">
+        <div
+            node_ref=viewport
+            class="hero bg-base-200 flex-1 relative"
+            on:mousemove=show_controls
+            on:wheel=wheel_zoom
+            on:dragover=|ev| ev.prevent_default()
+            on:drop=|ev| ev.prevent_default()
+        >
+            <Show when=dragging>
+                <div class="absolute inset-0 z-40 flex items-center justify-center bg-base-300/80 border-4 border-dashed border-primary pointer-events-none">
+                    <p class="text-2xl font-bold">{move || t!("hero.drop_here")}</p>
+                </div>
+            </Show>
+            <HealthBanner report=health/>
+            <div
+                class=("hidden", move || !preparing())
+                class="absolute top-4 inset-x-4 flex items-center gap-4"
+            >
+                <progress
+                    class="progress progress-primary w-full"
+                    value=progress_percentage
+                    max="100"
+                ></progress>
+                <span class="text-sm">{move || format!("{}%", progress_percentage())}</span>
+            </div>
+            <Show when=converting>
+                <div class="absolute top-4 inset-x-4 flex items-center justify-center gap-2">
+                    <span class="loading loading-spinner loading-sm"></span>
+                    <span class="text-sm">{move || t!("hero.converting")}</span>
+                </div>
+            </Show>
             <div class="hero-content text-center">
                 <div class="max-w-md">
-                    {move || match selected_page().is_some() {
-                        false => {
+                    {move || match (selected_page().is_some(), scroll_mode(), spread_mode()) {
+                        (false, _, _) => {
                             view! {
-                                <h1 class="text-4xl font-bold">"Inicio"</h1>
-                                <p class="py-6">"Para começar, selecione um documento."</p>
-                                <button
-                                    class="btn btn-primary"
-                                    on:click=move |ev| {
-                                        ev.prevent_default();
-                                        select_document.dispatch((set_images, set_page_number));
-                                    }
-                                >
-
-                                    "Selecionar documento"
-                                </button>
+                                <span class="loading loading-spinner loading-lg"></span>
+                                <p class="py-6">{move || t!("hero.preparing")}</p>
                             }
                                 .into_view()
                         }
-                        true => {
+                        (true, true, _) => {
                             view! {
-                                <img
-                                    src=move || selected_page().unwrap().url
-                                    alt="Loaded image"
-                                    style="width: 1000px; height: auto;"
+                                <ContinuousView
+                                    path=scroll_path.clone()
+                                    images=images.read_only()
+                                    total_pages=Signal::derive(total_pages)
+                                    page_number=page_number.write_only()
+                                    zoom=zoom
                                 />
                             }
                                 .into_view()
                         }
+                        (true, false, true) => {
+                            view! {
+                                <div
+                                    class="flex gap-2 justify-center"
+                                    style=move || format!("width: {}px;", (PAGE_WIDTH * zoom()) as u32)
+                                >
+                                    {move || {
+                                        spread_pages()
+                                            .0
+                                            .map(|page| {
+                                                view! {
+                                                    <img
+                                                        src=page.url
+                                                        alt=page_alt(page.page_number)
+                                                        class="w-1/2 h-auto"
+                                                    />
+                                                }
+                                            })
+                                    }}
+                                    {move || {
+                                        spread_pages()
+                                            .1
+                                            .map(|page| {
+                                                view! {
+                                                    <img
+                                                        src=page.url
+                                                        alt=page_alt(page.page_number)
+                                                        class="w-1/2 h-auto"
+                                                    />
+                                                }
+                                            })
+                                    }}
+                                </div>
+                            }
+                                .into_view()
+                        }
+                        (true, false, false) => {
+                            view! {
+                                <div
+                                    class="relative inline-block"
+                                    style=move || {
+                                        let (x, y) = pan_offset();
+                                        format!(
+                                            "width: {}px; transform: translate({}px, {}px);",
+                                            (PAGE_WIDTH * zoom()) as u32,
+                                            x,
+                                            y,
+                                        )
+                                    }
+                                >
+                                    <img
+                                        node_ref=page_image
+                                        src=move || {
+                                            match inverted().then(inverted_url).flatten() {
+                                                Some(url) => url,
+                                                None => selected_page().unwrap().url,
+                                            }
+                                        }
+                                        style=move || {
+                                            (inverted() && inverted_url().is_none())
+                                                .then_some("filter: invert(100%);")
+                                        }
+                                        alt=move || page_alt(page_number())
+                                        class="w-full h-auto"
+                                        class=("cursor-grab", move || zoom() > DEFAULT_ZOOM)
+                                        class=("cursor-grabbing", move || panning().is_some())
+                                        on:pointerdown=start_pan
+                                        on:pointermove=pan
+                                        on:pointerup=stop_pan
+                                        on:pointerleave=stop_pan
+                                        on:dblclick=toggle_magnification
+                                        on:load=move |_| {
+                                            if let Some(image) = page_image.get_untracked() {
+                                                page_size
+                                                    .set(
+                                                        Some((
+                                                            image.natural_width() as f64,
+                                                            image.natural_height() as f64,
+                                                        )),
+                                                    );
+                                            }
+                                        }
+                                        on:contextmenu=move |ev| {
+                                            ev.prevent_default();
+                                            context_menu.set(Some((ev.client_x(), ev.client_y())));
+                                        }
+                                    />
+                                    <AnnotationLayer path=annotation_path.clone() page=page_number/>
+                                    <SearchHighlightLayer
+                                        path=highlight_path.clone()
+                                        page=page_number
+                                        matches=search_matches
+                                    />
+                                    <LinkLayer path=link_path.clone() page=page_number/>
+                                    <FormLayer path=form_path.clone() page=page_number/>
+                                    <Show when=redacting>
+                                        <RedactionLayer
+                                            path=redaction_path.clone()
+                                            page=page_number
+                                            on_done=move |_| set_redacting(false)
+                                        />
+                                    </Show>
+                                </div>
+                            }
+                                .into_view()
+                        }
                     }}
+                    <div
+                        class=("hidden", controls_hidden)
+                        class="absolute top-4 left-1/2 -translate-x-1/2 join"
+                        role="toolbar"
+                        aria-label=move || t!("hero.toolbar")
+                    >
+                        <button
+                            class="btn btn-sm join-item"
+                            aria-label=move || t!("hero.zoom_out")
+                            disabled=move || zoom() <= MIN_ZOOM
+                            on:click=move |_| zoom_out()
+                        >
+                            "−"
+                        </button>
+                        <span
+                            class="btn btn-sm join-item no-animation"
+                            aria-label=move || t!("hero.zoom_level")
+                        >
+                            {move || format!("{}%", (zoom() * 100.0).round())}
+                        </span>
+                        <button
+                            class="btn btn-sm join-item"
+                            aria-label=move || t!("hero.zoom_in")
+                            disabled=move || zoom() >= MAX_ZOOM
+                            on:click=move |_| zoom_in()
+                        >
+                            "+"
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            aria-label=move || t!("hero.zoom_reset_label")
+                            on:click=move |_| reset_zoom()
+                        >
+                            {move || t!("hero.zoom_reset")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            class=("btn-active", move || display_mode() == DisplayMode::FitWidth)
+                            aria-label=move || t!("hero.fit_width")
+                            aria-pressed=move || (display_mode() == DisplayMode::FitWidth).to_string()
+                            on:click=move |_| set_display_mode(DisplayMode::FitWidth)
+                        >
+                            {move || t!("hero.fit_width")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            class=("btn-active", move || display_mode() == DisplayMode::FitHeight)
+                            aria-label=move || t!("hero.fit_height")
+                            aria-pressed=move || (display_mode() == DisplayMode::FitHeight).to_string()
+                            on:click=move |_| set_display_mode(DisplayMode::FitHeight)
+                        >
+                            {move || t!("hero.fit_height")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            class=("btn-active", move || display_mode() == DisplayMode::ActualSize)
+                            aria-label=move || t!("hero.actual_size")
+                            aria-pressed=move || (display_mode() == DisplayMode::ActualSize).to_string()
+                            on:click=move |_| set_display_mode(DisplayMode::ActualSize)
+                        >
+                            {move || t!("hero.actual_size")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            title=move || t!("hero.rotate_left")
+                            aria-label=move || t!("hero.rotate_left")
+                            disabled=rotate_page.pending()
+                            on:click=move |_| rotate_page.dispatch(-90)
+                        >
+                            "⟲"
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            title=move || t!("hero.rotate_right")
+                            aria-label=move || t!("hero.rotate_right")
+                            disabled=rotate_page.pending()
+                            on:click=move |_| rotate_page.dispatch(90)
+                        >
+                            "⟳"
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            title=move || t!("hero.print_label")
+                            aria-label=move || t!("hero.print_label")
+                            disabled=print_page.pending()
+                            on:click=move |_| print_page.dispatch(())
+                        >
+                            {move || t!("hero.print")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            title=move || t!("hero.copy_title")
+                            aria-label=move || t!("hero.copy_label")
+                            disabled=copy_page.pending()
+                            on:click=move |_| copy_page.dispatch(())
+                        >
+                            {move || t!("hero.copy")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            class=("btn-active", spread_mode)
+                            title=move || t!("hero.spread_label")
+                            aria-label=move || t!("hero.spread_label")
+                            aria-pressed=move || spread_mode().to_string()
+                            on:click=move |_| {
+                                spread_mode.update(|spread| *spread = !*spread);
+                                scroll_mode.set(false);
+                            }
+                        >
+                            {move || t!("hero.spread")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            class=("btn-active", scroll_mode)
+                            title=move || t!("hero.continuous")
+                            aria-label=move || t!("hero.continuous")
+                            aria-pressed=move || scroll_mode().to_string()
+                            on:click=move |_| {
+                                scroll_mode.update(|scroll| *scroll = !*scroll);
+                                spread_mode.set(false);
+                            }
+                        >
+                            {move || t!("hero.continuous")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            class=("btn-active", move || render_options.with(|options| options.grayscale))
+                            title=move || t!("hero.grayscale")
+                            aria-label=move || t!("hero.grayscale")
+                            aria-pressed=move || {
+                                render_options.with(|options| options.grayscale).to_string()
+                            }
+                            on:click=toggle_grayscale
+                        >
+                            {move || t!("hero.grayscale")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            class=("btn-active", inverted)
+                            title=move || t!("hero.night_mode_label")
+                            aria-label=move || t!("hero.night_mode_label")
+                            aria-pressed=move || inverted().to_string()
+                            on:click=move |_| inverted.update(|inverted| *inverted = !*inverted)
+                        >
+                            {move || t!("hero.night_mode")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            class=("btn-active", show_render_settings)
+                            aria-label=move || t!("hero.render_settings_label")
+                            aria-expanded=move || show_render_settings().to_string()
+                            on:click=move |_| set_show_render_settings.update(|show| *show = !*show)
+                        >
+                            {move || t!("hero.render_settings")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            aria-label=move || t!("hero.export_label")
+                            aria-haspopup="dialog"
+                            on:click=move |_| set_show_export(true)
+                        >
+                            {move || t!("hero.export")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            aria-label=move || t!("hero.export_images_label")
+                            aria-haspopup="dialog"
+                            on:click=move |_| set_show_image_export(true)
+                        >
+                            {move || t!("hero.export_images")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            aria-label=move || t!("hero.split_label")
+                            aria-haspopup="dialog"
+                            on:click=move |_| set_show_split(true)
+                        >
+                            {move || t!("hero.split")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            class=("btn-active", show_search)
+                            aria-label=move || t!("hero.search_label")
+                            aria-expanded=move || show_search().to_string()
+                            on:click=move |_| {
+                                set_show_ocr(false);
+                                set_show_search.update(|show| *show = !*show);
+                            }
+                        >
+                            {move || t!("hero.search")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            class=("btn-active", show_ocr)
+                            aria-label=move || t!("hero.ocr")
+                            aria-expanded=move || show_ocr().to_string()
+                            disabled=move || !ocr_available()
+                            title=move || {
+                                if ocr_available() { String::new() } else { t!("hero.ocr_unavailable") }
+                            }
+                            on:click=move |_| {
+                                set_show_search(false);
+                                set_show_ocr.update(|show| *show = !*show);
+                            }
+                        >
+                            {move || t!("hero.ocr")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            aria-label=move || t!("hero.delete_page_label")
+                            aria-haspopup="dialog"
+                            disabled=move || total_pages() <= 1
+                            on:click=move |_| set_confirm_delete(true)
+                        >
+                            {move || t!("hero.delete_page")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            aria-label=move || t!("hero.anonymize_label")
+                            aria-haspopup="dialog"
+                            disabled=move || anonymize_document.pending()()
+                            on:click=move |_| set_confirm_anonymize(true)
+                        >
+                            {move || t!("hero.anonymize")}
+                        </button>
+                        <button
+                            class="btn btn-sm join-item"
+                            class=("btn-active", redacting)
+                            aria-label=move || t!("hero.redact_label")
+                            aria-pressed=move || redacting().to_string()
+                            on:click=move |_| set_redacting.update(|redacting| *redacting = !*redacting)
+                        >
+                            {move || t!("hero.redact")}
+                        </button>
+                    </div>
+                    <div
+                        class=("hidden", controls_hidden)
+                        class="absolute top-16 left-1/2 -translate-x-1/2"
+                    >
+                        <AdjustmentToolbar
+                            path=adjustment_path.clone()
+                            session_id=adjustment_session_id.clone()
+                            page=page_number
+                        />
+                    </div>
                     <button
-                        class=("hidden", move || selected_page().is_none())
+                        class=("hidden", controls_hidden)
+                        class="absolute top-4 left-4 btn btn-ghost"
+                        aria-label=move || t!("hero.outline_label")
+                        aria-expanded=move || show_outline().to_string()
+                        on:click=move |_| set_show_outline.update(|show| *show = !*show)
+                    >
+                        {move || t!("hero.outline")}
+                    </button>
+                    <button
+                        class=("hidden", controls_hidden)
+                        class="absolute top-16 right-4 btn btn-ghost"
+                        title=fullscreen_label
+                        aria-label=fullscreen_label
+                        on:click=move |_| toggle_fullscreen.dispatch(())
+                    >
+                        {move || if fullscreen() { "🗗" } else { "⛶" }}
+                    </button>
+                    <button
+                        class=("hidden", controls_hidden)
+                        class="absolute top-4 right-4 btn btn-ghost"
+                        aria-label=move || t!("hero.info_label")
+                        aria-expanded=move || show_info().to_string()
+                        on:click=move |_| set_show_info.update(|show| *show = !*show)
+                    >
+                        {move || t!("hero.info")}
+                    </button>
+                    <button
+                        class=("hidden", controls_hidden)
                         class="absolute bottom-24 right-4 btn btn-primary"
+                        aria-label=move || t!("common.select_document")
                         on:click=move |ev| {
                             ev.prevent_default();
-                            select_document.dispatch((set_images, set_page_number));
+                            on_select_document.call(());
                         }
                     >
 
-                        "Selecionar documento"
+                        {move || t!("common.select_document")}
                     </button>
-                    <button
+                    <div role="navigation" aria-label=move || t!("hero.page_navigation") class="contents">
+                        <div
+                            class=("hidden", controls_hidden)
+                            class="absolute bottom-4 left-1/2 -translate-x-1/2 flex items-center gap-2"
+                        >
+                            <input
+                                type="number"
+                                aria-label=move || t!("hero.page_number")
+                                class="input input-bordered input-sm w-20 text-center"
+                                min="1"
+                                max=move || total_pages().to_string()
+                                prop:value=page_input
+                                on:input=move |ev| page_input.set(event_target_value(&ev))
+                                on:blur=move |_| go_to_page()
+                                on:keydown=move |ev| {
+                                    if ev.key() == "Enter" {
+                                        go_to_page();
+                                    }
+                                }
+                            />
+                            <span aria-hidden="true">{move || format!("/ {}", total_pages())}</span>
+                            <span class="sr-only" aria-live="polite">
+                                {move || t!("hero.page_counter", page_number(), total_pages())}
+                            </span>
+                        </div>
+                        <button
 
-                        class=("hidden", move || selected_page().is_none())
-                        class="absolute bottom-4 left-4 btn btn-primary"
-                        on:click=_previous_page
-                    >
-                        "Página anterior"
-                    </button>
-                    <button
+                            class=("hidden", controls_hidden)
+                            class="absolute bottom-4 left-4 btn btn-primary"
+                            aria-label=move || t!("hero.previous_page")
+                            on:click=_previous_page
+                        >
+                            {move || t!("hero.previous_page")}
+                        </button>
+                        <button
 
-                        class=("hidden", move || selected_page().is_none())
-                        class="absolute bottom-4 left-4 btn btn-primary"
-                        class:hidden=move || selected_page().is_none()
-                        class="absolute bottom-4 right-4 btn btn-primary"
-                        on:click=_next_page
-                    >
-                        "Próxima página"
-                    </button>
+                            class=("hidden", controls_hidden)
+                            class="absolute bottom-4 left-4 btn btn-primary"
+                            class:hidden=controls_hidden
+                            class="absolute bottom-4 right-4 btn btn-primary"
+                            aria-label=move || t!("hero.next_page")
+                            on:click=_next_page
+                        >
+                            {move || t!("hero.next_page")}
+                        </button>
+                    </div>
                 </div>
             </div>
+            <Show when=move || selected_page().is_some()>
+                <ThumbnailStrip
+                    path=thumbnail_path.clone()
+                    images=images.read_only()
+                    total_pages=Signal::derive(total_pages)
+                    current_page=page_number.read_only()
+                    page_number=page_number.write_only()
+                    on_select=move |page| {
+                        if scroll_mode.get_untracked() {
+                            scroll_to_page(page);
+                        }
+                    }
+                />
+            </Show>
+            <Show when=move || page_errors.with(|errors| !errors.is_empty())>
+                <div class="toast toast-start">
+                    <div role="alert" class="alert alert-error flex flex-col items-start">
+                        <span>{move || t!("hero.page_errors")}</span>
+                        <For
+                            each=page_errors
+                            key=|error| error.page_number
+                            children={
+                                let retry_session = retry_session.clone();
+                                move |error| {
+                                    let retry_session = retry_session.clone();
+                                    let page = error.page_number;
+                                    view! {
+                                        <div class="flex items-center gap-2" title=error.message>
+                                            <span>{move || t!("hero.page", page)}</span>
+                                            <button
+                                                class="btn btn-xs"
+                                                aria-label=move || t!("hero.retry_label", page)
+                                                on:click=move |_| retry_session.retry_page(page)
+                                            >
+                                                {move || t!("hero.retry")}
+                                            </button>
+                                        </div>
+                                    }
+                                }
+                            }
+                        />
+                    </div>
+                </div>
+            </Show>
+            <Show when=changed_on_disk>
+                <div class="toast toast-top toast-center">
+                    <div role="alert" class="alert alert-info">
+                        <span>{move || t!("hero.changed_on_disk")}</span>
+                        <div class="flex gap-2">
+                            <button class="btn btn-sm" on:click=move |_| changed_on_disk.set(false)>
+                                {move || t!("hero.ignore")}
+                            </button>
+                            <button
+                                class="btn btn-sm btn-primary"
+                                on:click={
+                                    let reload_session = reload_session.clone();
+                                    move |_| reload_session.reload()
+                                }
+                            >
+                                {move || t!("hero.reload")}
+                            </button>
+                        </div>
+                    </div>
+                </div>
+            </Show>
+            <ContextMenu
+                position=context_menu
+                items=context_menu_items
+                label=Signal::derive(move || i18n.t("hero.context_menu"))
+            />
+            {move || {
+                extracted()
+                    .map(|output| {
+                        view! {
+                            <div class="toast toast-end">
+                                <div class="alert alert-success">
+                                    <span>{move || t!("hero.page_saved", output)}</span>
+                                </div>
+                            </div>
+                        }
+                    })
+            }}
+            <Show when=copied>
+                <div class="toast toast-end">
+                    <div class="alert alert-success">
+                        <span>{move || t!("hero.page_copied")}</span>
+                    </div>
+                </div>
+            </Show>
+            <Show when=show_export>
+                <ExportDialog
+                    path=export_path.clone()
+                    total_pages=Signal::derive(total_pages)
+                    on_close=move |_| set_show_export(false)
+                />
+            </Show>
+            <Show when=show_image_export>
+                <ImageExportDialog
+                    path=image_export_path.clone()
+                    on_close=move |_| set_show_image_export(false)
+                />
+            </Show>
+            <Show when=confirm_delete>
+                <dialog class="modal modal-open">
+                    <div class="modal-box">
+                        <h3 class="font-bold text-lg">{move || t!("hero.delete_page")}</h3>
+                        <p class="py-4">
+                            {move || t!("hero.delete_confirm", page_number())}
+                        </p>
+                        <div class="modal-action">
+                            <button class="btn" on:click=move |_| set_confirm_delete(false)>
+                                {move || t!("common.cancel")}
+                            </button>
+                            <button
+                                class="btn btn-error"
+                                on:click={
+                                    let delete_session = delete_session.clone();
+                                    move |_| {
+                                        delete_session.delete_page(page_number.get_untracked());
+                                        set_confirm_delete(false);
+                                    }
+                                }
+                            >
+                                {move || t!("hero.delete")}
+                            </button>
+                        </div>
+                    </div>
+                </dialog>
+            </Show>
+            <Show when=confirm_anonymize>
+                <dialog class="modal modal-open">
+                    <div class="modal-box">
+                        <h3 class="font-bold text-lg">{move || t!("hero.anonymize_label")}</h3>
+                        <p class="py-4">
+                            {move || t!("hero.anonymize_confirm")}
+                        </p>
+                        <div class="modal-action">
+                            <button class="btn" on:click=move |_| set_confirm_anonymize(false)>
+                                {move || t!("common.cancel")}
+                            </button>
+                            <button
+                                class="btn btn-warning"
+                                on:click=move |_| {
+                                    set_confirm_anonymize(false);
+                                    anonymize_document.dispatch(());
+                                }
+                            >
+                                {move || t!("hero.anonymize")}
+                            </button>
+                        </div>
+                    </div>
+                </dialog>
+            </Show>
+            <Show when=show_split>
+                <SplitDialog
+                    path=split_path.clone()
+                    total_pages=Signal::derive(total_pages)
+                    on_close=move |_| set_show_split(false)
+                />
+            </Show>
+            <Show when=show_outline>
+                <OutlinePanel path=outline_path.clone() page_number=page_number.write_only()/>
+            </Show>
+            <Show when=show_search>
+                <SearchPanel
+                    document_id=document_id
+                    page_number=page_number.write_only()
+                    matches=search_matches
+                />
+            </Show>
+            <Show when=show_ocr>
+                <OcrPanel
+                    path=ocr_path.clone()
+                    page_number=page_number
+                    languages=ocr_languages
+                />
+            </Show>
+            <Show when=show_info>
+                <DocumentInfoPanel
+                    path=info_path
+                    on_retry={
+                        let verify_session = verify_session.clone();
+                        move |page| verify_session.retry_page(page)
+                    }
+                />
+            </Show>
+            <Show when=show_render_settings>
+                <RenderSettingsPanel options=render_options on_change=reprocess/>
+            </Show>
         </div>
     }
 }
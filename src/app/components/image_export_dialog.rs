@@ -0,0 +1,147 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::JsValue;
+
+use crate::app::i18n::t;
+use crate::app::tauri::{invoke, listen, EventListener};
+
+const JPEG_QUALITY: u8 = 90;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum ImageFormat {
+    WebP,
+    Png,
+    Jpeg { quality: u8 },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportDocumentImagesArgs {
+    path: String,
+    output_dir: String,
+    format: ImageFormat,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct ExportProgress {
+    current: u32,
+    total: u32,
+}
+
+#[component]
+pub fn ImageExportDialog(path: String, #[prop(into)] on_close: Callback<()>) -> impl IntoView {
+    let format = create_rw_signal(ImageFormat::Png);
+    let output_dir = create_rw_signal(None::<String>);
+    let progress = create_rw_signal(None::<ExportProgress>);
+    let result = create_rw_signal(None::<Result<usize, String>>);
+
+    let listener = store_value(None::<EventListener>);
+    create_effect(move |_| {
+        on_cleanup(move || listener.set_value(None));
+        spawn_local(async move {
+            let callback = listen("export_progress", move |update: ExportProgress| {
+                progress.set(Some(update));
+            })
+            .await
+            .unwrap();
+            listener.set_value(Some(callback));
+        });
+    });
+
+    let pick_output_dir = create_action(move |_: &()| async move {
+        if let Ok(dir) = invoke::<String>("select_directory", &JsValue::default()).await {
+            output_dir.set(Some(dir));
+        }
+    });
+
+    let export = create_action(move |_: &()| {
+        let path = path.clone();
+        async move {
+            let Some(output_dir) = output_dir.get_untracked() else {
+                return;
+            };
+            let args = ExportDocumentImagesArgs {
+                path,
+                output_dir,
+                format: format.get_untracked(),
+            };
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            let outcome = invoke::<Vec<String>>("export_document_images", &args)
+                .await
+                .map(|exported| exported.len())
+                .map_err(|err| err.to_string());
+            result.set(Some(outcome));
+        }
+    });
+
+    let select_format = move |ev: ev::Event| {
+        format.set(match event_target_value(&ev).as_str() {
+            "webp" => ImageFormat::WebP,
+            "jpeg" => ImageFormat::Jpeg {
+                quality: JPEG_QUALITY,
+            },
+            _ => ImageFormat::Png,
+        });
+    };
+
+    view! {
+        <dialog class="modal modal-open">
+            <div class="modal-box text-left">
+                <h3 class="font-bold text-lg">{move || t!("hero.export_images")}</h3>
+                <div class="flex flex-col gap-4 py-4">
+                    <label class="form-control">
+                        <span class="label-text">{move || t!("info.format")}</span>
+                        <select class="select select-bordered select-sm" on:change=select_format>
+                            <option value="png" selected=true>
+                                "PNG"
+                            </option>
+                            <option value="jpeg">"JPEG"</option>
+                            <option value="webp">"WebP"</option>
+                        </select>
+                    </label>
+                    <div class="flex items-center gap-2">
+                        <button class="btn btn-sm" on:click=move |_| pick_output_dir.dispatch(())>
+                            {move || t!("common.select_folder")}
+                        </button>
+                        <span class="text-sm truncate">
+                            {move || {
+                                output_dir().unwrap_or_else(|| t!("common.no_folder_selected"))
+                            }}
+                        </span>
+                    </div>
+                    <Show when=move || export.pending()()>
+                        <progress
+                            class="progress progress-primary w-full"
+                            value=move || progress().map(|progress| progress.current).unwrap_or(0)
+                            max=move || progress().map(|progress| progress.total).unwrap_or(1)
+                        ></progress>
+                    </Show>
+                    {move || match result() {
+                        Some(Ok(count)) => {
+                            view! { <p class="text-success">{t!("image_export.exported", count)}</p> }
+                                .into_view()
+                        }
+                        Some(Err(err)) => view! { <p class="text-error">{err}</p> }.into_view(),
+                        None => ().into_view(),
+                    }}
+                </div>
+                <div class="modal-action">
+                    <button class="btn" on:click=move |_| on_close.call(())>
+                        {move || t!("common.close")}
+                    </button>
+                    <button
+                        class="btn btn-primary"
+                        disabled=move || output_dir.with(Option::is_none) || export.pending()()
+                        on:click=move |_| export.dispatch(())
+                    >
+                        {move || t!("hero.export")}
+                    </button>
+                </div>
+            </div>
+        </dialog>
+    }
+}
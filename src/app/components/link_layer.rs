@@ -0,0 +1,121 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use crate::app::tauri::{invoke, log_error};
+
+#[derive(Serialize, Deserialize)]
+struct DocumentPath {
+    path: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OpenLinkArgs {
+    uri: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct DocumentLink {
+    page: u16,
+    uri: String,
+    bbox: [f32; 4],
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct PageDimensions {
+    page: u32,
+    width_pt: f32,
+    height_pt: f32,
+}
+
+#[component]
+pub fn LinkLayer(path: String, #[prop(into)] page: Signal<u32>) -> impl IntoView {
+    let document = create_resource(
+        move || path.clone(),
+        |path| async move {
+            let Ok(args) = to_value(&DocumentPath { path }) else {
+                return (Vec::new(), Vec::new());
+            };
+            let links = invoke::<Vec<DocumentLink>>("list_document_links", &args)
+                .await
+                .unwrap_or_default();
+            if links.is_empty() {
+                return (links, Vec::new());
+            }
+            let dimensions = invoke::<Vec<PageDimensions>>("get_page_dimensions", &args)
+                .await
+                .unwrap_or_default();
+            (links, dimensions)
+        },
+    );
+
+    // Link rectangles are in PDF points with the origin at the bottom left.
+    let page_links = move || {
+        let page = page();
+        document.with(|document| {
+            let Some((links, dimensions)) = document else {
+                return Vec::new();
+            };
+            let Some((width, height)) = dimensions
+                .iter()
+                .find(|dimensions| dimensions.page == page)
+                .filter(|dimensions| dimensions.width_pt > 0.0 && dimensions.height_pt > 0.0)
+                .map(|dimensions| (dimensions.width_pt, dimensions.height_pt))
+            else {
+                return Vec::new();
+            };
+            links
+                .iter()
+                .filter(|link| u32::from(link.page) == page)
+                .map(|link| {
+                    let [x0, y0, x1, y1] = link.bbox;
+                    let style = format!(
+                        "left: {}%; top: {}%; width: {}%; height: {}%;",
+                        x0 / width * 100.0,
+                        (1.0 - y1 / height) * 100.0,
+                        (x1 - x0) / width * 100.0,
+                        (y1 - y0) / height * 100.0,
+                    );
+                    (link.uri.clone(), style)
+                })
+                .collect::<Vec<_>>()
+        })
+    };
+
+    let open_link = create_action(|uri: &String| {
+        let uri = uri.clone();
+        async move {
+            let Ok(args) = to_value(&OpenLinkArgs { uri }) else {
+                return;
+            };
+            if let Err(err) = invoke::<()>("open_link", &args).await {
+                log_error(err.to_string());
+            }
+        }
+    });
+
+    view! {
+        <div class="absolute inset-0 pointer-events-none">
+            {move || {
+                page_links()
+                    .into_iter()
+                    .map(|(uri, style)| {
+                        let target = uri.clone();
+                        view! {
+                            <a
+                                href=uri.clone()
+                                title=uri
+                                class="absolute pointer-events-auto cursor-pointer hover:bg-info/20 rounded-sm"
+                                style=style
+                                on:click=move |ev| {
+                                    ev.prevent_default();
+                                    open_link.dispatch(target.clone());
+                                }
+                            ></a>
+                        }
+                    })
+                    .collect_view()
+            }}
+        </div>
+    }
+}
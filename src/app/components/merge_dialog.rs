@@ -0,0 +1,194 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::JsValue;
+
+use crate::app::i18n::t;
+use crate::app::tauri::{invoke, listen, EventListener};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveFileArgs {
+    filter_name: String,
+    extensions: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MergeDocumentsArgs {
+    paths: Vec<String>,
+    output: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct MergeProgress {
+    current: usize,
+    total: usize,
+}
+
+#[component]
+pub fn MergeDialog(#[prop(into)] on_close: Callback<()>) -> impl IntoView {
+    let files = create_rw_signal(Vec::<String>::new());
+    let dragged = create_rw_signal(None::<String>);
+    let output = create_rw_signal(None::<String>);
+    let progress = create_rw_signal(None::<MergeProgress>);
+    let result = create_rw_signal(None::<Result<String, String>>);
+
+    let listener = store_value(None::<EventListener>);
+    create_effect(move |_| {
+        on_cleanup(move || listener.set_value(None));
+        spawn_local(async move {
+            let callback = listen("merge_progress", move |update: MergeProgress| {
+                progress.set(Some(update));
+            })
+            .await
+            .unwrap();
+            listener.set_value(Some(callback));
+        });
+    });
+
+    let add_files = create_action(move |_: &()| async move {
+        if let Ok(paths) = invoke::<Vec<String>>("select_documents", &JsValue::default()).await {
+            files.update(|files| {
+                for path in paths {
+                    if !files.contains(&path) {
+                        files.push(path);
+                    }
+                }
+            });
+        }
+    });
+
+    let pick_output = create_action(move |_: &()| async move {
+        let args = SaveFileArgs {
+            filter_name: "PDF".to_string(),
+            extensions: vec!["pdf".to_string()],
+        };
+        let Ok(args) = to_value(&args) else {
+            return;
+        };
+        if let Ok(path) = invoke::<String>("select_save_file", &args).await {
+            output.set(Some(path));
+        }
+    });
+
+    let merge = create_action(move |_: &()| async move {
+        let Some(output) = output.get_untracked() else {
+            return;
+        };
+        let args = MergeDocumentsArgs {
+            paths: files.get_untracked(),
+            output,
+        };
+        let Ok(args) = to_value(&args) else {
+            return;
+        };
+        let outcome = invoke::<String>("merge_documents", &args)
+            .await
+            .map_err(|err| err.to_string());
+        result.set(Some(outcome));
+    });
+
+    let move_before = move |target: String| {
+        let Some(source) = dragged.get_untracked() else {
+            return;
+        };
+        files.update(|files| {
+            let Some(from) = files.iter().position(|path| path == &source) else {
+                return;
+            };
+            let path = files.remove(from);
+            let to = files
+                .iter()
+                .position(|path| path == &target)
+                .unwrap_or(files.len());
+            files.insert(to, path);
+        });
+        dragged.set(None);
+    };
+
+    view! {
+        <dialog class="modal modal-open">
+            <div class="modal-box text-left">
+                <h3 class="font-bold text-lg">{move || t!("home.merge_pdfs")}</h3>
+                <div class="flex flex-col gap-4 py-4">
+                    <ul class="flex flex-col gap-1">
+                        <For
+                            each=move || files()
+                            key=|path| path.clone()
+                            children=move |path| {
+                                let drag_path = path.clone();
+                                let drop_path = path.clone();
+                                let remove_path = path.clone();
+                                view! {
+                                    <li
+                                        class="flex items-center gap-2 p-2 bg-base-200 rounded cursor-move"
+                                        draggable="true"
+                                        on:dragstart=move |_| dragged.set(Some(drag_path.clone()))
+                                        on:dragover=move |ev| ev.prevent_default()
+                                        on:drop=move |ev| {
+                                            ev.prevent_default();
+                                            move_before(drop_path.clone());
+                                        }
+                                    >
+                                        <span class="flex-1 text-sm truncate" title=path.clone()>
+                                            {path.clone()}
+                                        </span>
+                                        <button
+                                            class="btn btn-ghost btn-xs"
+                                            on:click=move |_| {
+                                                files.update(|files| files.retain(|file| file != &remove_path))
+                                            }
+                                        >
+                                            "✕"
+                                        </button>
+                                    </li>
+                                }
+                            }
+                        />
+                    </ul>
+                    <button class="btn btn-sm" on:click=move |_| add_files.dispatch(())>
+                        {move || t!("merge.add_files")}
+                    </button>
+                    <div class="flex items-center gap-2">
+                        <button class="btn btn-sm" on:click=move |_| pick_output.dispatch(())>
+                            {move || t!("common.save_as")}
+                        </button>
+                        <span class="text-sm truncate">
+                            {move || output().unwrap_or_else(|| t!("merge.no_output"))}
+                        </span>
+                    </div>
+                    <Show when=move || merge.pending()()>
+                        <progress
+                            class="progress progress-primary w-full"
+                            value=move || progress().map(|progress| progress.current).unwrap_or(0)
+                            max=move || progress().map(|progress| progress.total).unwrap_or(1)
+                        ></progress>
+                    </Show>
+                    {move || match result() {
+                        Some(Ok(path)) => {
+                            view! { <p class="text-success">{t!("merge.saved", path)}</p> }
+                                .into_view()
+                        }
+                        Some(Err(err)) => view! { <p class="text-error">{err}</p> }.into_view(),
+                        None => ().into_view(),
+                    }}
+                </div>
+                <div class="modal-action">
+                    <button class="btn" on:click=move |_| on_close.call(())>
+                        {move || t!("common.close")}
+                    </button>
+                    <button
+                        class="btn btn-primary"
+                        disabled=move || {
+                            files.with(|files| files.len() < 2) || output.with(Option::is_none)
+                                || merge.pending()()
+                        }
+                        on:click=move |_| merge.dispatch(())
+                    >
+                        {move || t!("merge.merge")}
+                    </button>
+                </div>
+            </div>
+        </dialog>
+    }
+}
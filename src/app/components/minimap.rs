@@ -0,0 +1,126 @@
+use leptos::*;
+
+use crate::app::session::ImageUrl;
+
+// Pages are stacked with heights proportional to their aspect ratios, so fractions of the
+// summed aspects map directly onto fractions of the scrolled document.
+#[component]
+pub fn Minimap(
+    images: ReadSignal<Vec<ImageUrl>>,
+    #[prop(into)] aspects: Signal<Vec<f64>>,
+    #[prop(into)] visible_pages: Signal<(u32, u32)>,
+    #[prop(into)] on_select: Callback<u32>,
+    #[prop(into)] on_scroll: Callback<f64>,
+) -> impl IntoView {
+    let minimap = create_node_ref::<html::Div>();
+    let dragging = create_rw_signal(None::<(i32, f64)>);
+
+    let indicator = create_memo(move |_| {
+        let (first, last) = visible_pages();
+        aspects.with(|aspects| {
+            let total: f64 = aspects.iter().sum();
+            if total <= 0.0 || first == 0 {
+                return (0.0, 0.0);
+            }
+            let before: f64 = aspects.iter().take(first as usize - 1).sum();
+            let visible: f64 = aspects
+                .iter()
+                .skip(first as usize - 1)
+                .take((last + 1).saturating_sub(first) as usize)
+                .sum();
+            (before / total, visible / total)
+        })
+    });
+
+    let start_drag = move |ev: ev::PointerEvent| {
+        ev.prevent_default();
+        ev.stop_propagation();
+        if let Some(minimap) = minimap.get_untracked() {
+            let _ = minimap.set_pointer_capture(ev.pointer_id());
+        }
+        dragging.set(Some((ev.client_y(), indicator.get_untracked().0)));
+    };
+    let drag = move |ev: ev::PointerEvent| {
+        let Some((start_y, start_top)) = dragging.get_untracked() else {
+            return;
+        };
+        let Some(minimap) = minimap.get_untracked() else {
+            return;
+        };
+        let height = minimap.client_height() as f64;
+        if height <= 0.0 {
+            return;
+        }
+        let top = start_top + (ev.client_y() - start_y) as f64 / height;
+        on_scroll.call(top.clamp(0.0, 1.0));
+    };
+    let stop_drag = move |ev: ev::PointerEvent| {
+        if dragging.get_untracked().is_none() {
+            return;
+        }
+        if let Some(minimap) = minimap.get_untracked() {
+            let _ = minimap.release_pointer_capture(ev.pointer_id());
+        }
+        dragging.set(None);
+    };
+
+    let page_count = move || aspects.with(Vec::len) as u32;
+
+    view! {
+        <div
+            node_ref=minimap
+            class="absolute top-0 right-2 h-full w-12 flex flex-col bg-base-300/60 rounded shadow overflow-hidden"
+            aria-hidden="true"
+            on:pointermove=drag
+            on:pointerup=stop_drag
+            on:pointercancel=stop_drag
+        >
+            <For
+                each=move || 1..=page_count()
+                key=|page| *page
+                children=move |page| {
+                    let aspect = move || {
+                        aspects.with(|aspects| aspects.get(page as usize - 1).copied().unwrap_or(1.0))
+                    };
+                    let url = move || {
+                        images
+                            .with(|urls| {
+                                urls.iter()
+                                    .find(|url| url.page_number == page)
+                                    .map(|url| url.url.clone())
+                            })
+                    };
+                    view! {
+                        <button
+                            class="min-h-0 w-full border-b border-base-100/40 bg-base-200"
+                            style=move || format!("flex: {} 1 0;", aspect())
+                            on:click=move |_| on_select.call(page)
+                        >
+                            {move || {
+                                url()
+                                    .map(|url| {
+                                        view! {
+                                            <img
+                                                src=url
+                                                alt=""
+                                                class="w-full h-full object-cover grayscale"
+                                            />
+                                        }
+                                    })
+                            }}
+                        </button>
+                    }
+                }
+            />
+            <div
+                class="absolute inset-x-0 bg-primary/30 border border-primary cursor-grab"
+                class=("cursor-grabbing", move || dragging().is_some())
+                style=move || {
+                    let (top, height) = indicator();
+                    format!("top: {}%; height: {}%;", top * 100.0, height * 100.0)
+                }
+                on:pointerdown=start_drag
+            ></div>
+        </div>
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use super::toast::{push_toast, use_toasts, ToastKind};
+use crate::app::i18n::{t, use_i18n};
+use crate::app::tauri::{invoke, log_error};
+
+const PREFERRED_LANGUAGE: &str = "por";
+
+#[derive(Serialize, Deserialize)]
+struct OcrPageArgs {
+    path: String,
+    page: u16,
+    lang: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct OcrAllPagesArgs {
+    path: String,
+    lang: String,
+}
+
+#[component]
+pub fn OcrPanel(
+    path: String,
+    #[prop(into)] page_number: Signal<u32>,
+    #[prop(into)] languages: Signal<Vec<String>>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let toasts = use_toasts();
+    let lang = create_rw_signal(String::new());
+    let pages = create_rw_signal(BTreeMap::<(String, u16), String>::new());
+
+    create_effect(move |_| {
+        languages.with(|languages| {
+            if !lang.with_untracked(|lang| languages.contains(lang)) {
+                let preferred = languages
+                    .iter()
+                    .find(|lang| lang.as_str() == PREFERRED_LANGUAGE)
+                    .or_else(|| languages.first())
+                    .cloned()
+                    .unwrap_or_default();
+                lang.set(preferred);
+            }
+        });
+    });
+
+    let page_path = path.clone();
+    let recognize_page = create_action(move |(page, lang): &(u16, String)| {
+        let page = *page;
+        let args = OcrPageArgs {
+            path: page_path.clone(),
+            page,
+            lang: lang.clone(),
+        };
+        async move {
+            let Ok(js_args) = to_value(&args) else {
+                return;
+            };
+            match invoke::<String>("ocr_page", &js_args).await {
+                Ok(text) => pages.update(|pages| {
+                    pages.insert((args.lang, page), text);
+                }),
+                Err(err) => {
+                    log_error(err.to_string());
+                    push_toast(
+                        toasts,
+                        i18n.format("ocr.failed", &[err.to_string()]),
+                        ToastKind::Error,
+                    );
+                }
+            }
+        }
+    });
+
+    let recognize_all = create_action(move |lang: &String| {
+        let args = OcrAllPagesArgs {
+            path: path.clone(),
+            lang: lang.clone(),
+        };
+        async move {
+            let Ok(js_args) = to_value(&args) else {
+                return;
+            };
+            match invoke::<Vec<(u16, String)>>("ocr_all_pages", &js_args).await {
+                Ok(results) => {
+                    pages.update(|pages| {
+                        pages.extend(
+                            results
+                                .into_iter()
+                                .map(|(page, text)| ((args.lang.clone(), page), text)),
+                        );
+                    });
+                    push_toast(toasts, i18n.t("ocr.done"), ToastKind::Success);
+                }
+                Err(err) => {
+                    log_error(err.to_string());
+                    push_toast(
+                        toasts,
+                        i18n.format("ocr.failed", &[err.to_string()]),
+                        ToastKind::Error,
+                    );
+                }
+            }
+        }
+    });
+
+    let current_page = move || u16::try_from(page_number()).unwrap_or(u16::MAX);
+    let current_text = move || pages.with(|pages| pages.get(&(lang(), current_page())).cloned());
+    let busy = move || recognize_page.pending()() || recognize_all.pending()();
+
+    view! {
+        <aside class="absolute top-20 bottom-20 right-4 w-80 flex flex-col gap-2 p-4 bg-base-100 rounded-box shadow text-left">
+            <h2 class="font-semibold">{move || t!("ocr.title")}</h2>
+            <select
+                class="select select-bordered select-sm"
+                prop:value=lang
+                on:change=move |ev| lang.set(event_target_value(&ev))
+            >
+                {move || {
+                    languages()
+                        .into_iter()
+                        .map(|language| view! { <option value=language.clone()>{language}</option> })
+                        .collect_view()
+                }}
+            </select>
+            <div class="join">
+                <button
+                    class="btn btn-sm btn-primary join-item"
+                    disabled=move || busy() || lang.with(String::is_empty)
+                    on:click=move |_| recognize_page.dispatch((current_page(), lang.get_untracked()))
+                >
+                    {move || t!("hero.ocr")}
+                </button>
+                <button
+                    class="btn btn-sm join-item"
+                    disabled=move || busy() || lang.with(String::is_empty)
+                    on:click=move |_| recognize_all.dispatch(lang.get_untracked())
+                >
+                    {move || t!("ocr.all_pages")}
+                </button>
+            </div>
+            <Show when=busy>
+                <span class="loading loading-spinner loading-sm"></span>
+            </Show>
+            <pre class="flex-1 overflow-auto text-xs whitespace-pre-wrap bg-base-200 rounded p-2">
+                {move || {
+                    current_text()
+                        .unwrap_or_else(|| t!("ocr.not_recognized", page_number()))
+                }}
+            </pre>
+        </aside>
+    }
+}
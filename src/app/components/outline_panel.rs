@@ -0,0 +1,72 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use crate::app::tauri::invoke;
+
+#[derive(Serialize, Deserialize)]
+struct DocumentPath {
+    path: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct OutlineEntry {
+    title: String,
+    page: u32,
+    children: Vec<OutlineEntry>,
+}
+
+#[component]
+pub fn OutlinePanel(path: String, page_number: WriteSignal<u32>) -> impl IntoView {
+    let outline = create_resource(
+        move || path.clone(),
+        |path| async move {
+            let Ok(args) = to_value(&DocumentPath { path }) else {
+                return Vec::new();
+            };
+            invoke::<Vec<OutlineEntry>>("extract_outline", &args)
+                .await
+                .unwrap_or_default()
+        },
+    );
+
+    view! {
+        <Suspense fallback=|| ()>
+            {move || {
+                outline
+                    .get()
+                    .filter(|entries| !entries.is_empty())
+                    .map(|entries| {
+                        view! {
+                            <aside class="absolute top-20 bottom-20 left-44 w-64 overflow-y-auto bg-base-100 rounded-box shadow text-left">
+                                <ul class="menu menu-sm">{outline_entries(entries, page_number)}</ul>
+                            </aside>
+                        }
+                    })
+            }}
+
+        </Suspense>
+    }
+}
+
+fn outline_entries(entries: Vec<OutlineEntry>, page_number: WriteSignal<u32>) -> View {
+    entries
+        .into_iter()
+        .map(|entry| {
+            let page = entry.page;
+            let link = view! { <a on:click=move |_| page_number(page)>{entry.title}</a> };
+            if entry.children.is_empty() {
+                view! { <li>{link}</li> }
+            } else {
+                view! {
+                    <li>
+                        <details>
+                            <summary>{link}</summary>
+                            <ul>{outline_entries(entry.children, page_number)}</ul>
+                        </details>
+                    </li>
+                }
+            }
+        })
+        .collect_view()
+}
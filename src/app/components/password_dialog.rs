@@ -0,0 +1,69 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use crate::app::i18n::t;
+use crate::app::tauri::invoke;
+
+#[derive(Serialize, Deserialize)]
+struct DecryptDocumentArgs {
+    path: String,
+    password: String,
+}
+
+#[component]
+pub fn PasswordDialog(path: String, #[prop(into)] on_unlock: Callback<String>) -> impl IntoView {
+    let password = create_rw_signal(String::new());
+    let failed = create_rw_signal(false);
+
+    let decrypt = create_action(move |_: &()| {
+        let args = DecryptDocumentArgs {
+            path: path.clone(),
+            password: password.get_untracked(),
+        };
+        async move {
+            failed.set(false);
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            match invoke::<String>("decrypt_document", &args).await {
+                Ok(decrypted_path) => on_unlock.call(decrypted_path),
+                Err(_) => failed.set(true),
+            }
+        }
+    });
+
+    view! {
+        <div class="hero bg-base-200 flex-1">
+            <div class="hero-content text-center">
+                <form
+                    class="max-w-md flex flex-col gap-4"
+                    on:submit=move |ev| {
+                        ev.prevent_default();
+                        decrypt.dispatch(());
+                    }
+                >
+                    <h1 class="text-2xl font-bold">{move || t!("password.title")}</h1>
+                    <p>{move || t!("password.prompt")}</p>
+                    <input
+                        type="password"
+                        class="input input-bordered"
+                        placeholder=move || t!("password.placeholder")
+                        prop:value=password
+                        on:input=move |ev| password.set(event_target_value(&ev))
+                    />
+                    <Show when=failed>
+                        <p class="text-error">{move || t!("password.incorrect")}</p>
+                    </Show>
+                    <button
+                        type="submit"
+                        class="btn btn-primary"
+                        disabled=move || decrypt.pending()() || password.with(String::is_empty)
+                    >
+                        {move || t!("password.unlock")}
+                    </button>
+                </form>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,62 @@
+use leptos::*;
+use serde::Deserialize;
+use wasm_bindgen::JsValue;
+
+use crate::app::i18n::t;
+use crate::app::tauri::invoke;
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[allow(dead_code)]
+struct RecentDocument {
+    path: String,
+    name: String,
+    last_opened: u64,
+}
+
+#[component]
+pub fn RecentDocuments(#[prop(into)] on_open: Callback<String>) -> impl IntoView {
+    let documents = create_resource(
+        || (),
+        |_| async move {
+            invoke::<Vec<RecentDocument>>("get_recent_documents", &JsValue::default())
+                .await
+                .unwrap_or_default()
+        },
+    );
+
+    view! {
+        <Suspense fallback=|| ()>
+            {move || {
+                documents
+                    .get()
+                    .filter(|documents| !documents.is_empty())
+                    .map(|documents| {
+                        view! {
+                            <div class="mt-8 text-left">
+                                <h2 class="text-lg font-semibold">{t!("recent.title")}</h2>
+                                <ul class="menu bg-base-100 rounded-box">
+                                    {documents
+                                        .into_iter()
+                                        .map(|document| {
+                                            let path = document.path.clone();
+                                            view! {
+                                                <li>
+                                                    <a
+                                                        title=document.path
+                                                        on:click=move |_| on_open.call(path.clone())
+                                                    >
+                                                        {document.name}
+                                                    </a>
+                                                </li>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </ul>
+                            </div>
+                        }
+                    })
+            }}
+
+        </Suspense>
+    }
+}
@@ -0,0 +1,253 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use super::toast::{push_toast, use_toasts, ToastKind};
+use crate::app::i18n::{t, use_i18n};
+use crate::app::tauri::{invoke, log_error};
+
+const MIN_REGION_SIZE: f32 = 0.005;
+
+#[derive(Serialize, Deserialize)]
+struct DocumentPath {
+    path: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveFileArgs {
+    filter_name: String,
+    extensions: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RedactRegionArgs {
+    path: String,
+    page: u16,
+    regions: Vec<[f32; 4]>,
+    output: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct PageDimensions {
+    page: u32,
+    width_pt: f32,
+    height_pt: f32,
+    origin_x_pt: f32,
+    origin_y_pt: f32,
+    rotation: u16,
+}
+
+// Regions are kept as (x, y, width, height) in a unit square with the origin at the
+// top left, matching the other page overlays, and converted to PDF points on apply.
+type Region = (f32, f32, f32, f32);
+
+#[component]
+pub fn RedactionLayer(
+    path: String,
+    #[prop(into)] page: Signal<u32>,
+    #[prop(into)] on_done: Callback<()>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let toasts = use_toasts();
+    let overlay = create_node_ref::<html::Div>();
+    let regions = create_rw_signal(Vec::<Region>::new());
+    let drag = create_rw_signal(None::<((f32, f32), (f32, f32))>);
+
+    let dimensions_path = path.clone();
+    let dimensions = create_resource(
+        move || dimensions_path.clone(),
+        |path| async move {
+            let Ok(args) = to_value(&DocumentPath { path }) else {
+                return Vec::new();
+            };
+            invoke::<Vec<PageDimensions>>("get_page_dimensions", &args)
+                .await
+                .unwrap_or_default()
+        },
+    );
+
+    create_effect(move |previous: Option<u32>| {
+        let current = page();
+        if previous.is_some_and(|previous| previous != current) {
+            regions.set(Vec::new());
+        }
+        current
+    });
+
+    let unit_point = move |ev: &ev::PointerEvent| {
+        let overlay = overlay.get_untracked()?;
+        let (width, height) = (overlay.client_width(), overlay.client_height());
+        if width == 0 || height == 0 {
+            return None;
+        }
+        Some((
+            (ev.offset_x() as f32 / width as f32).clamp(0.0, 1.0),
+            (ev.offset_y() as f32 / height as f32).clamp(0.0, 1.0),
+        ))
+    };
+    let start_drag = move |ev: ev::PointerEvent| {
+        ev.prevent_default();
+        if let Some(point) = unit_point(&ev) {
+            drag.set(Some((point, point)));
+        }
+    };
+    let move_drag = move |ev: ev::PointerEvent| {
+        if drag.with_untracked(Option::is_none) {
+            return;
+        }
+        if let Some(point) = unit_point(&ev) {
+            drag.update(|drag| {
+                if let Some((_, end)) = drag {
+                    *end = point;
+                }
+            });
+        }
+    };
+    let finish_drag = move |_: ev::PointerEvent| {
+        let Some(region) = drag
+            .get_untracked()
+            .map(|(start, end)| to_region(start, end))
+        else {
+            return;
+        };
+        drag.set(None);
+        if region.2 >= MIN_REGION_SIZE && region.3 >= MIN_REGION_SIZE {
+            regions.update(|regions| regions.push(region));
+        }
+    };
+
+    let apply = create_action(move |_: &()| {
+        let path = path.clone();
+        let page = page.get_untracked();
+        let marked = regions.get_untracked();
+        let page_dimensions = dimensions.with_untracked(|dimensions| {
+            dimensions
+                .as_ref()?
+                .iter()
+                .find(|dimensions| dimensions.page == page)
+                .cloned()
+        });
+        async move {
+            let (Some(page_dimensions), Ok(page)) = (page_dimensions, u16::try_from(page)) else {
+                return;
+            };
+            let save_args = SaveFileArgs {
+                filter_name: "PDF".to_string(),
+                extensions: vec!["pdf".to_string()],
+            };
+            let Ok(save_args) = to_value(&save_args) else {
+                return;
+            };
+            let Ok(output) = invoke::<String>("select_save_file", &save_args).await else {
+                return;
+            };
+
+            let regions_pt = marked
+                .into_iter()
+                .map(|region| to_page_points(region, &page_dimensions))
+                .collect();
+            let Ok(args) = to_value(&RedactRegionArgs {
+                path,
+                page,
+                regions: regions_pt,
+                output,
+            }) else {
+                return;
+            };
+            match invoke::<()>("redact_region", &args).await {
+                Ok(()) => {
+                    push_toast(toasts, i18n.t("redaction.saved"), ToastKind::Success);
+                    regions.set(Vec::new());
+                    on_done.call(());
+                }
+                Err(err) => {
+                    log_error(err.to_string());
+                    push_toast(
+                        toasts,
+                        i18n.format("redaction.failed", &[err.to_string()]),
+                        ToastKind::Error,
+                    );
+                }
+            }
+        }
+    });
+
+    let shapes = move || {
+        let mut shapes = regions();
+        shapes.extend(drag().map(|(start, end)| to_region(start, end)));
+        shapes
+    };
+
+    view! {
+        <div
+            node_ref=overlay
+            class="absolute inset-0 cursor-crosshair"
+            on:pointerdown=start_drag
+            on:pointermove=move_drag
+            on:pointerup=finish_drag
+            on:pointerleave=finish_drag
+        >
+            <svg
+                class="w-full h-full pointer-events-none"
+                viewBox="0 0 1 1"
+                preserveAspectRatio="none"
+            >
+                {move || {
+                    shapes()
+                        .into_iter()
+                        .map(|(x, y, width, height)| {
+                            view! {
+                                <rect x=x y=y width=width height=height fill="black" fill-opacity="0.7"></rect>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </svg>
+        </div>
+        <div class="absolute bottom-2 left-1/2 -translate-x-1/2 join shadow">
+            <button
+                class="btn btn-sm btn-error join-item"
+                disabled=move || regions.with(Vec::is_empty) || apply.pending()()
+                on:click=move |_| apply.dispatch(())
+            >
+                {move || t!("redaction.apply")}
+            </button>
+            <button
+                class="btn btn-sm join-item"
+                disabled=move || regions.with(Vec::is_empty)
+                on:click=move |_| regions.set(Vec::new())
+            >
+                {move || t!("common.clear")}
+            </button>
+            <button class="btn btn-sm join-item" on:click=move |_| on_done.call(())>
+                {move || t!("common.cancel")}
+            </button>
+        </div>
+    }
+}
+
+// The rendered image shows the page turned clockwise by `/Rotate`, so the region is turned
+// back before it is scaled to the MediaBox, whose y axis points up from its own origin.
+fn to_page_points((x, y, width, height): Region, dimensions: &PageDimensions) -> [f32; 4] {
+    let unrotate = |(x, y): (f32, f32)| match dimensions.rotation {
+        90 => (y, 1.0 - x),
+        180 => (1.0 - x, 1.0 - y),
+        270 => (1.0 - y, x),
+        _ => (x, y),
+    };
+    let (x0, y0) = unrotate((x, y));
+    let (x1, y1) = unrotate((x + width, y + height));
+    let (left, right) = (x0.min(x1), x0.max(x1));
+    let (top, bottom) = (y0.min(y1), y0.max(y1));
+    [
+        dimensions.origin_x_pt + left * dimensions.width_pt,
+        dimensions.origin_y_pt + (1.0 - bottom) * dimensions.height_pt,
+        (right - left) * dimensions.width_pt,
+        (bottom - top) * dimensions.height_pt,
+    ]
+}
+
+fn to_region((x0, y0): (f32, f32), (x1, y1): (f32, f32)) -> Region {
+    (x0.min(x1), y0.min(y1), (x1 - x0).abs(), (y1 - y0).abs())
+}
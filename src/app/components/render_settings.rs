@@ -0,0 +1,50 @@
+use leptos::*;
+
+use crate::app::i18n::t;
+use crate::app::session::RenderOptions;
+
+#[component]
+pub fn RenderSettingsPanel(
+    #[prop(into)] options: Signal<RenderOptions>,
+    #[prop(into)] on_change: Callback<RenderOptions>,
+) -> impl IntoView {
+    let toggle_deskew = move |_| {
+        let mut options = options.get_untracked();
+        options.deskew = !options.deskew;
+        on_change.call(options);
+    };
+    let toggle_skip_blank_pages = move |_| {
+        let mut options = options.get_untracked();
+        options.skip_blank_pages = !options.skip_blank_pages;
+        on_change.call(options);
+    };
+
+    view! {
+        <aside class="absolute top-20 right-4 w-80 card bg-base-100 shadow-xl">
+            <div class="card-body">
+                <h2 class="card-title">{move || t!("render.title")}</h2>
+                <label class="label cursor-pointer">
+                    <span class="label-text">{move || t!("render.deskew")}</span>
+                    <input
+                        type="checkbox"
+                        class="toggle toggle-primary"
+                        prop:checked=move || options.with(|options| options.deskew)
+                        on:change=toggle_deskew
+                    />
+                </label>
+                <label class="label cursor-pointer">
+                    <span class="label-text">{move || t!("render.skip_blank_pages")}</span>
+                    <input
+                        type="checkbox"
+                        class="toggle toggle-primary"
+                        prop:checked=move || options.with(|options| options.skip_blank_pages)
+                        on:change=toggle_skip_blank_pages
+                    />
+                </label>
+                <p class="text-xs opacity-70">
+                    {move || t!("render.rerender_notice")}
+                </p>
+            </div>
+        </aside>
+    }
+}
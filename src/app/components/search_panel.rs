@@ -0,0 +1,225 @@
+use std::time::Duration;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use crate::app::i18n::t;
+use crate::app::tauri::{invoke, log_error};
+
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchDocumentArgs {
+    document_id: String,
+    query: String,
+    case_sensitive: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DocumentPath {
+    path: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SearchMatch {
+    pub page: u32,
+    pub snippet: String,
+    pub match_start: usize,
+    pub match_end: usize,
+    pub bbox: Option<[f32; 4]>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct PageDimensions {
+    page: u32,
+    width_pt: f32,
+    height_pt: f32,
+}
+
+#[component]
+pub fn SearchPanel(
+    #[prop(into)] document_id: Signal<Option<String>>,
+    page_number: WriteSignal<u32>,
+    matches: RwSignal<Vec<SearchMatch>>,
+) -> impl IntoView {
+    let query = create_rw_signal(String::new());
+    let case_sensitive = create_rw_signal(false);
+    let searching = create_rw_signal(false);
+    let search_timeout = store_value(None::<TimeoutHandle>);
+    let generation = store_value(0usize);
+
+    create_effect(move |_| {
+        let query = query();
+        let case_sensitive = case_sensitive();
+        if let Some(handle) = search_timeout.get_value() {
+            handle.clear();
+        }
+        generation.update_value(|generation| *generation += 1);
+        let Some(document_id) = document_id().filter(|_| !query.trim().is_empty()) else {
+            searching.set(false);
+            matches.set(Vec::new());
+            return;
+        };
+
+        let handle = set_timeout_with_handle(
+            move || {
+                let current = generation.get_value();
+                let args = SearchDocumentArgs {
+                    document_id,
+                    query,
+                    case_sensitive,
+                };
+                searching.set(true);
+                spawn_local(async move {
+                    let Ok(args) = to_value(&args) else {
+                        return;
+                    };
+                    let result = invoke::<Vec<SearchMatch>>("search_document", &args).await;
+                    if generation.get_value() != current {
+                        return;
+                    }
+                    searching.set(false);
+                    match result {
+                        Ok(results) => matches.set(results),
+                        Err(err) => log_error(err.to_string()),
+                    }
+                });
+            },
+            SEARCH_DEBOUNCE,
+        );
+        search_timeout.set_value(handle.ok());
+    });
+    on_cleanup(move || {
+        if let Some(handle) = search_timeout.get_value() {
+            handle.clear();
+        }
+        matches.set(Vec::new());
+    });
+
+    view! {
+        <aside class="absolute top-20 bottom-20 right-4 w-80 flex flex-col gap-2 p-4 bg-base-100 rounded-box shadow text-left">
+            <input
+                type="search"
+                class="input input-bordered input-sm"
+                placeholder=move || t!("hero.search_label")
+                prop:value=query
+                on:input=move |ev| query.set(event_target_value(&ev))
+            />
+            <label class="label cursor-pointer justify-start gap-2">
+                <input
+                    type="checkbox"
+                    class="checkbox checkbox-sm"
+                    prop:checked=case_sensitive
+                    on:change=move |ev| case_sensitive.set(event_target_checked(&ev))
+                />
+                <span class="label-text">{move || t!("search.case_sensitive")}</span>
+            </label>
+            <span class="text-sm">
+                {move || {
+                    if searching() {
+                        t!("search.searching")
+                    } else if query.with(|query| query.trim().is_empty()) {
+                        String::new()
+                    } else {
+                        t!("search.results", matches.with(Vec::len))
+                    }
+                }}
+            </span>
+            <ul class="menu menu-sm flex-nowrap overflow-y-auto">
+                {move || {
+                    matches()
+                        .into_iter()
+                        .map(|result| {
+                            let page = result.page;
+                            let (before, rest) = result.snippet.split_at(result.match_start);
+                            let (matched, after) = rest.split_at(result.match_end - result.match_start);
+                            view! {
+                                <li>
+                                    <a class="flex flex-col items-start" on:click=move |_| page_number(page)>
+                                        <span class="font-semibold">{t!("hero.page", page)}</span>
+                                        <span class="text-xs">
+                                            {before.to_string()}
+                                            <mark>{matched.to_string()}</mark>
+                                            {after.to_string()}
+                                        </span>
+                                    </a>
+                                </li>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </ul>
+        </aside>
+    }
+}
+
+#[component]
+pub fn SearchHighlightLayer(
+    path: String,
+    #[prop(into)] page: Signal<u32>,
+    #[prop(into)] matches: Signal<Vec<SearchMatch>>,
+) -> impl IntoView {
+    let dimensions = create_resource(
+        move || path.clone(),
+        |path| async move {
+            let Ok(args) = to_value(&DocumentPath { path }) else {
+                return Vec::new();
+            };
+            invoke::<Vec<PageDimensions>>("get_page_dimensions", &args)
+                .await
+                .unwrap_or_default()
+        },
+    );
+
+    // Bounding boxes are in PDF points with the origin at the bottom left,
+    // while the overlay uses a unit square with the origin at the top left.
+    let highlights = move || {
+        let page = page();
+        let Some((width, height)) = dimensions.with(|dimensions| {
+            dimensions
+                .as_ref()?
+                .iter()
+                .find(|dimensions| dimensions.page == page)
+                .filter(|dimensions| dimensions.width_pt > 0.0 && dimensions.height_pt > 0.0)
+                .map(|dimensions| (dimensions.width_pt, dimensions.height_pt))
+        }) else {
+            return Vec::new();
+        };
+        matches.with(|matches| {
+            matches
+                .iter()
+                .filter(|result| result.page == page)
+                .filter_map(|result| result.bbox)
+                .map(|[x0, y0, x1, y1]| {
+                    (
+                        x0 / width,
+                        1.0 - y1 / height,
+                        (x1 - x0) / width,
+                        (y1 - y0) / height,
+                    )
+                })
+                .collect()
+        })
+    };
+
+    view! {
+        <svg
+            class="absolute inset-0 w-full h-full pointer-events-none"
+            viewBox="0 0 1 1"
+            preserveAspectRatio="none"
+        >
+            {move || {
+                highlights()
+                    .into_iter()
+                    .map(|(x, y, width, height)| {
+                        view! {
+                            <rect x=x y=y width=width height=height fill="yellow" fill-opacity="0.4"></rect>
+                        }
+                    })
+                    .collect_view()
+            }}
+        </svg>
+    }
+}
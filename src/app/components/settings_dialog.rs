@@ -0,0 +1,296 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::JsValue;
+
+use super::theme_toggle::Theme;
+use crate::app::i18n::{t, use_i18n, Locale};
+use crate::app::tauri::invoke;
+
+const JPEG_QUALITY: u8 = 90;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum ImageFormat {
+    WebP,
+    Png,
+    Jpeg { quality: u8 },
+}
+
+impl ImageFormat {
+    fn value(&self) -> &'static str {
+        match self {
+            ImageFormat::WebP => "webp",
+            ImageFormat::Png => "png",
+            ImageFormat::Jpeg { .. } => "jpeg",
+        }
+    }
+
+    fn parse(value: &str) -> Self {
+        match value {
+            "png" => ImageFormat::Png,
+            "jpeg" => ImageFormat::Jpeg {
+                quality: JPEG_QUALITY,
+            },
+            _ => ImageFormat::WebP,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AppSettings {
+    density: u32,
+    format: ImageFormat,
+    resize: String,
+    cache_dir: Option<String>,
+    max_parallel_renders: usize,
+    magick_binary: Option<String>,
+    pub theme: Option<String>,
+    locale: Option<String>,
+    display_mode: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SaveSettingsArgs {
+    settings: AppSettings,
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[component]
+pub fn SettingsDialog(
+    theme: RwSignal<Theme>,
+    #[prop(into)] on_close: Callback<()>,
+) -> impl IntoView {
+    let i18n = use_i18n();
+    let settings = create_rw_signal(None::<AppSettings>);
+    let error = create_rw_signal(None::<String>);
+
+    spawn_local(async move {
+        match invoke::<AppSettings>("get_settings", &JsValue::default()).await {
+            Ok(loaded) => settings.set(Some(loaded)),
+            Err(err) => error.set(Some(err.to_string())),
+        }
+    });
+
+    let update = move |apply: fn(&mut AppSettings, String)| {
+        move |ev: ev::Event| {
+            let value = event_target_value(&ev);
+            settings.update(|settings| {
+                if let Some(settings) = settings {
+                    apply(settings, value);
+                }
+            });
+        }
+    };
+
+    let pick_cache_dir = create_action(move |_: &()| async move {
+        if let Ok(dir) = invoke::<String>("select_directory", &JsValue::default()).await {
+            settings.update(|settings| {
+                if let Some(settings) = settings {
+                    settings.cache_dir = Some(dir);
+                }
+            });
+        }
+    });
+
+    let save = create_action(move |_: &()| async move {
+        let Some(current) = settings.get_untracked() else {
+            return;
+        };
+        let Ok(args) = to_value(&SaveSettingsArgs {
+            settings: current.clone(),
+        }) else {
+            return;
+        };
+        match invoke::<()>("save_settings", &args).await {
+            Ok(()) => {
+                theme.set(
+                    current
+                        .theme
+                        .as_deref()
+                        .and_then(Theme::parse)
+                        .unwrap_or_else(Theme::system),
+                );
+                on_close.call(());
+            }
+            Err(err) => error.set(Some(err.to_string())),
+        }
+    });
+
+    let reset = create_action(move |_: &()| async move {
+        match invoke::<AppSettings>("reset_settings", &JsValue::default()).await {
+            Ok(defaults) => {
+                theme.set(Theme::system());
+                i18n.change_locale(Locale::default());
+                settings.set(Some(defaults));
+                error.set(None);
+            }
+            Err(err) => error.set(Some(err.to_string())),
+        }
+    });
+
+    let field = move |read: fn(&AppSettings) -> String| {
+        move || settings.with(|settings| settings.as_ref().map(read).unwrap_or_default())
+    };
+
+    view! {
+        <dialog class="modal modal-open">
+            <div class="modal-box text-left">
+                <h3 class="font-bold text-lg">{move || t!("settings.title")}</h3>
+                <p class="text-sm opacity-70">{move || t!("settings.next_document")}</p>
+                <Show
+                    when=move || settings.with(Option::is_some)
+                    fallback=|| view! { <span class="loading loading-spinner"></span> }
+                >
+                    <div class="flex flex-col gap-3 py-4">
+                        <label class="form-control">
+                            <span class="label-text">{move || t!("settings.density")}</span>
+                            <input
+                                type="number"
+                                min="1"
+                                class="input input-bordered input-sm"
+                                prop:value=field(|settings| settings.density.to_string())
+                                on:change=update(|settings, value| {
+                                    if let Ok(density) = value.parse() {
+                                        settings.density = density;
+                                    }
+                                })
+                            />
+                        </label>
+                        <label class="form-control">
+                            <span class="label-text">{move || t!("settings.format")}</span>
+                            <select
+                                class="select select-bordered select-sm"
+                                prop:value=field(|settings| settings.format.value().to_string())
+                                on:change=update(|settings, value| {
+                                    settings.format = ImageFormat::parse(&value);
+                                })
+                            >
+                                <option value="webp">"WebP"</option>
+                                <option value="png">"PNG"</option>
+                                <option value="jpeg">"JPEG"</option>
+                            </select>
+                        </label>
+                        <label class="form-control">
+                            <span class="label-text">{move || t!("settings.resize")}</span>
+                            <input
+                                type="text"
+                                class="input input-bordered input-sm"
+                                prop:value=field(|settings| settings.resize.clone())
+                                on:change=update(|settings, value| settings.resize = value)
+                            />
+                        </label>
+                        <label class="form-control">
+                            <span class="label-text">{move || t!("settings.parallel_renders")}</span>
+                            <input
+                                type="number"
+                                min="1"
+                                class="input input-bordered input-sm"
+                                prop:value=field(|settings| settings.max_parallel_renders.to_string())
+                                on:change=update(|settings, value| {
+                                    if let Ok(renders) = value.parse() {
+                                        settings.max_parallel_renders = renders;
+                                    }
+                                })
+                            />
+                        </label>
+                        <div class="form-control">
+                            <span class="label-text">{move || t!("settings.cache_dir")}</span>
+                            <div class="flex items-center gap-2">
+                                <input
+                                    type="text"
+                                    class="input input-bordered input-sm flex-1"
+                                    placeholder=move || t!("settings.cache_dir_default")
+                                    prop:value=field(|settings| {
+                                        settings.cache_dir.clone().unwrap_or_default()
+                                    })
+                                    on:change=update(|settings, value| {
+                                        settings.cache_dir = non_empty(value);
+                                    })
+                                />
+                                <button class="btn btn-sm" on:click=move |_| pick_cache_dir.dispatch(())>
+                                    {move || t!("common.choose")}
+                                </button>
+                            </div>
+                        </div>
+                        <label class="form-control">
+                            <span class="label-text">{move || t!("settings.magick_binary")}</span>
+                            <input
+                                type="text"
+                                class="input input-bordered input-sm"
+                                placeholder=move || t!("settings.magick_binary_auto")
+                                prop:value=field(|settings| {
+                                    settings.magick_binary.clone().unwrap_or_default()
+                                })
+                                on:change=update(|settings, value| {
+                                    settings.magick_binary = non_empty(value);
+                                })
+                            />
+                        </label>
+                        <label class="form-control">
+                            <span class="label-text">{move || t!("settings.theme")}</span>
+                            <select
+                                class="select select-bordered select-sm"
+                                prop:value=field(|settings| settings.theme.clone().unwrap_or_default())
+                                on:change=update(|settings, value| settings.theme = non_empty(value))
+                            >
+                                <option value="">{move || t!("settings.theme_system")}</option>
+                                <option value="light">{move || t!("settings.theme_light")}</option>
+                                <option value="dark">{move || t!("settings.theme_dark")}</option>
+                            </select>
+                        </label>
+                        <label class="form-control">
+                            <span class="label-text">{move || t!("settings.language")}</span>
+                            <select
+                                class="select select-bordered select-sm"
+                                prop:value=move || i18n.locale.get().code()
+                                on:change=move |ev| {
+                                    let Some(locale) = Locale::parse(&event_target_value(&ev)) else {
+                                        return;
+                                    };
+                                    settings.update(|settings| {
+                                        if let Some(settings) = settings {
+                                            settings.locale = Some(locale.code().to_string());
+                                        }
+                                    });
+                                    i18n.change_locale(locale);
+                                }
+                            >
+                                {Locale::ALL
+                                    .into_iter()
+                                    .map(|locale| {
+                                        view! { <option value=locale.code()>{locale.label()}</option> }
+                                    })
+                                    .collect_view()}
+                            </select>
+                        </label>
+                    </div>
+                </Show>
+                {move || error().map(|error| view! { <p class="text-error text-sm">{error}</p> })}
+                <div class="modal-action">
+                    <button
+                        class="btn btn-ghost mr-auto"
+                        disabled=move || reset.pending()()
+                        on:click=move |_| reset.dispatch(())
+                    >
+                        {move || t!("settings.restore_defaults")}
+                    </button>
+                    <button class="btn" on:click=move |_| on_close.call(())>
+                        {move || t!("common.cancel")}
+                    </button>
+                    <button
+                        class="btn btn-primary"
+                        disabled=move || settings.with(Option::is_none) || save.pending()()
+                        on:click=move |_| save.dispatch(())
+                    >
+                        {move || t!("common.save")}
+                    </button>
+                </div>
+            </div>
+        </dialog>
+    }
+}
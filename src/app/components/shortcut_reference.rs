@@ -0,0 +1,75 @@
+use leptos::*;
+
+use crate::app::i18n::t;
+use crate::app::shortcuts::{is_typing, shortcut_action, ShortcutAction, SHORTCUTS};
+
+// Rendered through a portal so the viewer's `overflow: hidden` container can't clip it.
+#[component]
+pub fn ShortcutReference() -> impl IntoView {
+    let open = create_rw_signal(false);
+
+    let toggle_reference = window_event_listener(ev::keydown, move |ev| {
+        if open.get_untracked() && ev.key() == "Escape" {
+            ev.prevent_default();
+            open.set(false);
+            return;
+        }
+        if is_typing(&ev) || shortcut_action(&ev) != Some(ShortcutAction::ShortcutReference) {
+            return;
+        }
+        ev.prevent_default();
+        open.update(|open| *open = !*open);
+    });
+    on_cleanup(move || toggle_reference.remove());
+
+    view! {
+        <Show when=open>
+            <Portal>
+                <div
+                    class="fixed inset-0 z-[100] flex items-center justify-center bg-base-300/80"
+                    role="dialog"
+                    aria-modal="true"
+                    aria-label=move || t!("shortcuts.title")
+                    on:click=move |_| open.set(false)
+                >
+                    <div
+                        class="bg-base-100 rounded-box shadow-xl p-6 max-w-3xl w-full max-h-[90vh] overflow-y-auto"
+                        on:click=|ev| ev.stop_propagation()
+                    >
+                        <div class="flex items-center justify-between mb-4">
+                            <h2 class="text-xl font-bold">{move || t!("shortcuts.title")}</h2>
+                            <button
+                                class="btn btn-sm btn-ghost"
+                                aria-label=move || t!("common.close")
+                                on:click=move |_| open.set(false)
+                            >
+                                "✕"
+                            </button>
+                        </div>
+                        <dl class="grid grid-cols-1 md:grid-cols-2 gap-x-8 gap-y-2">
+                            {SHORTCUTS
+                                .iter()
+                                .map(|shortcut| {
+                                    view! {
+                                        <div class="flex items-center justify-between gap-4">
+                                            <dt class="text-sm">{move || t!(shortcut.description)}</dt>
+                                            <dd class="flex flex-wrap gap-1 justify-end">
+                                                {shortcut
+                                                    .bindings
+                                                    .iter()
+                                                    .map(|binding| {
+                                                        view! { <kbd class="kbd kbd-sm">{binding.label()}</kbd> }
+                                                    })
+                                                    .collect_view()}
+                                            </dd>
+                                        </div>
+                                    }
+                                })
+                                .collect_view()}
+                        </dl>
+                    </div>
+                </div>
+            </Portal>
+        </Show>
+    }
+}
@@ -0,0 +1,122 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::JsValue;
+
+use crate::app::i18n::t;
+use crate::app::tauri::invoke;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SplitDocumentArgs {
+    path: String,
+    split_after_page: u32,
+    output_dir: String,
+}
+
+#[component]
+pub fn SplitDialog(
+    path: String,
+    #[prop(into)] total_pages: Signal<u32>,
+    #[prop(into)] on_close: Callback<()>,
+) -> impl IntoView {
+    let split_after_page = create_rw_signal(1u32);
+    let output_dir = create_rw_signal(None::<String>);
+    let result = create_rw_signal(None::<Result<(String, String), String>>);
+
+    let valid_page = move || {
+        let page = split_after_page();
+        page >= 1 && page < total_pages()
+    };
+
+    let pick_output_dir = create_action(move |_: &()| async move {
+        if let Ok(dir) = invoke::<String>("select_directory", &JsValue::default()).await {
+            output_dir.set(Some(dir));
+        }
+    });
+
+    let split = create_action(move |_: &()| {
+        let path = path.clone();
+        async move {
+            let Some(output_dir) = output_dir.get_untracked() else {
+                return;
+            };
+            let args = SplitDocumentArgs {
+                path,
+                split_after_page: split_after_page.get_untracked(),
+                output_dir,
+            };
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            let outcome = invoke::<(String, String)>("split_document", &args)
+                .await
+                .map_err(|err| err.to_string());
+            result.set(Some(outcome));
+        }
+    });
+
+    view! {
+        <dialog class="modal modal-open">
+            <div class="modal-box text-left">
+                <h3 class="font-bold text-lg">{move || t!("split.title")}</h3>
+                <div class="flex flex-col gap-4 py-4">
+                    <label class="form-control">
+                        <span class="label-text">{move || t!("split.after_page")}</span>
+                        <input
+                            type="number"
+                            class="input input-bordered input-sm w-24"
+                            min="1"
+                            max=move || total_pages().saturating_sub(1).to_string()
+                            prop:value=move || split_after_page().to_string()
+                            on:input=move |ev| {
+                                split_after_page.set(event_target_value(&ev).parse().unwrap_or(1))
+                            }
+                        />
+                    </label>
+                    <div class="flex items-center gap-2">
+                        <button class="btn btn-sm" on:click=move |_| pick_output_dir.dispatch(())>
+                            {move || t!("common.choose_folder")}
+                        </button>
+                        <span class="text-sm truncate">
+                            {move || output_dir().unwrap_or_else(|| t!("common.no_folder_selected"))}
+                        </span>
+                    </div>
+                    <Show when=move || !valid_page()>
+                        <p class="text-error">
+                            {move || t!("split.invalid_page", total_pages().saturating_sub(1))}
+                        </p>
+                    </Show>
+                    {move || match result() {
+                        Some(Ok((first, second))) => {
+                            view! {
+                                <div class="text-sm">
+                                    <p class="text-success">{t!("split.done")}</p>
+                                    <p>{first}</p>
+                                    <p>{second}</p>
+                                </div>
+                            }
+                                .into_view()
+                        }
+                        Some(Err(err)) => view! { <p class="text-error">{err}</p> }.into_view(),
+                        None => ().into_view(),
+                    }}
+                </div>
+                <div class="modal-action">
+                    <button class="btn" on:click=move |_| on_close.call(())>
+                        {move || t!("common.close")}
+                    </button>
+                    <button
+                        class="btn btn-primary"
+                        disabled=move || {
+                            !valid_page() || output_dir.with(Option::is_none) || split.pending()()
+                        }
+                        on:click=move |_| split.dispatch(())
+                    >
+                        {move || t!("split.split")}
+                    </button>
+                </div>
+            </div>
+        </dialog>
+    }
+}
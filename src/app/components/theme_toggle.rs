@@ -0,0 +1,114 @@
+use anyhow::anyhow;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::JsValue;
+
+use super::settings_dialog::AppSettings;
+use crate::app::i18n::t;
+use crate::app::shortcuts::{is_typing, shortcut_action, ShortcutAction};
+use crate::app::tauri::{invoke, log_warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "light" => Some(Theme::Light),
+            "dark" => Some(Theme::Dark),
+            _ => None,
+        }
+    }
+
+    fn toggled(&self) -> Self {
+        match self {
+            Theme::Light => Theme::Dark,
+            Theme::Dark => Theme::Light,
+        }
+    }
+
+    pub fn system() -> Self {
+        let prefers_dark = window()
+            .match_media("(prefers-color-scheme: dark)")
+            .ok()
+            .flatten()
+            .is_some_and(|query| query.matches());
+        match prefers_dark {
+            true => Theme::Dark,
+            false => Theme::Light,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SaveSettingsArgs {
+    settings: AppSettings,
+}
+
+async fn save_theme(theme: Theme) -> anyhow::Result<()> {
+    let mut settings = invoke::<AppSettings>("get_settings", &JsValue::default()).await?;
+    settings.theme = Some(theme.as_str().to_string());
+    let args = to_value(&SaveSettingsArgs { settings }).map_err(|err| anyhow!("{:?}", err))?;
+    invoke::<()>("save_settings", &args).await
+}
+
+#[component]
+pub fn ThemeToggle(theme: RwSignal<Theme>) -> impl IntoView {
+    spawn_local(async move {
+        if let Ok(settings) = invoke::<AppSettings>("get_settings", &JsValue::default()).await {
+            if let Some(saved) = settings.theme.as_deref().and_then(Theme::parse) {
+                theme.set(saved);
+            }
+        }
+    });
+
+    create_effect(move |_| {
+        if let Some(root) = document().document_element() {
+            let _ = root.set_attribute("data-theme", theme().as_str());
+        }
+    });
+
+    let toggle = move || {
+        let next = theme.get_untracked().toggled();
+        theme.set(next);
+        spawn_local(async move {
+            if let Err(err) = save_theme(next).await {
+                log_warn(&format!("Failed to save theme preference: {}", err));
+            }
+        });
+    };
+    let theme_shortcut = window_event_listener(ev::keydown, move |ev| {
+        if is_typing(&ev) || shortcut_action(&ev) != Some(ShortcutAction::ToggleTheme) {
+            return;
+        }
+        ev.prevent_default();
+        toggle();
+    });
+    on_cleanup(move || theme_shortcut.remove());
+    view! {
+        <button
+            class="btn btn-ghost btn-sm"
+            title=move || match theme() {
+                Theme::Light => t!("theme.dark"),
+                Theme::Dark => t!("theme.light"),
+            }
+            on:click=move |_| toggle()
+        >
+            {move || match theme() {
+                Theme::Light => "☾",
+                Theme::Dark => "☀",
+            }}
+        </button>
+    }
+}
@@ -0,0 +1,214 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use super::document_info::{format_reading_time, DocumentStatistics};
+use crate::app::i18n::t;
+use crate::app::session::ImageUrl;
+use crate::app::tauri::invoke;
+
+#[derive(Serialize, Deserialize)]
+struct DocumentPath {
+    path: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveFileArgs {
+    filter_name: String,
+    extensions: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReorderPagesArgs {
+    path: String,
+    new_order: Vec<u32>,
+    output: String,
+}
+
+#[component]
+pub fn ThumbnailStrip(
+    path: String,
+    images: ReadSignal<Vec<ImageUrl>>,
+    current_page: ReadSignal<u32>,
+    page_number: WriteSignal<u32>,
+    #[prop(into)] total_pages: Signal<u32>,
+    #[prop(optional, into)] on_select: Option<Callback<u32>>,
+) -> impl IntoView {
+    let order = create_rw_signal(None::<Vec<u32>>);
+    let dragged = create_rw_signal(None::<u32>);
+    let error = create_rw_signal(None::<String>);
+
+    let statistics_path = path.clone();
+    let statistics = create_resource(
+        move || statistics_path.clone(),
+        |path| async move {
+            let args = to_value(&DocumentPath { path }).ok()?;
+            invoke::<DocumentStatistics>("get_document_statistics", &args)
+                .await
+                .ok()
+        },
+    );
+    let tooltip = move |page: u32| {
+        let page_statistics = statistics.with(|statistics| {
+            statistics
+                .as_ref()?
+                .as_ref()?
+                .pages
+                .iter()
+                .find(|(number, _)| *number == page)
+                .map(|(_, statistics)| *statistics)
+        });
+        match page_statistics {
+            Some(statistics) => t!(
+                "thumbnails.page_statistics",
+                page,
+                statistics.word_count,
+                statistics.char_count,
+                format_reading_time(statistics.estimated_reading_seconds)
+            ),
+            None => t!("hero.page", page),
+        }
+    };
+
+    let pages = move || {
+        order().unwrap_or_else(|| {
+            images.with(|images| images.iter().map(|image| image.page_number).collect())
+        })
+    };
+    let image_url = move |page: u32| {
+        images.with(|images| {
+            images
+                .iter()
+                .find(|image| image.page_number == page)
+                .map(|image| image.url.clone())
+        })
+    };
+
+    let move_before = move |target: u32| {
+        let Some(source) = dragged.get_untracked() else {
+            return;
+        };
+        dragged.set(None);
+        if source == target {
+            return;
+        }
+        order.update(|order| {
+            let order = order.get_or_insert_with(|| (1..=total_pages.get_untracked()).collect());
+            let Some(from) = order.iter().position(|page| *page == source) else {
+                return;
+            };
+            let page = order.remove(from);
+            let to = order
+                .iter()
+                .position(|page| *page == target)
+                .unwrap_or(order.len());
+            order.insert(to, page);
+        });
+    };
+
+    let save_order = create_action(move |_: &()| {
+        let path = path.clone();
+        async move {
+            let Some(new_order) = order.get_untracked() else {
+                return;
+            };
+            let save_args = SaveFileArgs {
+                filter_name: "PDF".to_string(),
+                extensions: vec!["pdf".to_string()],
+            };
+            let Ok(save_args) = to_value(&save_args) else {
+                return;
+            };
+            let Ok(output) = invoke::<String>("select_save_file", &save_args).await else {
+                return;
+            };
+            let args = ReorderPagesArgs {
+                path,
+                new_order,
+                output,
+            };
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            match invoke::<()>("reorder_pages", &args).await {
+                Ok(()) => {
+                    error.set(None);
+                    order.set(None);
+                }
+                Err(err) => error.set(Some(err.to_string())),
+            }
+        }
+    });
+
+    view! {
+        <nav
+            aria-label=move || t!("thumbnails.label")
+            class="absolute top-20 bottom-20 left-4 flex flex-col gap-2 overflow-y-auto p-2 bg-base-100 rounded-box shadow"
+        >
+            <Show when=move || order.with(Option::is_some)>
+                <div class="flex flex-col gap-1">
+                    <button
+                        class="btn btn-primary btn-xs"
+                        disabled=move || save_order.pending()()
+                        on:click=move |_| save_order.dispatch(())
+                    >
+                        {move || t!("thumbnails.save_order")}
+                    </button>
+                    <button class="btn btn-ghost btn-xs" on:click=move |_| order.set(None)>
+                        {move || t!("thumbnails.undo")}
+                    </button>
+                </div>
+            </Show>
+            {move || error().map(|error| view! { <p class="text-error text-xs max-w-[120px]">{error}</p> })}
+            <For
+                each=pages
+                key=|page| *page
+                children=move |page| {
+                    view! {
+                        <button
+                            class="flex flex-col items-center gap-1 p-1 rounded border-2 border-transparent"
+                            class=("border-primary", move || current_page() == page)
+                            title=move || tooltip(page)
+                            aria-label=move || t!("thumbnails.go_to_page", page)
+                            aria-current=move || (current_page() == page).then_some("page")
+                            draggable="true"
+                            on:dragstart=move |_| dragged.set(Some(page))
+                            on:dragover=move |ev| ev.prevent_default()
+                            on:drop=move |ev| {
+                                ev.prevent_default();
+                                move_before(page);
+                            }
+                            on:click=move |_| {
+                                page_number(page);
+                                if let Some(on_select) = on_select {
+                                    on_select.call(page);
+                                }
+                            }
+                        >
+                            {move || match image_url(page) {
+                                Some(url) => {
+                                    view! {
+                                        <img
+                                            src=url
+                                            alt=t!("thumbnails.thumbnail_alt", page)
+                                            style="max-width: 120px; height: auto;"
+                                        />
+                                    }
+                                        .into_view()
+                                }
+                                None => {
+                                    view! { <div class="skeleton" style="width: 120px; height: 160px;"></div> }
+                                        .into_view()
+                                }
+                            }}
+                            <span class="text-xs">{page}</span>
+                        </button>
+                    }
+                }
+            />
+
+        </nav>
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use leptos::*;
+use uuid::Uuid;
+
+const DEFAULT_TOAST_DURATION_MS: u32 = 4000;
+const ERROR_TOAST_DURATION_MS: u32 = 8000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Success,
+    Error,
+    Warning,
+    Info,
+}
+
+impl ToastKind {
+    fn alert_class(self) -> &'static str {
+        match self {
+            ToastKind::Success => "alert-success",
+            ToastKind::Error => "alert-error",
+            ToastKind::Warning => "alert-warning",
+            ToastKind::Info => "alert-info",
+        }
+    }
+
+    fn duration_ms(self) -> u32 {
+        match self {
+            ToastKind::Error => ERROR_TOAST_DURATION_MS,
+            _ => DEFAULT_TOAST_DURATION_MS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    pub id: Uuid,
+    pub message: String,
+    pub kind: ToastKind,
+    pub duration_ms: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Toasts(RwSignal<Vec<Toast>>);
+
+impl Toasts {
+    fn dismiss(self, id: Uuid) {
+        self.0
+            .update(|toasts| toasts.retain(|toast| toast.id != id));
+    }
+}
+
+pub fn provide_toasts() {
+    provide_context(Toasts(create_rw_signal(Vec::new())));
+}
+
+pub fn use_toasts() -> Toasts {
+    expect_context::<Toasts>()
+}
+
+pub fn push_toast(toasts: Toasts, message: impl Into<String>, kind: ToastKind) {
+    let toast = Toast {
+        id: Uuid::new_v4(),
+        message: message.into(),
+        kind,
+        duration_ms: kind.duration_ms(),
+    };
+    let id = toast.id;
+    let duration = Duration::from_millis(toast.duration_ms as u64);
+    toasts.0.update(|toasts| toasts.push(toast));
+    set_timeout(move || toasts.dismiss(id), duration);
+}
+
+#[component]
+pub fn ToastNotification() -> impl IntoView {
+    let toasts = use_toasts();
+
+    view! {
+        <div class="toast toast-end toast-bottom z-50">
+            <For
+                each=move || toasts.0.get()
+                key=|toast| toast.id
+                children=move |toast| {
+                    let id = toast.id;
+                    view! {
+                        <div class=format!("alert {}", toast.kind.alert_class())>
+                            <span>{toast.message}</span>
+                            <button
+                                class="btn btn-ghost btn-xs"
+                                on:click=move |_| toasts.dismiss(id)
+                            >
+                                "✕"
+                            </button>
+                        </div>
+                    }
+                }
+            />
+        </div>
+    }
+}
@@ -0,0 +1,124 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+
+use crate::app::i18n::t;
+use crate::app::tauri::{invoke, listen, EventListener};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveFileArgs {
+    filter_name: String,
+    extensions: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DownloadDocumentArgs {
+    url: String,
+    destination: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct DownloadProgress {
+    bytes_downloaded: u64,
+    total_bytes: Option<u64>,
+}
+
+#[component]
+pub fn UrlDialog(
+    #[prop(into)] on_open: Callback<String>,
+    #[prop(into)] on_close: Callback<()>,
+) -> impl IntoView {
+    let url = create_rw_signal(String::new());
+    let progress = create_rw_signal(None::<DownloadProgress>);
+    let error = create_rw_signal(None::<String>);
+
+    let listener = store_value(None::<EventListener>);
+    create_effect(move |_| {
+        on_cleanup(move || listener.set_value(None));
+        spawn_local(async move {
+            let callback = listen("download_progress", move |update: DownloadProgress| {
+                progress.set(Some(update));
+            })
+            .await
+            .unwrap();
+            listener.set_value(Some(callback));
+        });
+    });
+
+    let download = create_action(move |_: &()| async move {
+        error.set(None);
+        let save_args = SaveFileArgs {
+            filter_name: "PDF".to_string(),
+            extensions: vec!["pdf".to_string()],
+        };
+        let Ok(save_args) = to_value(&save_args) else {
+            return;
+        };
+        let Ok(destination) = invoke::<String>("select_save_file", &save_args).await else {
+            return;
+        };
+
+        let args = DownloadDocumentArgs {
+            url: url.get_untracked().trim().to_string(),
+            destination,
+        };
+        let Ok(args) = to_value(&args) else {
+            return;
+        };
+        match invoke::<String>("download_document", &args).await {
+            Ok(path) => {
+                on_open.call(path);
+                on_close.call(());
+            }
+            Err(err) => error.set(Some(err.to_string())),
+        }
+    });
+
+    let progress_label = move || {
+        progress.with(|progress| match progress {
+            Some(DownloadProgress {
+                bytes_downloaded,
+                total_bytes: Some(total),
+            }) if *total > 0 => format!("{}%", bytes_downloaded * 100 / total),
+            Some(progress) => format!("{} KB", progress.bytes_downloaded / 1024),
+            None => String::new(),
+        })
+    };
+
+    view! {
+        <dialog class="modal modal-open">
+            <div class="modal-box text-left">
+                <h3 class="font-bold text-lg">{move || t!("home.open_url")}</h3>
+                <div class="flex flex-col gap-4 py-4">
+                    <input
+                        type="url"
+                        class="input input-bordered w-full"
+                        placeholder=move || t!("url.placeholder")
+                        prop:value=url
+                        on:input=move |ev| url.set(event_target_value(&ev))
+                    />
+                    <Show when=move || download.pending()()>
+                        <div class="flex items-center gap-2">
+                            <span class="loading loading-spinner loading-sm"></span>
+                            <span class="text-sm">{progress_label}</span>
+                        </div>
+                    </Show>
+                    {move || error().map(|error| view! { <p class="text-error">{error}</p> })}
+                </div>
+                <div class="modal-action">
+                    <button class="btn" on:click=move |_| on_close.call(())>
+                        {move || t!("common.close")}
+                    </button>
+                    <button
+                        class="btn btn-primary"
+                        disabled=move || url.with(|url| url.trim().is_empty()) || download.pending()()
+                        on:click=move |_| download.dispatch(())
+                    >
+                        {move || t!("url.download")}
+                    </button>
+                </div>
+            </div>
+        </dialog>
+    }
+}
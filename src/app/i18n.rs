@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::{from_value, to_value};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::app::tauri::{invoke, log_error};
+
+// Catalogues ship with the frontend assets copied from `public/`.
+const CATALOGUE_DIR: &str = "/public/locales";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Locale {
+    #[default]
+    PtBr,
+    EnUs,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::PtBr, Locale::EnUs];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::PtBr => "pt-BR",
+            Locale::EnUs => "en-US",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Locale::PtBr => "Português (Brasil)",
+            Locale::EnUs => "English (US)",
+        }
+    }
+
+    pub fn parse(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|locale| locale.code() == code)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SetLocaleArgs {
+    locale: String,
+}
+
+#[derive(Clone, Copy)]
+pub struct I18n {
+    pub locale: ReadSignal<Locale>,
+    set_locale: WriteSignal<Locale>,
+    messages: Resource<Locale, HashMap<String, String>>,
+}
+
+// Every handle is copied from the same context, so the locale signal identifies it.
+impl PartialEq for I18n {
+    fn eq(&self, other: &Self) -> bool {
+        self.locale == other.locale
+    }
+}
+
+impl fmt::Debug for I18n {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("I18n")
+            .field("locale", &self.locale)
+            .finish_non_exhaustive()
+    }
+}
+
+impl I18n {
+    // Falls back to the key itself so a missing entry is visible instead of blank.
+    pub fn t(&self, key: &str) -> String {
+        self.messages
+            .with(|messages| {
+                messages
+                    .as_ref()
+                    .and_then(|messages| messages.get(key).cloned())
+            })
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    // Replaces each `{}` in the message with the next argument, in order.
+    pub fn format(&self, key: &str, args: &[String]) -> String {
+        let template = self.t(key);
+        let mut args = args.iter();
+        let mut message = String::with_capacity(template.len());
+        let mut rest = template.as_str();
+        while let Some((before, after)) = rest.split_once("{}") {
+            message.push_str(before);
+            message.push_str(args.next().map(String::as_str).unwrap_or("{}"));
+            rest = after;
+        }
+        message.push_str(rest);
+        message
+    }
+
+    pub fn change_locale(&self, locale: Locale) {
+        self.set_locale.set(locale);
+        spawn_local(async move {
+            let Ok(args) = to_value(&SetLocaleArgs {
+                locale: locale.code().to_string(),
+            }) else {
+                return;
+            };
+            if let Err(err) = invoke::<()>("set_locale", &args).await {
+                log_error(err.to_string());
+            }
+        });
+    }
+}
+
+pub fn provide_i18n() {
+    let (locale, set_locale) = create_signal(Locale::default());
+    let messages = create_resource(locale, load_messages);
+
+    spawn_local(async move {
+        if let Ok(code) = invoke::<String>("get_locale", &JsValue::default()).await {
+            if let Some(locale) = Locale::parse(&code) {
+                set_locale.set(locale);
+            }
+        }
+    });
+    create_effect(move |_| {
+        if let Some(root) = document().document_element() {
+            let _ = root.set_attribute("lang", locale().code());
+        }
+    });
+
+    provide_context(I18n {
+        locale,
+        set_locale,
+        messages,
+    });
+}
+
+pub fn use_i18n() -> I18n {
+    expect_context::<I18n>()
+}
+
+macro_rules! t {
+    ($key:expr) => {
+        $crate::app::i18n::use_i18n().t($key)
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::app::i18n::use_i18n().format($key, &[$($arg.to_string()),+])
+    };
+}
+pub(crate) use t;
+
+async fn load_messages(locale: Locale) -> HashMap<String, String> {
+    match fetch_messages(locale).await {
+        Ok(messages) => messages,
+        Err(err) => {
+            log_error(format!(
+                "Failed to load {} messages: {:?}",
+                locale.code(),
+                err
+            ));
+            HashMap::new()
+        }
+    }
+}
+
+async fn fetch_messages(locale: Locale) -> Result<HashMap<String, String>, JsValue> {
+    let url = format!("{}/{}.json", CATALOGUE_DIR, locale.code());
+    let response: web_sys::Response = JsFuture::from(window().fetch_with_str(&url))
+        .await?
+        .dyn_into()?;
+    let json = JsFuture::from(response.json()?).await?;
+    Ok(from_value(json)?)
+}
@@ -0,0 +1,699 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use js_sys::Array;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use web_sys::{Blob, BlobPropertyBag, Url};
+
+use crate::app::components::toast::{push_toast, use_toasts, ToastKind, Toasts};
+use crate::app::i18n::{use_i18n, I18n};
+use crate::app::tauri::{error_kind, invoke, listen, log_error, EventListener};
+
+static NEXT_SESSION_ID: AtomicUsize = AtomicUsize::new(1);
+const ENCRYPTED_DOCUMENT_ERROR: &str = "encrypted_document";
+const MAX_CACHED_PAGES: usize = 20;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrepareDocumentArgs {
+    path: String,
+    session_id: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[allow(dead_code)]
+struct ImageLoaded {
+    session_id: String,
+    page_number: u32,
+    path: String,
+    mime_type: String,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct PageUpdated {
+    session_id: String,
+    page_number: u32,
+    mime_type: String,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[allow(dead_code)]
+struct ConversionStarted {
+    session_id: String,
+    path: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct BlankPageSkipped {
+    session_id: String,
+    page_number: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct PageDeleted {
+    session_id: String,
+    page: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeletePageArgs {
+    document_id: String,
+    page: u32,
+    output: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct DocumentChanged {
+    path: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct PageErrors {
+    session_id: String,
+    failed_pages: Vec<u32>,
+    errors: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RetryPageArgs {
+    path: String,
+    session_id: String,
+    page: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrepareDocumentWithOptionsArgs {
+    path: String,
+    session_id: String,
+    options: RenderOptions,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DocumentPath {
+    path: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionIdArgs {
+    session_id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DocumentIdArgs {
+    document_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct RenderOptions {
+    pub grayscale: bool,
+    pub deskew: bool,
+    pub skip_blank_pages: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GetPageArgs {
+    path: String,
+    session_id: String,
+    page_number: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageError {
+    pub page_number: u32,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NotifyDocumentReadyArgs {
+    path: String,
+    name: String,
+    page_count: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+struct ViewportState {
+    last_page: u32,
+    zoom: f64,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ImageUrl {
+    pub page_number: u32,
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct Progress {
+    session_id: String,
+    pub current: u32,
+    pub total: u32,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentSession {
+    pub id: String,
+    pub path: String,
+    pub name: String,
+    pub images: RwSignal<Vec<ImageUrl>>,
+    pub page_number: RwSignal<u32>,
+    pub progress: RwSignal<Option<Progress>>,
+    pub encrypted: RwSignal<bool>,
+    pub converting: RwSignal<bool>,
+    pub options: RwSignal<RenderOptions>,
+    pub page_errors: RwSignal<Vec<PageError>>,
+    pub blank_pages: RwSignal<Vec<u32>>,
+    pub changed_on_disk: RwSignal<bool>,
+    pub document_id: RwSignal<Option<String>>,
+    pub viewport_restored: RwSignal<bool>,
+    pub restored_zoom: RwSignal<Option<f64>>,
+    i18n: I18n,
+    toasts: Toasts,
+    listeners: StoredValue<Vec<EventListener>>,
+}
+
+impl DocumentSession {
+    pub fn open(path: String) -> Self {
+        let id = format!(
+            "session-{}",
+            NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        let name = path.rsplit(['/', '\\']).next().unwrap_or(&path).to_string();
+        let session = Self {
+            id,
+            path,
+            name,
+            images: create_rw_signal(Vec::new()),
+            page_number: create_rw_signal(1),
+            progress: create_rw_signal(None),
+            encrypted: create_rw_signal(false),
+            converting: create_rw_signal(false),
+            options: create_rw_signal(RenderOptions::default()),
+            page_errors: create_rw_signal(Vec::new()),
+            blank_pages: create_rw_signal(Vec::new()),
+            changed_on_disk: create_rw_signal(false),
+            document_id: create_rw_signal(None),
+            viewport_restored: create_rw_signal(false),
+            restored_zoom: create_rw_signal(None),
+            i18n: use_i18n(),
+            toasts: use_toasts(),
+            listeners: store_value(Vec::new()),
+        };
+
+        session.listen_events();
+        session.prepare();
+        session.set_watched(true);
+        session
+    }
+
+    pub fn close(&self) {
+        self.listeners.set_value(Vec::new());
+        self.set_watched(false);
+        if let Some(document_id) = self.document_id.get_untracked() {
+            spawn_local(async move {
+                let Ok(args) = to_value(&DocumentIdArgs { document_id }) else {
+                    return;
+                };
+                if let Err(err) = invoke::<()>("close_document", &args).await {
+                    log_error(err.to_string());
+                }
+            });
+        }
+        self.images.update(|images| {
+            for image in images.drain(..) {
+                let _ = Url::revoke_object_url(&image.url);
+            }
+        });
+    }
+
+    fn listen_events(&self) {
+        let session_id = self.id.clone();
+        let listeners = self.listeners;
+        let images = self.images;
+        let current_page = self.page_number;
+        spawn_local(async move {
+            let callback = listen("image", move |image: ImageLoaded| {
+                if image.session_id != session_id {
+                    return;
+                }
+                let url = create_object_url(image.data, &image.mime_type);
+                let page_number = image.page_number;
+                images.update(|urls| {
+                    let index = urls.partition_point(|url| url.page_number < page_number);
+                    match urls.get_mut(index) {
+                        Some(image) if image.page_number == page_number => {
+                            let _ = Url::revoke_object_url(&image.url);
+                            image.url = url;
+                        }
+                        _ => urls.insert(index, ImageUrl { page_number, url }),
+                    }
+                    evict_pages(urls, current_page.get_untracked());
+                });
+            })
+            .await
+            .unwrap();
+            listeners.update_value(|listeners| listeners.push(callback));
+        });
+
+        let session_id = self.id.clone();
+        let listeners = self.listeners;
+        spawn_local(async move {
+            let callback = listen("page_updated", move |page: PageUpdated| {
+                if page.session_id != session_id {
+                    return;
+                }
+                let url = create_object_url(page.data, &page.mime_type);
+                images.update(|urls| {
+                    if let Some(image) = urls
+                        .iter_mut()
+                        .find(|image| image.page_number == page.page_number)
+                    {
+                        let _ = Url::revoke_object_url(&image.url);
+                        image.url = url;
+                    }
+                });
+            })
+            .await
+            .unwrap();
+            listeners.update_value(|listeners| listeners.push(callback));
+        });
+
+        let session_id = self.id.clone();
+        let listeners = self.listeners;
+        let progress = self.progress;
+        let converting = self.converting;
+        spawn_local(async move {
+            let callback = listen("progress", move |update: Progress| {
+                if update.session_id == session_id {
+                    converting.set(false);
+                    progress.set(Some(update));
+                }
+            })
+            .await
+            .unwrap();
+            listeners.update_value(|listeners| listeners.push(callback));
+        });
+
+        let session_id = self.id.clone();
+        let listeners = self.listeners;
+        spawn_local(async move {
+            let callback = listen("converting", move |update: ConversionStarted| {
+                if update.session_id == session_id {
+                    converting.set(true);
+                }
+            })
+            .await
+            .unwrap();
+            listeners.update_value(|listeners| listeners.push(callback));
+        });
+
+        let session_id = self.id.clone();
+        let listeners = self.listeners;
+        let blank_pages = self.blank_pages;
+        spawn_local(async move {
+            let callback = listen("blank_page_skipped", move |update: BlankPageSkipped| {
+                if update.session_id != session_id {
+                    return;
+                }
+                blank_pages.update(|pages| {
+                    if !pages.contains(&update.page_number) {
+                        pages.push(update.page_number);
+                    }
+                });
+            })
+            .await
+            .unwrap();
+            listeners.update_value(|listeners| listeners.push(callback));
+        });
+
+        let session_id = self.id.clone();
+        let listeners = self.listeners;
+        let blank_pages = self.blank_pages;
+        let page_errors = self.page_errors;
+        spawn_local(async move {
+            let callback = listen("page_deleted", move |deleted: PageDeleted| {
+                if deleted.session_id != session_id {
+                    return;
+                }
+                let shift = |page: u32| if page > deleted.page { page - 1 } else { page };
+                images.update(|urls| {
+                    urls.retain(|image| {
+                        let keep = image.page_number != deleted.page;
+                        if !keep {
+                            let _ = Url::revoke_object_url(&image.url);
+                        }
+                        keep
+                    });
+                    for image in urls.iter_mut() {
+                        image.page_number = shift(image.page_number);
+                    }
+                });
+                blank_pages.update(|pages| {
+                    pages.retain(|page| *page != deleted.page);
+                    pages.iter_mut().for_each(|page| *page = shift(*page));
+                });
+                page_errors.update(|errors| {
+                    errors.retain(|error| error.page_number != deleted.page);
+                    errors
+                        .iter_mut()
+                        .for_each(|error| error.page_number = shift(error.page_number));
+                });
+                progress.update(|progress| {
+                    if let Some(progress) = progress {
+                        progress.total = progress.total.saturating_sub(1);
+                        progress.current = progress.current.min(progress.total);
+                    }
+                });
+                current_page.update(|page| *page = shift(*page).max(1));
+            })
+            .await
+            .unwrap();
+            listeners.update_value(|listeners| listeners.push(callback));
+        });
+
+        let path = self.path.clone();
+        let listeners = self.listeners;
+        let changed_on_disk = self.changed_on_disk;
+        spawn_local(async move {
+            let callback = listen("document_changed", move |update: DocumentChanged| {
+                if update.path == path {
+                    changed_on_disk.set(true);
+                }
+            })
+            .await
+            .unwrap();
+            listeners.update_value(|listeners| listeners.push(callback));
+        });
+
+        let session_id = self.id.clone();
+        let listeners = self.listeners;
+        let page_errors = self.page_errors;
+        let i18n = self.i18n;
+        let toasts = self.toasts;
+        spawn_local(async move {
+            let callback = listen("page_errors", move |update: PageErrors| {
+                if update.session_id != session_id {
+                    return;
+                }
+                push_toast(
+                    toasts,
+                    i18n.format(
+                        "session.page_errors",
+                        &[update.failed_pages.len().to_string()],
+                    ),
+                    ToastKind::Warning,
+                );
+                page_errors.set(
+                    update
+                        .failed_pages
+                        .into_iter()
+                        .zip(update.errors)
+                        .map(|(page_number, message)| PageError {
+                            page_number,
+                            message,
+                        })
+                        .collect(),
+                );
+            })
+            .await
+            .unwrap();
+            listeners.update_value(|listeners| listeners.push(callback));
+        });
+    }
+
+    pub fn retry_page(&self, page_number: u32) {
+        let args = RetryPageArgs {
+            path: self.path.clone(),
+            session_id: self.id.clone(),
+            page: page_number,
+        };
+        let page_errors = self.page_errors;
+        let i18n = self.i18n;
+        let toasts = self.toasts;
+        spawn_local(async move {
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            match invoke::<()>("retry_page", &args).await {
+                Ok(()) => page_errors
+                    .update(|errors| errors.retain(|error| error.page_number != page_number)),
+                Err(err) => {
+                    log_error(err.to_string());
+                    push_toast(
+                        toasts,
+                        i18n.format(
+                            "session.render_failed",
+                            &[page_number.to_string(), err.to_string()],
+                        ),
+                        ToastKind::Error,
+                    );
+                }
+            }
+        });
+    }
+
+    pub fn reprocess(&self, options: RenderOptions) {
+        self.options.set(options);
+        self.images.update(|images| {
+            for image in images.drain(..) {
+                let _ = Url::revoke_object_url(&image.url);
+            }
+        });
+        self.progress.set(None);
+        self.page_errors.set(Vec::new());
+        self.blank_pages.set(Vec::new());
+
+        let path = self.path.clone();
+        let args = PrepareDocumentWithOptionsArgs {
+            path: path.clone(),
+            session_id: self.id.clone(),
+            options,
+        };
+        let cancel_args = SessionIdArgs {
+            session_id: self.id.clone(),
+        };
+        let document_id = self.document_id;
+        let i18n = self.i18n;
+        let toasts = self.toasts;
+        spawn_local(async move {
+            let (Ok(cancel_args), Ok(path_args), Ok(args)) = (
+                to_value(&cancel_args),
+                to_value(&DocumentPath { path }),
+                to_value(&args),
+            ) else {
+                return;
+            };
+            if let Err(err) = invoke::<()>("cancel_preparation", &cancel_args).await {
+                log_error(err.to_string());
+            }
+            if let Err(err) = invoke::<()>("clear_cache", &path_args).await {
+                log_error(err.to_string());
+                return;
+            }
+            match invoke::<String>("prepare_document_with_options", &args).await {
+                Ok(id) => document_id.set(Some(id)),
+                Err(err) => {
+                    log_error(err.to_string());
+                    push_toast(
+                        toasts,
+                        i18n.format("session.reprocess_failed", &[err.to_string()]),
+                        ToastKind::Error,
+                    );
+                }
+            }
+        });
+    }
+
+    pub fn delete_page(&self, page: u32) {
+        let Some(document_id) = self.document_id.get_untracked() else {
+            return;
+        };
+        let args = DeletePageArgs {
+            document_id,
+            page,
+            output: self.path.clone(),
+        };
+        let i18n = self.i18n;
+        let toasts = self.toasts;
+        spawn_local(async move {
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            match invoke::<()>("delete_page", &args).await {
+                Ok(()) => push_toast(
+                    toasts,
+                    i18n.format("session.page_deleted", &[page.to_string()]),
+                    ToastKind::Success,
+                ),
+                Err(err) => {
+                    log_error(err.to_string());
+                    push_toast(
+                        toasts,
+                        i18n.format(
+                            "session.delete_failed",
+                            &[page.to_string(), err.to_string()],
+                        ),
+                        ToastKind::Error,
+                    );
+                }
+            }
+        });
+    }
+
+    pub fn reload(&self) {
+        self.changed_on_disk.set(false);
+        self.reprocess(self.options.get_untracked());
+    }
+
+    fn set_watched(&self, watched: bool) {
+        let command = if watched {
+            "watch_document"
+        } else {
+            "unwatch_document"
+        };
+        let path = self.path.clone();
+        spawn_local(async move {
+            let Ok(args) = to_value(&DocumentPath { path }) else {
+                return;
+            };
+            if let Err(err) = invoke::<()>(command, &args).await {
+                log_error(err.to_string());
+            }
+        });
+    }
+
+    pub fn evict_distant_pages(&self, current_page: u32) {
+        self.images.update(|urls| evict_pages(urls, current_page));
+    }
+
+    pub fn request_page(&self, page_number: u32) {
+        let args = GetPageArgs {
+            path: self.path.clone(),
+            session_id: self.id.clone(),
+            page_number,
+        };
+        spawn_local(async move {
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            if let Err(err) = invoke::<bool>("get_page", &args).await {
+                log_error(err.to_string());
+            }
+        });
+    }
+
+    fn prepare(&self) {
+        let args = PrepareDocumentArgs {
+            path: self.path.clone(),
+            session_id: self.id.clone(),
+        };
+        let encrypted = self.encrypted;
+        let converting = self.converting;
+        let document_id = self.document_id;
+        let i18n = self.i18n;
+        let toasts = self.toasts;
+        let session = self.clone();
+        spawn_local(async move {
+            let Ok(args) = to_value(&args) else {
+                return;
+            };
+            match invoke::<String>("prepare_document", &args).await {
+                Ok(id) => {
+                    document_id.set(Some(id));
+                    session.notify_ready().await;
+                    session.restore_viewport().await;
+                }
+                Err(err) => {
+                    converting.set(false);
+                    if error_kind(&err) == Some(ENCRYPTED_DOCUMENT_ERROR) {
+                        encrypted.set(true);
+                    } else {
+                        log_error(err.to_string());
+                        push_toast(
+                            toasts,
+                            i18n.format("session.open_failed", &[err.to_string()]),
+                            ToastKind::Error,
+                        );
+                    }
+                }
+            }
+        });
+    }
+
+    // Only notifies when the user can't already see the viewer.
+    async fn notify_ready(&self) {
+        let document = document();
+        if !document.hidden() || document.has_focus().unwrap_or(true) {
+            return;
+        }
+        let page_count = self
+            .progress
+            .with_untracked(|progress| progress.as_ref().map(|progress| progress.total))
+            .unwrap_or_default()
+            .max(self.images.with_untracked(Vec::len) as u32);
+        let Ok(args) = to_value(&NotifyDocumentReadyArgs {
+            path: self.path.clone(),
+            name: self.name.clone(),
+            page_count,
+        }) else {
+            return;
+        };
+        if let Err(err) = invoke::<()>("notify_document_ready", &args).await {
+            log_error(err.to_string());
+        }
+    }
+
+    async fn restore_viewport(&self) {
+        let viewport = match to_value(&DocumentPath {
+            path: self.path.clone(),
+        }) {
+            Ok(args) => invoke::<Option<ViewportState>>("get_viewport", &args)
+                .await
+                .unwrap_or_else(|err| {
+                    log_error(err.to_string());
+                    None
+                }),
+            Err(_) => None,
+        };
+        if let Some(viewport) = viewport {
+            self.page_number.set(viewport.last_page.max(1));
+            self.restored_zoom.set(Some(viewport.zoom));
+        }
+        self.viewport_restored.set(true);
+    }
+}
+
+fn evict_pages(urls: &mut Vec<ImageUrl>, current_page: u32) {
+    if urls.len() <= MAX_CACHED_PAGES {
+        return;
+    }
+    let radius = (MAX_CACHED_PAGES / 2) as u32;
+    urls.retain(|image| {
+        let keep = image.page_number.abs_diff(current_page) <= radius;
+        if !keep {
+            let _ = Url::revoke_object_url(&image.url);
+        }
+        keep
+    });
+}
+
+pub fn create_object_url(data: Vec<u8>, mime_type: &str) -> String {
+    let array = Array::new();
+    array.push(&js_sys::Uint8Array::from(&data[..]));
+
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = Blob::new_with_u8_array_sequence_and_options(&array, &options).unwrap();
+    Url::create_object_url_with_blob(&blob).unwrap()
+}
@@ -0,0 +1,230 @@
+use leptos::*;
+use wasm_bindgen::JsCast;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortcutAction {
+    ZoomIn,
+    ZoomOut,
+    ResetZoom,
+    NextPage,
+    PreviousPage,
+    FirstPage,
+    LastPage,
+    ToggleFullscreen,
+    Print,
+    Search,
+    CopyPage,
+    FitWidth,
+    FitHeight,
+    ActualSize,
+    ToggleTheme,
+    ContextMenu,
+    ShortcutReference,
+    CommandPalette,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub key: &'static str,
+    pub ctrl: bool,
+    pub shift: bool,
+}
+
+impl KeyBinding {
+    const fn key(key: &'static str) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+        }
+    }
+
+    const fn ctrl(key: &'static str) -> Self {
+        Self {
+            key,
+            ctrl: true,
+            shift: false,
+        }
+    }
+
+    const fn shift(self) -> Self {
+        Self {
+            shift: true,
+            ..self
+        }
+    }
+
+    // Shift is only checked when the binding asks for it, since keys like `+` need it anyway.
+    fn matches(&self, ev: &ev::KeyboardEvent) -> bool {
+        ev.key().eq_ignore_ascii_case(self.key)
+            && (ev.ctrl_key() || ev.meta_key()) == self.ctrl
+            && (!self.shift || ev.shift_key())
+            && !ev.alt_key()
+    }
+
+    pub fn label(&self) -> String {
+        let key = match self.key {
+            "ArrowRight" => "→",
+            "ArrowLeft" => "←",
+            "ArrowUp" => "↑",
+            "ArrowDown" => "↓",
+            key => key,
+        };
+        let mut parts = Vec::new();
+        if self.ctrl {
+            parts.push("Ctrl".to_string());
+        }
+        if self.shift {
+            parts.push("Shift".to_string());
+        }
+        parts.push(match key.chars().count() {
+            1 => key.to_uppercase(),
+            _ => key.to_string(),
+        });
+        parts.join("+")
+    }
+}
+
+pub struct Shortcut {
+    pub action: ShortcutAction,
+    pub description: &'static str,
+    pub bindings: &'static [KeyBinding],
+}
+
+// Every key handler dispatches through this table, so the reference overlay always lists
+// the bindings that are actually in effect.
+pub const SHORTCUTS: &[Shortcut] = &[
+    Shortcut {
+        action: ShortcutAction::ZoomIn,
+        description: "shortcuts.zoom_in",
+        bindings: &[KeyBinding::key("+"), KeyBinding::key("=")],
+    },
+    Shortcut {
+        action: ShortcutAction::ZoomOut,
+        description: "shortcuts.zoom_out",
+        bindings: &[KeyBinding::key("-")],
+    },
+    Shortcut {
+        action: ShortcutAction::ResetZoom,
+        description: "shortcuts.reset_zoom",
+        bindings: &[KeyBinding::key("0")],
+    },
+    Shortcut {
+        action: ShortcutAction::NextPage,
+        description: "shortcuts.next_page",
+        bindings: &[
+            KeyBinding::key("ArrowRight"),
+            KeyBinding::key("ArrowDown"),
+            KeyBinding::key("PageDown"),
+        ],
+    },
+    Shortcut {
+        action: ShortcutAction::PreviousPage,
+        description: "shortcuts.previous_page",
+        bindings: &[
+            KeyBinding::key("ArrowLeft"),
+            KeyBinding::key("ArrowUp"),
+            KeyBinding::key("PageUp"),
+        ],
+    },
+    Shortcut {
+        action: ShortcutAction::FirstPage,
+        description: "shortcuts.first_page",
+        bindings: &[KeyBinding::key("Home")],
+    },
+    Shortcut {
+        action: ShortcutAction::LastPage,
+        description: "shortcuts.last_page",
+        bindings: &[KeyBinding::key("End")],
+    },
+    Shortcut {
+        action: ShortcutAction::ToggleFullscreen,
+        description: "shortcuts.fullscreen",
+        bindings: &[KeyBinding::key("F11")],
+    },
+    Shortcut {
+        action: ShortcutAction::Print,
+        description: "shortcuts.print",
+        bindings: &[KeyBinding::ctrl("p")],
+    },
+    Shortcut {
+        action: ShortcutAction::Search,
+        description: "shortcuts.search",
+        bindings: &[KeyBinding::ctrl("f")],
+    },
+    Shortcut {
+        action: ShortcutAction::CopyPage,
+        description: "shortcuts.copy_page",
+        bindings: &[KeyBinding::ctrl("c")],
+    },
+    Shortcut {
+        action: ShortcutAction::FitWidth,
+        description: "shortcuts.fit_width",
+        bindings: &[KeyBinding::key("w")],
+    },
+    Shortcut {
+        action: ShortcutAction::FitHeight,
+        description: "shortcuts.fit_height",
+        bindings: &[KeyBinding::key("h")],
+    },
+    Shortcut {
+        action: ShortcutAction::ActualSize,
+        description: "shortcuts.actual_size",
+        bindings: &[KeyBinding::key("1")],
+    },
+    Shortcut {
+        action: ShortcutAction::ToggleTheme,
+        description: "shortcuts.toggle_theme",
+        bindings: &[KeyBinding::ctrl("l").shift()],
+    },
+    Shortcut {
+        action: ShortcutAction::ContextMenu,
+        description: "shortcuts.context_menu",
+        bindings: &[
+            KeyBinding::key("ContextMenu"),
+            KeyBinding::key("F10").shift(),
+        ],
+    },
+    Shortcut {
+        action: ShortcutAction::ShortcutReference,
+        description: "shortcuts.reference",
+        bindings: &[
+            KeyBinding::ctrl("/"),
+            KeyBinding::ctrl("?"),
+            KeyBinding::key("F1"),
+        ],
+    },
+    Shortcut {
+        action: ShortcutAction::CommandPalette,
+        description: "shortcuts.command_palette",
+        bindings: &[KeyBinding::ctrl("k")],
+    },
+];
+
+pub fn shortcut_action(ev: &ev::KeyboardEvent) -> Option<ShortcutAction> {
+    SHORTCUTS
+        .iter()
+        .find(|shortcut| shortcut.bindings.iter().any(|binding| binding.matches(ev)))
+        .map(|shortcut| shortcut.action)
+}
+
+pub fn is_typing(ev: &ev::KeyboardEvent) -> bool {
+    let Some(target) = ev.target() else {
+        return false;
+    };
+    target.has_type::<web_sys::HtmlInputElement>()
+        || target.has_type::<web_sys::HtmlTextAreaElement>()
+        || target.has_type::<web_sys::HtmlSelectElement>()
+        || target
+            .dyn_ref::<web_sys::HtmlElement>()
+            .is_some_and(web_sys::HtmlElement::is_content_editable)
+}
+
+// Copy shortcuts leave selected text to the browser instead of copying the page image.
+pub fn has_text_selection() -> bool {
+    window()
+        .get_selection()
+        .ok()
+        .flatten()
+        .is_some_and(|selection| selection.to_string().length() > 0)
+}
@@ -0,0 +1,176 @@
+use anyhow::{anyhow, Result};
+use leptos::logging::log;
+use leptos::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_wasm_bindgen::to_value;
+use wasm_bindgen::{prelude::*, JsCast};
+
+#[wasm_bindgen(js_namespace = ["window"])]
+extern "C" {
+    #[derive(Debug, Clone)]
+    type TauriInstance;
+    #[wasm_bindgen(js_name = "__TAURI__")]
+    static TAURI_INSTANCE: TauriInstance;
+
+    #[wasm_bindgen(getter, method)]
+    fn core(this: &TauriInstance) -> TauriCoreApi;
+
+    #[wasm_bindgen(getter, method)]
+    fn event(this: &TauriInstance) -> TauriEventApi;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    type TauriCoreApi;
+
+    #[wasm_bindgen(catch, method)]
+    async fn invoke(this: &TauriCoreApi, fn_name: &str, args: &JsValue)
+        -> Result<JsValue, JsValue>;
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[derive(Debug, Clone)]
+    type TauriEventApi;
+
+    #[wasm_bindgen(catch, method)]
+    async fn listen(
+        this: &TauriEventApi,
+        event_name: &str,
+        callback: &Closure<dyn FnMut(JsValue)>,
+    ) -> Result<JsValue, JsValue>;
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct EventData<T> {
+    event: String,
+    payload: T,
+}
+
+#[derive(Debug)]
+pub struct EventListener {
+    _callback: Closure<dyn FnMut(JsValue)>,
+    unlisten: js_sys::Function,
+}
+
+impl Drop for EventListener {
+    fn drop(&mut self) {
+        if let Err(err) = self.unlisten.call0(&JsValue::NULL) {
+            log!("Failed to remove event listener: {:?}", err);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommandError {
+    pub kind: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+pub fn error_kind(err: &anyhow::Error) -> Option<&str> {
+    err.downcast_ref::<CommandError>()
+        .map(|error| error.kind.as_str())
+}
+
+#[derive(Serialize, Deserialize)]
+struct Log {
+    message: String,
+}
+
+pub fn log_trace(message: &String) {
+    let args = to_value(&Log {
+        message: message.to_string(),
+    })
+    .unwrap();
+    spawn_local(async move {
+        if let Err(err) = invoke::<String>("log_trace", &args).await {
+            log!("{}", err.to_string());
+        }
+    });
+}
+
+pub fn log_warn(message: &String) {
+    let args = to_value(&Log {
+        message: message.to_string(),
+    })
+    .unwrap();
+    spawn_local(async move {
+        if let Err(err) = invoke::<String>("log_warn", &args).await {
+            log!("{}", err.to_string());
+        }
+    });
+}
+
+pub fn log_error(message: String) {
+    let args = to_value(&Log { message }).unwrap();
+    spawn_local(async move {
+        if let Err(err) = invoke::<String>("log_error", &args).await {
+            log!("{}", err.to_string());
+        }
+    });
+}
+
+pub async fn listen<F, T>(event_name: &str, mut callback: F) -> Result<EventListener>
+where
+    F: FnMut(T) + 'static,
+    T: DeserializeOwned,
+{
+    let callback = Closure::new(move |data: JsValue| {
+        let data: Result<EventData<T>> =
+            serde_wasm_bindgen::from_value(data).map_err(|err| anyhow!("{:?}", err));
+        match data {
+            Ok(data) => callback(data.payload),
+            Err(err) => log_error(err.to_string()),
+        }
+    });
+
+    let unlisten = TAURI_INSTANCE
+        .event()
+        .listen(event_name, &callback)
+        .await
+        .map_err(|err| anyhow!("{:?}", err))?
+        .dyn_into::<js_sys::Function>()
+        .map_err(|err| anyhow!("{:?}", err))?;
+
+    Ok(EventListener {
+        _callback: callback,
+        unlisten,
+    })
+}
+
+pub async fn invoke<T>(fn_name: &str, args: &JsValue) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let result = TAURI_INSTANCE
+        .core()
+        .invoke(fn_name, args)
+        .await
+        .map_err(
+            |err| match serde_wasm_bindgen::from_value::<CommandError>(err.clone()) {
+                Ok(error) => anyhow::Error::new(error),
+                Err(_) => anyhow!("{:?}", err),
+            },
+        )?;
+
+    let output: Result<T> =
+        serde_wasm_bindgen::from_value(result).map_err(|err| anyhow!("{:?}", err));
+
+    match output {
+        Ok(data) => Ok(data),
+        Err(err) => {
+            log_error(err.to_string());
+            Err(err)
+        }
+    }
+}
@@ -8,6 +8,9 @@ module.exports = {
   },
   plugins: [require("@tailwindcss/typography"), require("daisyui")],
   daisyui: {
-    themes: ["valentine"],
+    themes: [
+      { light: require("daisyui/src/theming/themes")["valentine"] },
+      "dark",
+    ],
   },
 }
\ No newline at end of file